@@ -14,7 +14,7 @@ static EDGE_SIZE: usize = std::env::var("EDGE_SIZE")
     .parse()
     .unwrap();
 
-criterion_group!(benches, tree_backed, adjacent_list);
+criterion_group!(benches, tree_backed, adjacent_list, tree_backed_unboxed);
 criterion_main!(benches);
 
 fn tree_backed(c: &mut Criterion) {
@@ -25,6 +25,50 @@ fn adjacent_list(c: &mut Criterion) {
     cases::<AdjacentListGraph>(c, "adjacent_list");
 }
 
+fn tree_backed_unboxed(c: &mut Criterion) {
+    let vertex_size = *VERTEX_SIZE;
+    let edge_size = *EDGE_SIZE;
+    let mut g = TreeBackedGraph::with_capacity(vertex_size, edge_size);
+    let mut vertices = vec![];
+    for _ in 0..vertex_size {
+        let vid = g.add_vertex();
+        vertices.push(vid);
+    }
+    for _ in 0..edge_size {
+        let v0 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        let v1 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        let _ = g.add_edge(v0, v1);
+    }
+    c.bench_function("tree_backed/iter_edges boxed", |b| {
+        b.iter(|| iter_edges(&g))
+    });
+    c.bench_function("tree_backed/iter_edges unboxed", |b| {
+        b.iter(|| {
+            for x in g.edges_iter() {
+                black_box(x.id.to_raw());
+            }
+        })
+    });
+    c.bench_function("tree_backed/out_edges boxed", |b| {
+        b.iter(|| {
+            for v in vertices.iter() {
+                for x in g.out_edges(v) {
+                    black_box(x.id.to_raw());
+                }
+            }
+        })
+    });
+    c.bench_function("tree_backed/out_edges unboxed", |b| {
+        b.iter(|| {
+            for v in vertices.iter() {
+                for x in g.out_edges_iter(v) {
+                    black_box(x.id.to_raw());
+                }
+            }
+        })
+    });
+}
+
 fn cases<G>(c: &mut Criterion, prefix: &str)
 where
     G: GrowableGraph + QueryableGraph + EdgeShrinkableGraph + VertexShrinkableGraph + Clone,
@@ -40,7 +84,7 @@ where
         b.iter(|| add_vertices_and_edges::<G>(vertex_size, edge_size))
     });
 
-    let mut g = G::new();
+    let mut g = G::with_capacity(vertex_size, edge_size);
     let mut vertices = vec![];
     let mut edges = vec![];
     for _ in 0..vertex_size {
@@ -79,7 +123,7 @@ fn add_vertices<G>(vertex_size: usize)
 where
     G: GrowableGraph,
 {
-    let mut g = G::new();
+    let mut g = G::with_capacity(vertex_size, 0);
     for _ in 0..vertex_size {
         let _ = g.add_vertex();
     }
@@ -89,7 +133,7 @@ fn add_vertices_and_edges<G>(vertex_size: usize, edge_size: usize)
 where
     G: GrowableGraph,
 {
-    let mut g = G::new();
+    let mut g = G::with_capacity(vertex_size, edge_size);
     let mut vertices = vec![];
     for _ in 0..vertex_size {
         let vid = g.add_vertex();
@@ -0,0 +1,60 @@
+use algograph::{
+    algorithm::MultiSourceBfs,
+    graph::{directed::*, *},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use static_init::dynamic;
+
+#[dynamic]
+static VERTEX_SIZE: usize = std::env::var("VERTEX_SIZE")
+    .unwrap_or("100000".to_string())
+    .parse()
+    .unwrap();
+#[dynamic]
+static EDGE_SIZE: usize = std::env::var("EDGE_SIZE")
+    .unwrap_or("1000000".to_string())
+    .parse()
+    .unwrap();
+#[dynamic]
+static SOURCE_SIZE: usize = std::env::var("SOURCE_SIZE")
+    .unwrap_or("10".to_string())
+    .parse()
+    .unwrap();
+
+criterion_group!(benches, multi_source_bfs);
+criterion_main!(benches);
+
+fn multi_source_bfs(c: &mut Criterion) {
+    let vertex_size = *VERTEX_SIZE;
+    println!("VERTEX_SIZE: {}", vertex_size);
+    let edge_size = *EDGE_SIZE;
+    println!("EDGE_SIZE: {}", edge_size);
+    let source_size = *SOURCE_SIZE;
+    println!("SOURCE_SIZE: {}", source_size);
+
+    let mut g = TreeBackedGraph::with_capacity(vertex_size, edge_size);
+    let vertices: Vec<_> = (0..vertex_size).map(|_| g.add_vertex()).collect();
+    for _ in 0..edge_size {
+        let v0 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        let v1 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        g.add_edge(v0, v1);
+    }
+    let sources: Vec<_> = (0..source_size)
+        .map(|_| vertices[rand::thread_rng().gen::<usize>() % vertices.len()])
+        .collect();
+    let frozen = g.clone().freeze();
+
+    let mut group = c.benchmark_group("multi_source_bfs");
+    group.sample_size(10);
+    group.bench_function("TreeBackedGraph", |b| {
+        b.iter(|| black_box(g.multi_source_bfs(sources.iter().copied()).len()))
+    });
+    group.bench_function("FrozenGraph", |b| {
+        b.iter(|| black_box(frozen.multi_source_bfs(sources.iter().copied()).len()))
+    });
+    group.bench_function("FrozenGraph in parallel", |b| {
+        b.iter(|| black_box(frozen.par_multi_source_bfs(sources.iter().copied()).len()))
+    });
+    group.finish();
+}
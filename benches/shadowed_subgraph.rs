@@ -0,0 +1,57 @@
+use algograph::graph::{directed::*, *};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use static_init::dynamic;
+
+#[dynamic]
+static VERTEX_SIZE: usize = std::env::var("VERTEX_SIZE")
+    .unwrap_or("1000".to_string())
+    .parse()
+    .unwrap();
+#[dynamic]
+static EDGE_SIZE: usize = std::env::var("EDGE_SIZE")
+    .unwrap_or("20000".to_string())
+    .parse()
+    .unwrap();
+
+criterion_group!(benches, out_edges_and_degrees);
+criterion_main!(benches);
+
+fn out_edges_and_degrees(c: &mut Criterion) {
+    let vertex_size = *VERTEX_SIZE;
+    println!("VERTEX_SIZE: {}", vertex_size);
+    let edge_size = *EDGE_SIZE;
+    println!("EDGE_SIZE: {}", edge_size);
+
+    let mut g = TreeBackedGraph::with_capacity(vertex_size, edge_size);
+    let vertices: Vec<_> = (0..vertex_size).map(|_| g.add_vertex()).collect();
+    let mut edges = vec![];
+    for _ in 0..edge_size {
+        let v0 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        let v1 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        edges.push(g.add_edge(v0, v1));
+    }
+    let mut shadowed = ShadowedSubgraph::new(&g);
+    for e in edges.iter().step_by(2) {
+        shadowed.remove_edge(e);
+    }
+
+    let mut group = c.benchmark_group("shadowed_subgraph");
+    group.bench_function("out_edges", |b| {
+        b.iter(|| {
+            for v in vertices.iter() {
+                for e in shadowed.out_edges(v) {
+                    black_box(e.id.to_raw());
+                }
+            }
+        })
+    });
+    group.bench_function("out_degree", |b| {
+        b.iter(|| {
+            for v in vertices.iter() {
+                black_box(shadowed.out_degree(v));
+            }
+        })
+    });
+    group.finish();
+}
@@ -0,0 +1,55 @@
+use algograph::{
+    algorithm::SimpleCycles,
+    graph::{directed::*, *},
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use rand::Rng;
+use static_init::dynamic;
+
+#[dynamic]
+static VERTEX_SIZE: usize = std::env::var("VERTEX_SIZE")
+    .unwrap_or("1000".to_string())
+    .parse()
+    .unwrap();
+#[dynamic]
+static EDGE_SIZE: usize = std::env::var("EDGE_SIZE")
+    .unwrap_or("20000".to_string())
+    .parse()
+    .unwrap();
+
+criterion_group!(benches, dense_cyclic);
+criterion_main!(benches);
+
+fn dense_cyclic(c: &mut Criterion) {
+    let vertex_size = *VERTEX_SIZE;
+    println!("VERTEX_SIZE: {}", vertex_size);
+    let edge_size = *EDGE_SIZE;
+    println!("EDGE_SIZE: {}", edge_size);
+
+    let mut g = TreeBackedGraph::with_capacity(vertex_size, edge_size);
+    let vertices: Vec<_> = (0..vertex_size).map(|_| g.add_vertex()).collect();
+    // a ring guarantees that every vertex is on some cycle.
+    for (v0, v1) in vertices.iter().zip(vertices.iter().cycle().skip(1)) {
+        g.add_edge(*v0, *v1);
+    }
+    for _ in vertex_size..edge_size {
+        let v0 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        let v1 = vertices[rand::thread_rng().gen::<usize>() % vertices.len()];
+        g.add_edge(v0, v1);
+    }
+
+    c.bench_function("simple_cycles/dense_cyclic", |b| {
+        b.iter(|| simple_cycles(&g))
+    });
+}
+
+fn simple_cycles<G>(g: &G)
+where
+    G: QueryableGraph,
+{
+    for cycle in g.simple_cycles() {
+        for e in cycle {
+            black_box(e.id.to_raw());
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use super::ConnectedComponents;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{HashMap, VecDeque};
+
+/// Trait and default implementation of edge betweenness and Girvan-Newman clustering.
+pub trait EdgeBetweenness
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Betweenness of every edge, i.e., the sum over ordered pairs of distinct vertices $(s, t)$
+    /// of the fraction of shortest paths from $s$ to $t$ along out-edges passing through the edge.
+    ///
+    /// Parallel edges make distinct shortest paths,
+    /// so they share what a single edge would have.
+    /// Self-loops are never on shortest paths and thus of betweenness 0.
+    /// On undirected graphs, each unordered pair is counted twice, once in each direction.
+    ///
+    /// It accumulates dependencies backwards from each source as Brandes' algorithm does,
+    /// in $O(\|V\| \|E\|)$.
+    fn edge_betweenness(&self) -> HashMap<EdgeId, f64, RandomState> {
+        let mut res = HashMap::with_hasher(new_random_state());
+        for e in self.iter_edges() {
+            res.insert(e.id, 0.0);
+        }
+        let mut vertices: Vec<_> = self.iter_vertices().collect();
+        vertices.sort();
+        let mut index = HashMap::with_hasher(new_random_state());
+        index.extend(vertices.iter().enumerate().map(|(i, v)| (*v, i)));
+        let n = vertices.len();
+        for s in 0..n {
+            let mut dist = vec![usize::MAX; n];
+            let mut sigma = vec![0.0f64; n];
+            let mut preds: Vec<Vec<(usize, EdgeId)>> = vec![vec![]; n];
+            let mut order = vec![];
+            let mut queue = VecDeque::new();
+            dist[s] = 0;
+            sigma[s] = 1.0;
+            queue.push_back(s);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                self.for_each_out_edge(&vertices[v], |e| {
+                    let w = index[&e.sink];
+                    if dist[w] == usize::MAX {
+                        dist[w] = dist[v] + 1;
+                        queue.push_back(w);
+                    }
+                    if dist[w] == dist[v] + 1 {
+                        sigma[w] += sigma[v];
+                        preds[w].push((v, e.id));
+                    }
+                });
+            }
+            let mut delta = vec![0.0f64; n];
+            for w in order.into_iter().rev() {
+                for (v, e) in preds[w].iter() {
+                    let c = sigma[*v] / sigma[w] * (1.0 + delta[w]);
+                    *res.get_mut(e).unwrap() += c;
+                    delta[*v] += c;
+                }
+            }
+        }
+        res
+    }
+
+    /// Communities by Girvan-Newman clustering.
+    ///
+    /// Edges of the highest [EdgeBetweenness::edge_betweenness] are removed one at a time,
+    /// with betweenness recomputed after each removal,
+    /// until there are at least `target_communities` connected components
+    /// or no edges are left.
+    /// Ties are broken by removing the edge of the smallest ID.
+    /// The graph itself is untouched, as edges are removed from a [ShadowedSubgraph] of it.
+    ///
+    /// Communities are connected components of what is left, as in [ConnectedComponents],
+    /// each of which is sorted, and they are in the order of their smallest vertices.
+    fn girvan_newman(&self, target_communities: usize) -> Vec<Vec<VertexId>> {
+        let mut sub = ShadowedSubgraph::new(self);
+        loop {
+            let connectivity = sub.connectivity();
+            if connectivity.component_count() >= target_communities || sub.edge_size() == 0 {
+                let mut vertices: Vec<_> = sub.iter_vertices().collect();
+                vertices.sort();
+                let mut res = vec![vec![]; connectivity.component_count()];
+                for v in vertices {
+                    res[connectivity.component_of(&v).unwrap()].push(v);
+                }
+                return res;
+            }
+            let mut betweenness: Vec<_> = sub.edge_betweenness().into_iter().collect();
+            betweenness.sort_by_key(|(e, _)| *e);
+            let mut highest: Option<(EdgeId, f64)> = None;
+            for (e, x) in betweenness {
+                if highest.map_or(true, |(_, y)| x > y) {
+                    highest = Some((e, x));
+                }
+            }
+            if let Some((e, _)) = highest {
+                sub.remove_edge(&e);
+            }
+        }
+    }
+}
+
+impl<G: QueryableGraph> EdgeBetweenness for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::{Ops, TreeBackedGraph};
+    use quickcheck_macros::quickcheck;
+
+    /// Two cliques of 4 vertices joined by a bridge.
+    fn barbell() -> (undirected::TreeBackedGraph, Vec<VertexId>, EdgeId) {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..8).map(|_| g.add_vertex()).collect();
+        for side in [&vs[..4], &vs[4..]] {
+            for (i, u) in side.iter().enumerate() {
+                for v in side[i + 1..].iter() {
+                    g.add_edge(*u, *v);
+                }
+            }
+        }
+        let bridge = g.add_edge(vs[3], vs[4]);
+        (g, vs, bridge)
+    }
+
+    #[test]
+    fn bridge_of_barbell() {
+        let (g, _, bridge) = barbell();
+        let betweenness = g.edge_betweenness();
+        assert_eq!(betweenness.len(), g.edge_size());
+        // every vertex on one side to every vertex on the other side, in both directions
+        assert!((betweenness[&bridge] - 32.0).abs() < 1e-9);
+        for (e, x) in betweenness.iter() {
+            if *e != bridge {
+                assert!(*x < betweenness[&bridge]);
+            }
+        }
+    }
+
+    #[test]
+    fn girvan_newman_on_barbell() {
+        let (g, vs, _) = barbell();
+        assert_eq!(g.girvan_newman(1), vec![vs.clone()]);
+        assert_eq!(g.girvan_newman(2), vec![vs[..4].to_vec(), vs[4..].to_vec()]);
+        let singletons: Vec<_> = vs.iter().map(|v| vec![*v]).collect();
+        assert_eq!(g.girvan_newman(100), singletons);
+        assert_eq!(g.edge_size(), 13);
+    }
+
+    #[test]
+    fn parallel_edges_and_self_loops() {
+        let mut g = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let e0 = g.add_edge(vs[0], vs[1]);
+        let e1 = g.add_edge(vs[0], vs[1]);
+        let e2 = g.add_edge(vs[1], vs[2]);
+        let e3 = g.add_edge(vs[1], vs[1]);
+        let betweenness = g.edge_betweenness();
+        // (0, 1) and (0, 2), split between parallel edges
+        assert!((betweenness[&e0] - 1.0).abs() < 1e-9);
+        assert!((betweenness[&e1] - 1.0).abs() < 1e-9);
+        // (0, 2) and (1, 2)
+        assert!((betweenness[&e2] - 2.0).abs() < 1e-9);
+        assert_eq!(betweenness[&e3], 0.0);
+    }
+
+    #[quickcheck]
+    fn sum_of_distances(ops: Ops) {
+        let g = MappedGraph::<TreeBackedGraph>::from(&ops).graph;
+        let trial: f64 = g.edge_betweenness().values().sum();
+        let mut oracle = 0;
+        for s in g.iter_vertices() {
+            for t in g.iter_vertices() {
+                if s != t {
+                    oracle += g.distance(&s, &t).unwrap_or(0);
+                }
+            }
+        }
+        assert!((trial - oracle as f64).abs() < 1e-6 * (1.0 + oracle as f64));
+    }
+}
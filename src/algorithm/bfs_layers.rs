@@ -0,0 +1,122 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashSet;
+
+/// Trait and default implementation of breadth-first search level by level.
+pub trait BfsLayers
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Iteration over levels of a breadth-first search along out-edges from `start`.
+    ///
+    /// The `d`-th yielded vector consists of vertices `d` hops away from `start`,
+    /// in the order of their discovery.
+    /// Thus, the first one is `[start]`,
+    /// unless `start` is not in the graph, where nothing is yielded.
+    ///
+    /// Levels are computed lazily, one per call to `.next()`.
+    fn bfs_layers(&self, start: &VertexId) -> Box<dyn Iterator<Item = Vec<VertexId>> + '_> {
+        let mut visited = HashSet::with_hasher(new_random_state());
+        let mut current = vec![];
+        if self.contains_vertex(start) {
+            visited.insert(*start);
+            current.push(*start);
+        }
+        Box::new(Layers {
+            graph: self,
+            visited,
+            current,
+        })
+    }
+}
+
+impl<G: QueryableGraph> BfsLayers for G {}
+
+struct Layers<'a, G> {
+    graph: &'a G,
+    visited: HashSet<VertexId, RandomState>,
+    current: Vec<VertexId>,
+}
+
+impl<'a, G: QueryableGraph> Iterator for Layers<'a, G> {
+    type Item = Vec<VertexId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.current.is_empty() {
+            return None;
+        }
+        let mut next = vec![];
+        for v in self.current.iter() {
+            for e in self.graph.out_edges(v) {
+                if self.visited.insert(e.sink) {
+                    next.push(e.sink);
+                }
+            }
+        }
+        Some(std::mem::replace(&mut self.current, next))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tree() {
+        //       0
+        //     /   \
+        //    1     2
+        //   / \     \
+        //  3   4     5
+        //            |
+        //            6
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..7).map(|_| g.add_vertex()).collect();
+        for (p, c) in [(0, 1), (0, 2), (1, 3), (1, 4), (2, 5), (5, 6)] {
+            g.add_edge(vs[p], vs[c]);
+        }
+        let trial: Vec<Vec<_>> = g
+            .bfs_layers(&vs[0])
+            .map(|mut layer| {
+                layer.sort();
+                layer
+            })
+            .collect();
+        assert_eq!(
+            trial,
+            vec![
+                vec![vs[0]],
+                vec![vs[1], vs[2]],
+                vec![vs[3], vs[4], vs[5]],
+                vec![vs[6]],
+            ]
+        );
+        for (d, layer) in trial.iter().enumerate() {
+            for v in layer.iter() {
+                assert_eq!(g.distance(&vs[0], v), Some(d));
+            }
+        }
+        let trial: Vec<_> = g.bfs_layers(&vs[2]).collect();
+        assert_eq!(trial, vec![vec![vs[2]], vec![vs[5]], vec![vs[6]]]);
+    }
+
+    #[test]
+    fn cycles_and_missing_start() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        for i in 0..4 {
+            g.add_edge(vs[i], vs[(i + 1) % 4]);
+        }
+        g.add_edge(vs[0], vs[0]);
+        let trial: Vec<Vec<_>> = g
+            .bfs_layers(&vs[0])
+            .map(|mut layer| {
+                layer.sort();
+                layer
+            })
+            .collect();
+        assert_eq!(trial, vec![vec![vs[0]], vec![vs[1], vs[3]], vec![vs[2]]]);
+        assert_eq!(g.bfs_layers(&VertexId(100)).count(), 0);
+    }
+}
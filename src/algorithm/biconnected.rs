@@ -0,0 +1,141 @@
+use super::undirected_view::undirected_incidence;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// Trait and default implementation of decomposing a graph into biconnected components.
+pub trait BiconnectedComponents
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Partitions edges of the undirected view of the graph into biconnected components.
+    ///
+    /// * Directions of edges are ignored.
+    /// * Parallel edges between two vertices are in the same component.
+    /// * Each self-loop is a component by itself.
+    ///
+    /// It is the depth-first search with low-links and a stack of edges, in $O(\|V\| + \|E\|)$.
+    fn biconnected_components(&self) -> Vec<Vec<EdgeId>> {
+        let incidence = undirected_incidence(self);
+        let mut res: Vec<Vec<EdgeId>> = self
+            .iter_edges()
+            .filter(|e| e.source == e.sink)
+            .map(|e| vec![e.id])
+            .collect();
+        // discovery times and low-links
+        let mut times: HashMap<VertexId, (usize, usize), RandomState> =
+            HashMap::with_hasher(new_random_state());
+        let mut edge_stack = vec![];
+        for root in incidence.keys() {
+            if times.contains_key(root) {
+                continue;
+            }
+            times.insert(*root, (times.len(), times.len()));
+            // vertices, edges from their parents, and indices of incident edges to visit
+            let mut stack: Vec<(VertexId, Option<EdgeId>, usize)> = vec![(*root, None, 0)];
+            while let Some((v, come_from, idx)) = stack.last_mut() {
+                let v = *v;
+                let come_from = *come_from;
+                if let Some((e, w)) = incidence[&v].get(*idx) {
+                    *idx += 1;
+                    if Some(*e) == come_from {
+                        continue;
+                    }
+                    let disc_v = times[&v].0;
+                    match times.get(w) {
+                        None => {
+                            edge_stack.push(*e);
+                            times.insert(*w, (times.len(), times.len()));
+                            stack.push((*w, Some(*e), 0));
+                        }
+                        Some((disc_w, _)) if *disc_w < disc_v => {
+                            // a back edge to an ancestor
+                            let disc_w = *disc_w;
+                            edge_stack.push(*e);
+                            let low_v = &mut times.get_mut(&v).unwrap().1;
+                            *low_v = (*low_v).min(disc_w);
+                        }
+                        Some(_) => {
+                            // the other side of a back edge from a descendant
+                        }
+                    }
+                } else {
+                    stack.pop();
+                    if let (Some((u, _, _)), Some(come_from)) = (stack.last(), come_from) {
+                        let low_v = times[&v].1;
+                        let (disc_u, low_u) = times.get_mut(u).unwrap();
+                        *low_u = (*low_u).min(low_v);
+                        if low_v >= *disc_u {
+                            let mut component = vec![];
+                            while let Some(e) = edge_stack.pop() {
+                                component.push(e);
+                                if e == come_from {
+                                    break;
+                                }
+                            }
+                            res.push(component);
+                        }
+                    }
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> BiconnectedComponents for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+    use std::collections::BTreeSet;
+
+    fn sorted(mut components: Vec<Vec<EdgeId>>) -> Vec<Vec<EdgeId>> {
+        for c in components.iter_mut() {
+            c.sort();
+        }
+        components.sort();
+        components
+    }
+
+    #[test]
+    fn two_triangles_sharing_a_cut_vertex() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        let e0 = g.add_edge(vs[0], vs[1]);
+        let e1 = g.add_edge(vs[1], vs[2]);
+        let e2 = g.add_edge(vs[2], vs[0]);
+        let e3 = g.add_edge(vs[2], vs[3]);
+        let e4 = g.add_edge(vs[3], vs[4]);
+        let e5 = g.add_edge(vs[4], vs[2]);
+        let trial = sorted(g.biconnected_components());
+        assert_eq!(trial, vec![vec![e0, e1, e2], vec![e3, e4, e5]]);
+    }
+
+    #[test]
+    fn bridges_parallel_edges_and_self_loops() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let e0 = g.add_edge(vs[0], vs[1]);
+        let e1 = g.add_edge(vs[1], vs[2]);
+        let e2 = g.add_edge(vs[2], vs[1]);
+        let e3 = g.add_edge(vs[2], vs[2]);
+        let trial = sorted(g.biconnected_components());
+        assert_eq!(trial, vec![vec![e0], vec![e1, e2], vec![e3]]);
+    }
+
+    #[quickcheck]
+    fn components_partition_edges(ops: Ops) {
+        let ops_formed: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let graph = &ops_formed.graph;
+        let components = graph.biconnected_components();
+        let trial: Vec<_> = components.iter().flatten().copied().collect();
+        let trial_set: BTreeSet<_> = trial.iter().copied().collect();
+        assert_eq!(trial.len(), trial_set.len());
+        let oracle: BTreeSet<_> = graph.iter_edges().map(|e| e.id).collect();
+        assert_eq!(trial_set, oracle);
+    }
+}
@@ -0,0 +1,135 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{HashMap, HashSet};
+
+/// Trait and default implementation of maximum bipartite matching.
+pub trait BipartiteMatching
+where
+    Self: QueryableGraph + Sized,
+{
+    /// A maximum matching between `left` and the rest of the vertices, as sorted edge ID's.
+    ///
+    /// Directions of edges are ignored.
+    /// Only edges crossing sides, i.e., with exactly one endpoint in `left`, are considered.
+    /// The others, including self-loops, are ignored.
+    /// Vertices in `left` but not in the graph are ignored as well.
+    ///
+    /// It repeatedly searches augmenting paths from unmatched left vertices,
+    /// which takes O(VE) time.
+    fn maximum_bipartite_matching(&self, left: &HashSet<VertexId, RandomState>) -> Vec<EdgeId> {
+        let mut adj: HashMap<VertexId, Vec<(EdgeId, VertexId)>, RandomState> =
+            HashMap::with_hasher(new_random_state());
+        for e in self.iter_edges() {
+            match (left.contains(&e.source), left.contains(&e.sink)) {
+                (true, false) => adj.entry(e.source).or_default().push((e.id, e.sink)),
+                (false, true) => adj.entry(e.sink).or_default().push((e.id, e.source)),
+                _ => {}
+            }
+        }
+        let mut roots: Vec<_> = adj.keys().copied().collect();
+        roots.sort();
+
+        // matched right vertex -> (matching edge, matched left vertex)
+        let mut mates: HashMap<VertexId, (EdgeId, VertexId), RandomState> =
+            HashMap::with_hasher(new_random_state());
+        for root in roots {
+            let mut visited: HashSet<VertexId, RandomState> =
+                HashSet::with_hasher(new_random_state());
+            // `path[i]` connects `stack[i].0` to a right vertex.
+            let mut stack = vec![(root, 0)];
+            let mut path: Vec<(EdgeId, VertexId)> = vec![];
+            while let Some((u, idx)) = stack.last_mut() {
+                let candidates = &adj[u];
+                if *idx == candidates.len() {
+                    stack.pop();
+                    path.pop();
+                    continue;
+                }
+                let (eid, r) = candidates[*idx];
+                *idx += 1;
+                if !visited.insert(r) {
+                    continue;
+                }
+                path.push((eid, r));
+                match mates.get(&r) {
+                    Some((_, next)) => stack.push((*next, 0)),
+                    None => {
+                        for ((u, _), (eid, r)) in stack.iter().zip(path.iter()) {
+                            mates.insert(*r, (*eid, *u));
+                        }
+                        break;
+                    }
+                }
+            }
+        }
+        let mut res: Vec<_> = mates.values().map(|(eid, _)| *eid).collect();
+        res.sort();
+        res
+    }
+}
+
+impl<G: QueryableGraph> BipartiteMatching for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check<G: QueryableGraph>(
+        g: &G,
+        left: &HashSet<VertexId, RandomState>,
+        expected_size: usize,
+    ) {
+        let matching = g.maximum_bipartite_matching(left);
+        assert_eq!(matching.len(), expected_size);
+        let mut endpoints = HashSet::new();
+        for eid in matching.iter() {
+            let e = g.find_edge(eid).unwrap();
+            assert_ne!(left.contains(&e.source), left.contains(&e.sink));
+            assert!(endpoints.insert(e.source));
+            assert!(endpoints.insert(e.sink));
+        }
+    }
+
+    #[test]
+    fn needs_augmentation() {
+        // l0 - r0, l0 - r1, l1 - r0, l2 - r1, l2 - r2, l3 - r2
+        // A greedy matching from l0 takes r0 and leaves l1 unmatched,
+        // so augmenting paths are indispensable.
+        let mut g = undirected::TreeBackedGraph::new();
+        let ls: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let rs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        for (l, r) in [(0, 0), (0, 1), (1, 0), (2, 1), (2, 2), (3, 2)] {
+            g.add_edge(ls[l], rs[r]);
+        }
+        let left: HashSet<_, RandomState> = ls.iter().copied().collect();
+        check(&g, &left, 3);
+    }
+
+    #[test]
+    fn directions_and_non_crossing_edges() {
+        let mut g = directed::TreeBackedGraph::new();
+        let ls: Vec<_> = (0..2).map(|_| g.add_vertex()).collect();
+        let rs: Vec<_> = (0..2).map(|_| g.add_vertex()).collect();
+        g.add_edge(ls[0], rs[0]);
+        g.add_edge(rs[1], ls[0]);
+        g.add_edge(rs[0], ls[1]);
+        g.add_edge(ls[0], ls[1]);
+        g.add_edge(rs[0], rs[1]);
+        g.add_edge(ls[1], ls[1]);
+        let left: HashSet<_, RandomState> = ls.iter().copied().collect();
+        check(&g, &left, 2);
+    }
+
+    #[test]
+    fn star() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let center = g.add_vertex();
+        for _ in 0..5 {
+            let v = g.add_vertex();
+            g.add_edge(center, v);
+        }
+        let left: HashSet<_, RandomState> = [center].into_iter().collect();
+        check(&g, &left, 1);
+    }
+}
@@ -0,0 +1,136 @@
+use super::undirected_view::undirected_adjacency;
+use crate::graph::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Trait and default implementation of finding maximal cliques.
+pub trait MaximalCliques
+where
+    Self: QueryableGraph + Sized,
+{
+    /// All maximal cliques in the undirected view of the graph.
+    ///
+    /// Directions of edges are ignored.
+    /// Self-loops are ignored and parallel edges are collapsed.
+    /// Vertices in each clique are in ascending order.
+    /// An empty graph has no maximal cliques.
+    ///
+    /// It is the Bron-Kerbosch algorithm with pivoting.
+    /// Beware that there can be exponentially many maximal cliques,
+    /// so it takes exponential time in the worst cases.
+    fn maximal_cliques(&self) -> Vec<Vec<VertexId>> {
+        let adj = undirected_adjacency(self);
+        let mut res = vec![];
+        if adj.is_empty() {
+            return res;
+        }
+        let candidates: BTreeSet<_> = adj.keys().copied().collect();
+        bron_kerbosch(&adj, &mut vec![], candidates, BTreeSet::new(), &mut res);
+        res
+    }
+}
+
+impl<G: QueryableGraph> MaximalCliques for G {}
+
+fn bron_kerbosch(
+    adj: &BTreeMap<VertexId, BTreeSet<VertexId>>,
+    clique: &mut Vec<VertexId>,
+    mut candidates: BTreeSet<VertexId>,
+    mut excluded: BTreeSet<VertexId>,
+    res: &mut Vec<Vec<VertexId>>,
+) {
+    let pivot = candidates
+        .iter()
+        .chain(excluded.iter())
+        .max_by_key(|u| adj[u].intersection(&candidates).count())
+        .copied();
+    let pivot = match pivot {
+        Some(pivot) => pivot,
+        None => {
+            let mut found = clique.clone();
+            found.sort();
+            res.push(found);
+            return;
+        }
+    };
+    let to_try: Vec<_> = candidates.difference(&adj[&pivot]).copied().collect();
+    for v in to_try {
+        let neighbors = &adj[&v];
+        clique.push(v);
+        bron_kerbosch(
+            adj,
+            clique,
+            candidates.intersection(neighbors).copied().collect(),
+            excluded.intersection(neighbors).copied().collect(),
+            res,
+        );
+        clique.pop();
+        candidates.remove(&v);
+        excluded.insert(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn cliques_are_maximal(ops: Ops) {
+        let ops_formed: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        let graph = &ops_formed.graph;
+        let adj = undirected_adjacency(graph);
+        for clique in graph.maximal_cliques() {
+            for (i, u) in clique.iter().enumerate() {
+                for v in clique[i + 1..].iter() {
+                    assert!(adj[u].contains(v));
+                }
+            }
+            for v in graph.iter_vertices() {
+                if !clique.contains(&v) {
+                    assert!(clique.iter().any(|u| !adj[u].contains(&v)));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn k4() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        for (i, u) in vs.iter().enumerate() {
+            for v in vs[i + 1..].iter() {
+                g.add_edge(*u, *v);
+            }
+        }
+        assert_eq!(g.maximal_cliques(), vec![vs]);
+    }
+
+    #[test]
+    fn path() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v0);
+        g.add_edge(v2, v1);
+        g.add_edge(v2, v2);
+        let mut trial = g.maximal_cliques();
+        trial.sort();
+        assert_eq!(trial, vec![vec![v0, v1], vec![v1, v2]]);
+    }
+
+    #[test]
+    fn empty() {
+        let g = undirected::TreeBackedGraph::new();
+        assert_eq!(g.maximal_cliques(), Vec::<Vec<VertexId>>::new());
+    }
+
+    #[test]
+    fn isolated_vertex() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v = g.add_vertex();
+        assert_eq!(g.maximal_cliques(), vec![vec![v]]);
+    }
+}
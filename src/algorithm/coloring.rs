@@ -0,0 +1,149 @@
+use super::undirected_view::undirected_adjacency;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use keyed_priority_queue::KeyedPriorityQueue;
+use std::cmp::Reverse;
+use std::collections::{BTreeSet, HashMap};
+
+/// Trait and default implementation of heuristic vertex coloring.
+///
+/// Colors are `0, 1, 2, ...`.
+/// Directions of edges and self-loops are ignored,
+/// so that adjacent vertices in the undirected view always get different colors.
+pub trait Coloring
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Greedy coloring in the largest-first order.
+    ///
+    /// Vertices are visited in descending order of their degrees, ties broken by ascending ID's.
+    /// Each of them takes the smallest color unused by its neighbors.
+    fn greedy_coloring(&self) -> HashMap<VertexId, usize, RandomState> {
+        let adj = undirected_adjacency(self);
+        let mut order: Vec<_> = adj.keys().copied().collect();
+        order.sort_by_key(|v| Reverse(adj[v].len()));
+        let mut res = HashMap::with_hasher(new_random_state());
+        for v in order {
+            let used: BTreeSet<_> = adj[&v].iter().filter_map(|u| res.get(u)).collect();
+            res.insert(v, smallest_unused(used.into_iter().copied()));
+        }
+        res
+    }
+
+    /// Coloring by the DSATUR heuristic.
+    ///
+    /// It repeatedly picks the uncolored vertex with the most distinct colors among its neighbors,
+    /// i.e., the largest saturation degree,
+    /// ties broken by larger degrees and then by smaller ID's.
+    /// The picked vertex takes the smallest color unused by its neighbors.
+    /// It usually uses fewer colors than [Coloring::greedy_coloring],
+    /// and is exact on bipartite graphs.
+    fn dsatur_coloring(&self) -> HashMap<VertexId, usize, RandomState> {
+        let adj = undirected_adjacency(self);
+        let mut neighbor_colors: HashMap<VertexId, BTreeSet<usize>, RandomState> =
+            HashMap::with_hasher(new_random_state());
+        let mut queue = KeyedPriorityQueue::with_capacity_and_hasher(adj.len(), new_random_state());
+        for (v, neighbors) in adj.iter() {
+            neighbor_colors.insert(*v, BTreeSet::new());
+            queue.push(*v, (0, neighbors.len(), Reverse(*v)));
+        }
+        let mut res = HashMap::with_hasher(new_random_state());
+        while let Some((v, _)) = queue.pop() {
+            let color = smallest_unused(neighbor_colors[&v].iter().copied());
+            res.insert(v, color);
+            for u in adj[&v].iter() {
+                if res.contains_key(u) {
+                    continue;
+                }
+                let colors = neighbor_colors.get_mut(u).unwrap();
+                if colors.insert(color) {
+                    queue
+                        .set_priority(u, (colors.len(), adj[u].len(), Reverse(*u)))
+                        .unwrap();
+                }
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> Coloring for G {}
+
+/// The smallest color not in `used`, which must be ascending.
+fn smallest_unused<I: Iterator<Item = usize>>(used: I) -> usize {
+    let mut res = 0;
+    for c in used {
+        if c == res {
+            res += 1;
+        } else if c > res {
+            break;
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn color_count(coloring: &HashMap<VertexId, usize, RandomState>) -> usize {
+        coloring.values().collect::<BTreeSet<_>>().len()
+    }
+
+    fn check<G: QueryableGraph>(g: &G, coloring: &HashMap<VertexId, usize, RandomState>) {
+        assert_eq!(coloring.len(), g.vertex_size());
+        for e in g.iter_edges() {
+            if e.source != e.sink {
+                assert_ne!(coloring[&e.source], coloring[&e.sink]);
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn proper(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&g.graph, &g.graph.greedy_coloring());
+        check(&g.graph, &g.graph.dsatur_coloring());
+    }
+
+    #[test]
+    fn cycles() {
+        for n in 3..8 {
+            let mut g = undirected::TreeBackedGraph::new();
+            let vs: Vec<_> = (0..n).map(|_| g.add_vertex()).collect();
+            for i in 0..n {
+                g.add_edge(vs[i], vs[(i + 1) % n]);
+            }
+            let greedy = g.greedy_coloring();
+            let dsatur = g.dsatur_coloring();
+            check(&g, &greedy);
+            check(&g, &dsatur);
+            assert!(color_count(&dsatur) <= color_count(&greedy));
+            assert_eq!(color_count(&dsatur), if n % 2 == 0 { 2 } else { 3 });
+        }
+    }
+
+    #[test]
+    fn crown() {
+        // u_i and v_j are adjacent if and only if i != j.
+        // Visiting u_0, v_0, u_1, v_1, ... greedily takes 4 colors.
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..8).map(|_| g.add_vertex()).collect();
+        for i in 0..4 {
+            for j in 0..4 {
+                if i != j {
+                    g.add_edge(vs[2 * i], vs[2 * j + 1]);
+                }
+            }
+        }
+        let greedy = g.greedy_coloring();
+        let dsatur = g.dsatur_coloring();
+        check(&g, &greedy);
+        check(&g, &dsatur);
+        assert_eq!(color_count(&greedy), 4);
+        assert_eq!(color_count(&dsatur), 2);
+    }
+}
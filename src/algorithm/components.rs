@@ -0,0 +1,148 @@
+use super::disjoint_set::DisjointSet;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{BTreeMap, HashMap};
+
+/// Trait and default implementation of analyzing connected components.
+///
+/// For directed graphs, directions of edges are ignored,
+/// i.e., components are weakly connected ones.
+pub trait ConnectedComponents
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Maps sizes of components to how many components are of that size.
+    ///
+    /// Components are found by a disjoint set,
+    /// without collecting vertices of each component.
+    fn component_size_histogram(&self) -> BTreeMap<usize, usize> {
+        let (_, mut dset) = union_all(self);
+        let mut res = BTreeMap::new();
+        for i in 0..dset.len() {
+            if dset.find(i) == i {
+                *res.entry(dset.size_of(i)).or_insert(0) += 1;
+            }
+        }
+        res
+    }
+
+    /// Precomputes connected components for many queries.
+    ///
+    /// It takes nearly $O(\|V\| + \|E\|)$ time by a disjoint set,
+    /// and then each query is $O(1)$.
+    fn connectivity(&self) -> Connectivity {
+        let (index, mut dset) = union_all(self);
+        let mut vs: Vec<_> = index.keys().copied().collect();
+        vs.sort();
+        let mut ids = HashMap::with_hasher(new_random_state());
+        let mut component_of = HashMap::with_capacity_and_hasher(vs.len(), new_random_state());
+        for v in vs {
+            let root = dset.find(index[&v]);
+            let n = ids.len();
+            let id = *ids.entry(root).or_insert(n);
+            component_of.insert(v, id);
+        }
+        Connectivity {
+            component_of,
+            count: ids.len(),
+        }
+    }
+}
+
+impl<G: QueryableGraph> ConnectedComponents for G {}
+
+/// Precomputed connected components of a graph, by [ConnectedComponents::connectivity].
+///
+/// It is a snapshot and does not follow later changes of the graph.
+#[derive(Debug, Clone)]
+pub struct Connectivity {
+    component_of: HashMap<VertexId, usize, RandomState>,
+    count: usize,
+}
+
+impl Connectivity {
+    /// Whether `a` and `b` are in the same component.
+    ///
+    /// It is false if either is not in the graph.
+    pub fn connected(&self, a: &VertexId, b: &VertexId) -> bool {
+        match (self.component_of.get(a), self.component_of.get(b)) {
+            (Some(x), Some(y)) => x == y,
+            _ => false,
+        }
+    }
+
+    /// Index of the component containing `v`, or `None` if `v` is not in the graph.
+    ///
+    /// Components are indexed from 0 in the order of their smallest vertices.
+    pub fn component_of(&self, v: &VertexId) -> Option<usize> {
+        self.component_of.get(v).copied()
+    }
+
+    /// Number of components.
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+}
+
+/// Indexes vertices and unions endpoints of every edge.
+fn union_all<G: QueryableGraph>(graph: &G) -> (HashMap<VertexId, usize, RandomState>, DisjointSet) {
+    let mut index = HashMap::with_hasher(new_random_state());
+    for v in graph.iter_vertices() {
+        let n = index.len();
+        index.insert(v, n);
+    }
+    let mut dset = DisjointSet::new(index.len());
+    for e in graph.iter_edges() {
+        dset.union(index[&e.source], index[&e.sink]);
+    }
+    (index, dset)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn component_size_histogram() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..7).map(|_| g.add_vertex()).collect();
+        // {0, 1, 2}, {3, 4, 5} and {6}
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[2], vs[1]);
+        g.add_edge(vs[3], vs[4]);
+        g.add_edge(vs[4], vs[5]);
+        g.add_edge(vs[5], vs[3]);
+        g.add_edge(vs[6], vs[6]);
+        let trial = g.component_size_histogram();
+        assert_eq!(trial, [(1, 1), (3, 2)].into_iter().collect());
+        assert!(directed::TreeBackedGraph::new()
+            .component_size_histogram()
+            .is_empty());
+    }
+
+    fn check_connectivity<G: QueryableGraph>(g: &G) {
+        let conn = g.connectivity();
+        let mut count = 0;
+        for a in g.iter_vertices() {
+            let i = conn.component_of(&a).unwrap();
+            assert!(i < conn.component_count());
+            count = count.max(i + 1);
+            for b in g.iter_vertices() {
+                assert_eq!(conn.connected(&a, &b), g.in_same_component(&a, &b));
+            }
+        }
+        assert_eq!(count, conn.component_count());
+        assert_eq!(conn.component_of(&VertexId(usize::MAX)), None);
+    }
+
+    #[quickcheck]
+    fn connectivity_consistent_with_in_same_component(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check_connectivity(&g.graph);
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check_connectivity(&g.graph);
+    }
+}
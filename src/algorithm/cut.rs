@@ -0,0 +1,115 @@
+use crate::graph::*;
+use ahash::RandomState;
+use std::collections::HashSet;
+
+/// Trait and default implementation of measuring cuts between vertex sets.
+pub trait CutMetrics
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Number of edges with one endpoint in `set_a` and the other in `set_b`.
+    ///
+    /// Directions of edges are ignored.
+    /// Each edge is counted at most once, even if the sets overlap.
+    fn cut_size(
+        &self,
+        set_a: &HashSet<VertexId, RandomState>,
+        set_b: &HashSet<VertexId, RandomState>,
+    ) -> usize {
+        self.iter_edges()
+            .filter(|e| {
+                (set_a.contains(&e.source) && set_b.contains(&e.sink))
+                    || (set_b.contains(&e.source) && set_a.contains(&e.sink))
+            })
+            .count()
+    }
+
+    /// Conductance of `set` against the rest of the vertices.
+    ///
+    /// It is the cut size between both sides
+    /// divided by the smaller volume of them,
+    /// where the volume of a side is the total degree of its vertices in the undirected view,
+    /// i.e., a self-loop adds 2.
+    /// It is `None` if either side has no volume.
+    /// Vertices in `set` but not in the graph are ignored.
+    fn conductance(&self, set: &HashSet<VertexId, RandomState>) -> Option<f64> {
+        let mut cut = 0usize;
+        let mut volume_in = 0usize;
+        let mut volume_out = 0usize;
+        for e in self.iter_edges() {
+            let src_in = set.contains(&e.source);
+            let snk_in = set.contains(&e.sink);
+            if src_in != snk_in {
+                cut += 1;
+            }
+            for inside in [src_in, snk_in] {
+                if inside {
+                    volume_in += 1;
+                } else {
+                    volume_out += 1;
+                }
+            }
+        }
+        let volume = volume_in.min(volume_out);
+        if volume == 0 {
+            None
+        } else {
+            Some(cut as f64 / volume as f64)
+        }
+    }
+}
+
+impl<G: QueryableGraph> CutMetrics for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::random_state::new_random_state;
+
+    /// Two triangles joined by a bridge, with the latter.
+    fn barbell() -> (undirected::TreeBackedGraph, Vec<VertexId>, EdgeId) {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..6).map(|_| g.add_vertex()).collect();
+        for side in [&vs[..3], &vs[3..]] {
+            for i in 0..3 {
+                g.add_edge(side[i], side[(i + 1) % 3]);
+            }
+        }
+        let bridge = g.add_edge(vs[2], vs[3]);
+        (g, vs, bridge)
+    }
+
+    fn set(vs: &[VertexId]) -> HashSet<VertexId, RandomState> {
+        let mut res = HashSet::with_hasher(new_random_state());
+        res.extend(vs.iter().copied());
+        res
+    }
+
+    #[test]
+    fn barbell_cut() {
+        let (g, vs, bridge) = barbell();
+        let left = set(&vs[..3]);
+        let right = set(&vs[3..]);
+        assert_eq!(g.cut_size(&left, &right), 1);
+        assert_eq!(g.cut_size(&right, &left), 1);
+        let cut: Vec<_> = g
+            .iter_edges()
+            .filter(|e| left.contains(&e.source) != left.contains(&e.sink))
+            .map(|e| e.id)
+            .collect();
+        assert_eq!(cut, vec![bridge]);
+        // each side has volume 2 * 3 + 1
+        assert_eq!(g.conductance(&left), Some(1.0 / 7.0));
+
+        let unbalanced = set(&vs[..2]);
+        assert_eq!(g.cut_size(&unbalanced, &right), 0);
+        assert_eq!(g.conductance(&unbalanced), Some(2.0 / 4.0));
+    }
+
+    #[test]
+    fn degenerate_conductance() {
+        let (g, vs, _) = barbell();
+        assert_eq!(g.conductance(&set(&[])), None);
+        assert_eq!(g.conductance(&set(&vs)), None);
+    }
+}
@@ -0,0 +1,217 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use std::collections::HashMap;
+
+/// Trait and default implementation of detecting cycles on both directed and undirected graphs.
+///
+/// Unlike [SimpleCycles](crate::algorithm::SimpleCycles),
+/// it does not enumerate cycles.
+/// It stops as soon as any cycle is found.
+pub trait CycleDetection
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Whether the graph is free of cycles, i.e., a DAG for directed graphs or a forest for undirected graphs.
+    ///
+    /// Self-loops are cycles.
+    /// For undirected graphs, an edge is never walked back,
+    /// but two parallel edges form a cycle.
+    fn is_dag(&self) -> bool {
+        self.find_cycle().is_none()
+    }
+
+    /// Finds a cycle by detecting back edges during a depth-first search.
+    ///
+    /// Edges of the cycle are in order, i.e., the sink of an edge is the source of the next one,
+    /// and the sink of the last edge is the source of the first one.
+    /// It returns `None` iff [CycleDetection::is_dag].
+    fn find_cycle(&self) -> Option<Vec<Edge>> {
+        let mut states = HashMap::with_hasher(new_random_state());
+        for root in self.iter_vertices() {
+            if states.contains_key(&root) {
+                continue;
+            }
+            states.insert(root, VertexState::OnPath(0));
+            let mut stack = vec![(root, None, self.out_edges(&root))];
+            while let Some((vert, come_from, out_edges)) = stack.last_mut() {
+                let vert = *vert;
+                let come_from_id = come_from.as_ref().map(|e: &Edge| e.id);
+                match out_edges.next() {
+                    Some(e) => {
+                        if Some(e.id) == come_from_id {
+                            continue;
+                        }
+                        match states.get(&e.sink) {
+                            Some(VertexState::OnPath(depth)) => {
+                                let mut res: Vec<_> = stack[depth + 1..]
+                                    .iter()
+                                    .map(|(_, come_from, _)| come_from.clone().unwrap())
+                                    .collect();
+                                res.push(e);
+                                return Some(res);
+                            }
+                            Some(VertexState::Exhausted) => {}
+                            None => {
+                                states.insert(e.sink, VertexState::OnPath(stack.len()));
+                                let out_edges = self.out_edges(&e.sink);
+                                stack.push((e.sink, Some(e), out_edges));
+                            }
+                        }
+                    }
+                    None => {
+                        states.insert(vert, VertexState::Exhausted);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<G: QueryableGraph> CycleDetection for G {}
+
+enum VertexState {
+    /// on the current search path, with its depth
+    OnPath(usize),
+    Exhausted,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::SimpleCycles;
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+    use std::collections::HashSet;
+
+    #[test]
+    fn self_loop() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v = g.add_vertex();
+        g.add_edge(v, v);
+        assert!(!g.is_dag());
+    }
+
+    #[test]
+    fn tree() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v2);
+        g.add_edge(v2, v3);
+        assert!(g.is_dag());
+    }
+
+    #[test]
+    fn back_and_forth() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v0);
+        assert!(!g.is_dag());
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        g.add_edge(v0, v1);
+        assert!(g.is_dag());
+        g.add_edge(v1, v0);
+        assert!(!g.is_dag());
+    }
+
+    #[test]
+    fn find_cycle_on_dag() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v2);
+        g.add_edge(v1, v2);
+        assert_eq!(g.find_cycle(), None);
+    }
+
+    #[test]
+    fn find_cycle_from_deep_path() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        let e1 = g.add_edge(v1, v2);
+        let e2 = g.add_edge(v2, v3);
+        let e3 = g.add_edge(v3, v1);
+        let trial: Vec<_> = g.find_cycle().unwrap().iter().map(|e| e.id).collect();
+        assert_eq!(trial, vec![e1, e2, e3]);
+    }
+
+    #[quickcheck]
+    fn found_cycles_are_cyclic(ops: Ops) {
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        if let Some(cycle) = dig.graph.find_cycle() {
+            assert!(is_cyclic(&cycle));
+        }
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        if let Some(cycle) = udg.graph.find_cycle() {
+            assert!(is_cyclic(&cycle));
+        }
+    }
+
+    #[quickcheck]
+    fn consistent_with_simple_cycles(ops: Ops) {
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        assert_eq!(
+            dig.graph.is_dag(),
+            dig.graph.simple_cycles().next().is_none()
+        );
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        assert_eq!(
+            udg.graph.is_dag(),
+            udg.graph.simple_cycles().next().is_none()
+        );
+    }
+
+    #[quickcheck]
+    fn consistent_with_would_create_cycle(ops: Ops) {
+        fn check<G>(g: &G)
+        where
+            G: QueryableGraph + GrowableGraph + Clone,
+        {
+            if !g.is_dag() {
+                return;
+            }
+            for u in g.iter_vertices() {
+                for v in g.iter_vertices() {
+                    let mut h = g.clone();
+                    h.add_edge(u, v);
+                    assert_eq!(g.would_create_cycle(&u, &v), !h.is_dag());
+                }
+            }
+        }
+
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+    }
+
+    fn is_cyclic(cycle: &[Edge]) -> bool {
+        let ids: HashSet<_> = cycle.iter().map(|e| e.id).collect();
+        if ids.len() != cycle.len() {
+            return false;
+        }
+        cycle
+            .iter()
+            .zip(cycle.iter().cycle().skip(1))
+            .all(|(prev, next)| prev.sink == next.source)
+    }
+}
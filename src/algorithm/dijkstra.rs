@@ -0,0 +1,193 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use bimap::BiHashMap;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::ops::Add;
+
+/// Trait and default implementation of Dijkstra's single-source shortest paths.
+pub trait Dijkstra
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Shortest paths along out-edges from `source`, with edges weighted by `weight`.
+    ///
+    /// It returns, for each vertex reachable from `source`,
+    /// its distance and the last edge on one of its shortest paths.
+    /// `source` itself is at distance `W::default()` without any last edge.
+    /// If `source` is not in the graph, the result is empty.
+    ///
+    /// `W::default()` must be the zero of `W`, and weights must be non-negative.
+    /// Otherwise, the result is unspecified.
+    ///
+    /// It takes $O((\|V\| + \|E\|) \log \|E\|)$ time with a binary heap.
+    fn dijkstra<W, F>(
+        &self,
+        source: &VertexId,
+        weight: F,
+    ) -> HashMap<VertexId, (W, Option<Edge>), RandomState>
+    where
+        W: Copy + Ord + Default + Add<Output = W>,
+        F: Fn(&Edge) -> W,
+    {
+        let mut res: HashMap<VertexId, (W, Option<Edge>), RandomState> =
+            HashMap::with_hasher(new_random_state());
+        if !self.contains_vertex(source) {
+            return res;
+        }
+        let mut tentative: HashMap<VertexId, (W, Option<Edge>), RandomState> =
+            HashMap::with_hasher(new_random_state());
+        let mut heap = BinaryHeap::new();
+        tentative.insert(*source, (W::default(), None));
+        heap.push(Reverse((W::default(), *source)));
+        while let Some(Reverse((d, v))) = heap.pop() {
+            if res.contains_key(&v) {
+                continue;
+            }
+            let settled = tentative.remove(&v).unwrap();
+            debug_assert!(settled.0 == d);
+            res.insert(v, settled);
+            for e in self.out_edges(&v) {
+                if res.contains_key(&e.sink) {
+                    continue;
+                }
+                let nd = d + weight(&e);
+                match tentative.get(&e.sink) {
+                    Some((old, _)) if *old <= nd => {}
+                    _ => {
+                        heap.push(Reverse((nd, e.sink)));
+                        tentative.insert(e.sink, (nd, Some(e)));
+                    }
+                }
+            }
+        }
+        res
+    }
+
+    /// The shortest-path tree from `source`, by last edges of [Dijkstra::dijkstra].
+    ///
+    /// It consists of vertices reachable from `source`,
+    /// each of which but `source` has exactly one in-edge from its predecessor.
+    /// In the returned [MappedGraph], vertices and edges are mapped to the original ones.
+    /// If `source` is not in the graph, the tree is empty.
+    fn shortest_path_tree<G2, W, F>(&self, source: &VertexId, weight: F) -> MappedGraph<G2>
+    where
+        G2: GrowableGraph,
+        W: Copy + Ord + Default + Add<Output = W>,
+        F: Fn(&Edge) -> W,
+    {
+        let paths = self.dijkstra(source, weight);
+        let mut res = MappedGraph {
+            graph: G2::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            emap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+        };
+        let mut vs: Vec<_> = paths.keys().copied().collect();
+        vs.sort();
+        for v in vs.iter() {
+            let new_v = res.graph.add_vertex();
+            res.vmap.insert(new_v, *v);
+        }
+        for v in vs.iter() {
+            if let (_, Some(e)) = &paths[v] {
+                let new_src = *res.vmap.get_by_right(&e.source).unwrap();
+                let new_snk = *res.vmap.get_by_right(&e.sink).unwrap();
+                let new_e = res.graph.add_edge(new_src, new_snk);
+                res.emap.insert(new_e, e.id);
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> Dijkstra for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::FloydWarshall;
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn small() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        let mut weights = std::collections::HashMap::new();
+        let mut eids = vec![];
+        for (src, snk, w) in [(0, 1, 4u64), (0, 2, 1), (2, 1, 2), (1, 3, 1), (2, 3, 5)] {
+            let eid = g.add_edge(vs[src], vs[snk]);
+            weights.insert(eid, w);
+            eids.push(eid);
+        }
+        let res = g.dijkstra(&vs[0], |e| weights[&e.id]);
+        assert_eq!(res.len(), 4);
+        assert_eq!(res[&vs[0]], (0, None));
+        assert_eq!(res[&vs[1]].0, 3);
+        assert_eq!(res[&vs[1]].1.as_ref().unwrap().id, eids[2]);
+        assert_eq!(res[&vs[2]].0, 1);
+        assert_eq!(res[&vs[3]].0, 4);
+        assert_eq!(res[&vs[3]].1.as_ref().unwrap().id, eids[3]);
+        assert!(!res.contains_key(&vs[4]));
+    }
+
+    #[quickcheck]
+    fn consistent_with_floyd_warshall(ops: Ops) {
+        fn check<G: QueryableGraph>(g: &G) {
+            let weight = |e: &Edge| (e.id.0 % 7) as i64;
+            let (order, dists) = g.floyd_warshall(weight);
+            for (i, source) in order.iter().enumerate() {
+                let res = g.dijkstra(source, weight);
+                for (j, sink) in order.iter().enumerate() {
+                    assert_eq!(res.get(sink).map(|(d, _)| *d), dists[i][j]);
+                    if let Some((d, Some(last))) = res.get(sink) {
+                        assert_eq!(last.sink, *sink);
+                        assert_eq!(res[&last.source].0 + weight(last), *d);
+                    }
+                }
+            }
+        }
+
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+    }
+
+    #[quickcheck]
+    fn shortest_path_tree(ops: Ops) {
+        fn check<G: QueryableGraph>(g: &G) {
+            let weight = |e: &Edge| (e.id.0 % 7) as i64;
+            for source in g.iter_vertices() {
+                let paths = g.dijkstra(&source, weight);
+                let tree: MappedGraph<directed::TreeBackedGraph> =
+                    g.shortest_path_tree(&source, weight);
+                assert_eq!(tree.graph.vertex_size(), paths.len());
+                assert_eq!(tree.graph.edge_size(), paths.len() - 1);
+                for (new_v, v) in tree.vmap.iter() {
+                    let in_edges: Vec<_> = tree.graph.in_edges(new_v).collect();
+                    if *v == source {
+                        assert!(in_edges.is_empty());
+                    } else {
+                        assert_eq!(in_edges.len(), 1);
+                        let last = paths[v].1.as_ref().unwrap();
+                        assert_eq!(tree.emap.get_by_left(&in_edges[0].id), Some(&last.id));
+                        assert_eq!(
+                            tree.vmap.get_by_left(&in_edges[0].source),
+                            Some(&last.source)
+                        );
+                    }
+                }
+            }
+            let tree: MappedGraph<directed::TreeBackedGraph> =
+                g.shortest_path_tree(&VertexId(usize::MAX), weight);
+            assert_eq!(tree.graph.vertex_size(), 0);
+        }
+
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+    }
+}
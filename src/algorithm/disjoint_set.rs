@@ -0,0 +1,52 @@
+/// Union-find over `0..n`, by size and with path halving.
+#[derive(Debug, Clone)]
+pub(crate) struct DisjointSet {
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub(crate) fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            sizes: vec![1; n],
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.parents.len()
+    }
+
+    /// The representative of the set containing `x`.
+    pub(crate) fn find(&mut self, mut x: usize) -> usize {
+        while self.parents[x] != x {
+            self.parents[x] = self.parents[self.parents[x]];
+            x = self.parents[x];
+        }
+        x
+    }
+
+    /// Merges sets containing `x` and `y`.
+    /// Returns false if they are already in the same set.
+    pub(crate) fn union(&mut self, x: usize, y: usize) -> bool {
+        let x = self.find(x);
+        let y = self.find(y);
+        if x == y {
+            return false;
+        }
+        let (big, small) = if self.sizes[x] >= self.sizes[y] {
+            (x, y)
+        } else {
+            (y, x)
+        };
+        self.parents[small] = big;
+        self.sizes[big] += self.sizes[small];
+        true
+    }
+
+    /// Size of the set containing `x`.
+    pub(crate) fn size_of(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.sizes[root]
+    }
+}
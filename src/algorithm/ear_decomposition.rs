@@ -0,0 +1,200 @@
+use super::undirected_view::undirected_incidence;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{HashMap, HashSet};
+
+/// Trait and default implementation of ear decompositions.
+pub trait EarDecomposition
+where
+    Self: QueryableGraph + Sized,
+{
+    /// An ear decomposition of the undirected view of a 2-edge-connected graph,
+    /// or `None` if the graph is not 2-edge-connected.
+    ///
+    /// Directions of edges are ignored.
+    /// Each ear is a list of edges along a walk.
+    /// The first ear is a cycle.
+    /// Each following ear is either a path whose both endpoints, and only them, are on previous ears,
+    /// or a cycle sharing exactly one vertex with previous ears.
+    /// The latter happens only at cut vertices,
+    /// so the decomposition is open whenever the graph is also 2-vertex-connected.
+    /// Each self-loop is an ear by itself, after all others.
+    /// A graph with a single vertex, as well as the empty graph, has no ear at all.
+    ///
+    /// It is Schmidt's chain decomposition in $O(\|V\| + \|E\|)$:
+    /// from a depth-first search tree,
+    /// every back edge, in the discovery order of its upper endpoint,
+    /// starts an ear which climbs up tree edges until an already visited vertex.
+    fn ear_decomposition(&self) -> Option<Vec<Vec<EdgeId>>> {
+        let incidence = undirected_incidence(self);
+        let mut ears = vec![];
+        if let Some(root) = incidence.keys().next() {
+            // discovery times and edges from parents
+            let mut times: HashMap<VertexId, usize, RandomState> =
+                HashMap::with_hasher(new_random_state());
+            let mut parents: HashMap<VertexId, (VertexId, EdgeId), RandomState> =
+                HashMap::with_hasher(new_random_state());
+            let mut order = vec![*root];
+            times.insert(*root, 0);
+            let mut stack = vec![(*root, 0)];
+            while let Some((v, idx)) = stack.last_mut() {
+                let v = *v;
+                if let Some((e, w)) = incidence[&v].get(*idx) {
+                    *idx += 1;
+                    if !times.contains_key(w) {
+                        times.insert(*w, order.len());
+                        order.push(*w);
+                        parents.insert(*w, (v, *e));
+                        stack.push((*w, 0));
+                    }
+                } else {
+                    stack.pop();
+                }
+            }
+            if order.len() < incidence.len() {
+                return None;
+            }
+
+            let tree_edges: HashSet<EdgeId, RandomState> =
+                parents.values().map(|(_, e)| *e).collect();
+            let mut visited: HashSet<VertexId, RandomState> =
+                HashSet::with_hasher(new_random_state());
+            let mut covered = 0;
+            for v in order.iter() {
+                for (e, w) in incidence[v].iter() {
+                    if tree_edges.contains(e) || times[w] <= times[v] {
+                        continue;
+                    }
+                    visited.insert(*v);
+                    let mut ear = vec![*e];
+                    let mut x = *w;
+                    while visited.insert(x) {
+                        let (parent, up) = parents[&x];
+                        ear.push(up);
+                        x = parent;
+                    }
+                    covered += ear.len();
+                    ears.push(ear);
+                }
+            }
+            let non_loops: usize = incidence.values().map(|es| es.len()).sum::<usize>() / 2;
+            if covered < non_loops {
+                return None;
+            }
+        }
+        let mut self_loops: Vec<_> = self
+            .iter_edges()
+            .filter(|e| e.source == e.sink)
+            .map(|e| e.id)
+            .collect();
+        self_loops.sort();
+        ears.extend(self_loops.into_iter().map(|e| vec![e]));
+        Some(ears)
+    }
+}
+
+impl<G: QueryableGraph> EarDecomposition for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    fn check_ears<G: QueryableGraph>(g: &G, ears: &[Vec<EdgeId>]) {
+        let mut seen_vertices = BTreeSet::new();
+        let mut seen_edges = BTreeSet::new();
+        for ear in ears.iter() {
+            let mut vertices = vec![];
+            for e in ear.iter() {
+                let e = g.find_edge(e).unwrap();
+                assert!(seen_edges.insert(e.id));
+                vertices.push(e.source);
+                vertices.push(e.sink);
+            }
+            let endpoints: BTreeSet<_> = vertices
+                .iter()
+                .filter(|v| vertices.iter().filter(|u| u == v).count() == 1)
+                .copied()
+                .collect();
+            let attached: BTreeSet<_> = vertices
+                .iter()
+                .filter(|v| seen_vertices.contains(*v))
+                .copied()
+                .collect();
+            if seen_vertices.is_empty() {
+                assert!(endpoints.is_empty());
+            } else if endpoints.is_empty() {
+                // a closed ear
+                assert_eq!(attached.len(), 1);
+            } else {
+                assert_eq!(attached, endpoints);
+            }
+            seen_vertices.extend(vertices);
+        }
+        assert_eq!(seen_edges.len(), g.edge_size());
+    }
+
+    #[test]
+    fn cycle() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        let mut es: Vec<_> = (0..5).map(|i| g.add_edge(vs[i], vs[(i + 1) % 5])).collect();
+        let mut ears = g.ear_decomposition().unwrap();
+        assert_eq!(ears.len(), 1);
+        check_ears(&g, &ears);
+        ears[0].sort();
+        es.sort();
+        assert_eq!(ears[0], es);
+    }
+
+    #[test]
+    fn theta() {
+        // three internally disjoint paths between v0 and v1
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[0], vs[2]);
+        g.add_edge(vs[2], vs[1]);
+        g.add_edge(vs[1], vs[3]);
+        g.add_edge(vs[3], vs[4]);
+        g.add_edge(vs[4], vs[0]);
+        let ears = g.ear_decomposition().unwrap();
+        assert_eq!(ears.len(), 2);
+        check_ears(&g, &ears);
+    }
+
+    #[test]
+    fn bowtie_parallel_edges_and_self_loops() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        g.add_edge(vs[2], vs[0]);
+        g.add_edge(vs[2], vs[3]);
+        g.add_edge(vs[3], vs[4]);
+        g.add_edge(vs[4], vs[2]);
+        g.add_edge(vs[4], vs[3]);
+        g.add_edge(vs[4], vs[4]);
+        let ears = g.ear_decomposition().unwrap();
+        assert_eq!(ears.len(), 4);
+        check_ears(&g, &ears);
+    }
+
+    #[test]
+    fn not_2_edge_connected() {
+        let mut g = undirected::TreeBackedGraph::new();
+        assert_eq!(g.ear_decomposition(), Some(vec![]));
+        let v0 = g.add_vertex();
+        assert_eq!(g.ear_decomposition(), Some(vec![]));
+        let v1 = g.add_vertex();
+        assert_eq!(g.ear_decomposition(), None);
+        g.add_edge(v0, v1);
+        assert_eq!(g.ear_decomposition(), None);
+        g.add_edge(v1, v0);
+        assert_eq!(g.ear_decomposition().map(|ears| ears.len()), Some(1));
+        let v2 = g.add_vertex();
+        g.add_edge(v1, v2);
+        assert_eq!(g.ear_decomposition(), None);
+    }
+}
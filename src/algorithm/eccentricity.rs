@@ -0,0 +1,108 @@
+use super::BfsLayers;
+use crate::graph::*;
+
+/// Trait and default implementation of eccentricities, centers and radii.
+pub trait Eccentricity
+where
+    Self: QueryableGraph + Sized,
+{
+    /// The maximum distance from `v` to other vertices along out-edges.
+    ///
+    /// It is `None` if some vertex is unreachable from `v`, or `v` is not in the graph.
+    /// It takes a breadth-first search, i.e., $O(\|V\| + \|E\|)$.
+    fn eccentricity(&self, v: &VertexId) -> Option<usize> {
+        let mut reached = 0;
+        let mut layers = 0;
+        for layer in self.bfs_layers(v) {
+            reached += layer.len();
+            layers += 1;
+        }
+        if layers > 0 && reached == self.vertex_size() {
+            Some(layers - 1)
+        } else {
+            None
+        }
+    }
+
+    /// Vertices of the minimum eccentricity, in ascending order.
+    ///
+    /// Only vertices reaching every other vertex along out-edges are considered,
+    /// e.g., only the source of a directed path.
+    /// It is empty only when no such vertex exists,
+    /// e.g., for disconnected graphs and the empty graph.
+    /// It takes $O(\|V\| \cdot (\|V\| + \|E\|))$.
+    fn center(&self) -> Vec<VertexId> {
+        let mut res = vec![];
+        let mut radius = usize::MAX;
+        let mut vs: Vec<_> = self.iter_vertices().collect();
+        vs.sort();
+        for v in vs {
+            if let Some(ecc) = self.eccentricity(&v) {
+                if ecc < radius {
+                    radius = ecc;
+                    res.clear();
+                }
+                if ecc == radius {
+                    res.push(v);
+                }
+            }
+        }
+        res
+    }
+
+    /// The minimum eccentricity.
+    ///
+    /// As in [Eccentricity::center], only vertices reaching every other vertex are considered.
+    /// It is `None` only when no such vertex exists.
+    fn radius(&self) -> Option<usize> {
+        self.iter_vertices()
+            .filter_map(|v| self.eccentricity(&v))
+            .min()
+    }
+}
+
+impl<G: QueryableGraph> Eccentricity for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        for i in 1..vs.len() {
+            g.add_edge(vs[i - 1], vs[i]);
+        }
+        assert_eq!(g.eccentricity(&vs[0]), Some(4));
+        assert_eq!(g.eccentricity(&vs[1]), Some(3));
+        assert_eq!(g.center(), vec![vs[2]]);
+        assert_eq!(g.radius(), Some(2));
+
+        g.add_edge(vs[4], vs[0]);
+        assert_eq!(g.center(), vs);
+        assert_eq!(g.radius(), Some(2));
+    }
+
+    #[test]
+    fn disconnected_and_directed() {
+        let mut g = directed::TreeBackedGraph::new();
+        assert_eq!(g.center(), vec![]);
+        assert_eq!(g.radius(), None);
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        assert_eq!(g.eccentricity(&vs[0]), Some(2));
+        assert_eq!(g.eccentricity(&vs[1]), None);
+        assert_eq!(g.center(), vec![vs[0]]);
+        assert_eq!(g.radius(), Some(2));
+
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        assert_eq!(g.eccentricity(&vs[0]), None);
+        assert_eq!(g.eccentricity(&VertexId(100)), None);
+        assert_eq!(g.center(), vec![]);
+        assert_eq!(g.radius(), None);
+    }
+}
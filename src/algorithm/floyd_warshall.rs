@@ -0,0 +1,125 @@
+use crate::graph::*;
+
+/// Trait and default implementation of Floyd-Warshall all-pairs shortest paths.
+pub trait FloydWarshall
+where
+    Self: QueryableGraph + Sized,
+{
+    /// All-pairs shortest distances, with edges weighted by `weight`.
+    ///
+    /// It returns vertices in ascending order of their ID's,
+    /// and a matrix whose `[i][j]` cell is the distance from the `i`-th vertex to the `j`-th,
+    /// or `None` if the latter is unreachable from the former.
+    /// For undirected graphs, edges can be walked in both directions.
+    ///
+    /// Negative weights are allowed.
+    /// A negative cell on the diagonal tells that its vertex is on a negative cycle.
+    /// In that case, distances through such a vertex are meaningless.
+    /// Note that a negative edge in an undirected graph is a negative cycle itself.
+    ///
+    /// It takes O(V^3) time and O(V^2) memory.
+    fn floyd_warshall<F>(&self, weight: F) -> (Vec<VertexId>, Vec<Vec<Option<i64>>>)
+    where
+        F: Fn(&Edge) -> i64,
+    {
+        let mut order: Vec<_> = self.iter_vertices().collect();
+        order.sort();
+        let n = order.len();
+        let mut dist = vec![vec![None; n]; n];
+        for (i, v) in order.iter().enumerate() {
+            dist[i][i] = Some(0);
+            for e in self.out_edges(v) {
+                let j = order.binary_search(&e.sink).unwrap();
+                let w = weight(&e);
+                match dist[i][j] {
+                    Some(old) if old <= w => {}
+                    _ => dist[i][j] = Some(w),
+                }
+            }
+        }
+        for k in 0..n {
+            let row_k = dist[k].clone();
+            for row_i in dist.iter_mut() {
+                let d_ik = match row_i[k] {
+                    Some(d) => d,
+                    None => continue,
+                };
+                for (cell, d_kj) in row_i.iter_mut().zip(row_k.iter()) {
+                    if let Some(d_kj) = d_kj {
+                        let d = d_ik.saturating_add(*d_kj);
+                        match cell {
+                            Some(old) if *old <= d => {}
+                            _ => *cell = Some(d),
+                        }
+                    }
+                }
+            }
+        }
+        (order, dist)
+    }
+}
+
+impl<G: QueryableGraph> FloydWarshall for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn directed() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let mut weights = HashMap::new();
+        for (src, snk, w) in [(0, 1, 5), (0, 2, 9), (1, 2, 3), (2, 0, -2), (1, 1, 7)] {
+            let eid = g.add_edge(vs[src], vs[snk]);
+            weights.insert(eid, w);
+        }
+        let (order, dist) = g.floyd_warshall(|e| weights[&e.id]);
+        assert_eq!(order, vs);
+        assert_eq!(
+            dist,
+            vec![
+                vec![Some(0), Some(5), Some(8), None],
+                vec![Some(1), Some(0), Some(3), None],
+                vec![Some(-2), Some(3), Some(0), None],
+                vec![None, None, None, Some(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let mut weights = HashMap::new();
+        for (src, snk, w) in [(0, 1, 4), (1, 2, 1), (2, 0, 6)] {
+            let eid = g.add_edge(vs[src], vs[snk]);
+            weights.insert(eid, w);
+        }
+        let (_, dist) = g.floyd_warshall(|e| weights[&e.id]);
+        assert_eq!(
+            dist,
+            vec![
+                vec![Some(0), Some(4), Some(5)],
+                vec![Some(4), Some(0), Some(1)],
+                vec![Some(5), Some(1), Some(0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn negative_cycle() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let mut weights = HashMap::new();
+        for (src, snk, w) in [(0, 1, 1), (1, 0, -3), (1, 2, 1)] {
+            let eid = g.add_edge(vs[src], vs[snk]);
+            weights.insert(eid, w);
+        }
+        let (_, dist) = g.floyd_warshall(|e| weights[&e.id]);
+        assert!(dist[0][0].unwrap() < 0);
+        assert!(dist[1][1].unwrap() < 0);
+        assert_eq!(dist[2][2], Some(0));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{HashMap, VecDeque};
+
+/// Trait and default implementation of computing girth, i.e., the length of the shortest cycle.
+pub trait Girth
+where
+    Self: QueryableGraph + DirectedOrNot + Sized,
+{
+    /// Number of edges in the shortest cycle, or `None` if the graph is acyclic.
+    ///
+    /// * Self-loops are cycles of length 1.
+    /// * For directed graphs, it is the length of the shortest directed cycle.
+    /// * For undirected graphs, an edge is never walked back,
+    ///   but two parallel edges form a cycle of length 2.
+    ///
+    /// It runs a breadth-first search from each vertex, thus in $O(\|V\| \cdot \|E\|)$.
+    fn girth(&self) -> Option<usize> {
+        let mut res: Option<usize> = None;
+        for start in self.iter_vertices() {
+            let found = if Self::DIRECTED_OR_NOT {
+                shortest_directed_cycle_through(self, start)
+            } else {
+                shortest_undirected_cycle_around(self, start)
+            };
+            if let Some(found) = found {
+                res = Some(res.map_or(found, |x| x.min(found)));
+            }
+            if res == Some(1) {
+                break;
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph + DirectedOrNot> Girth for G {}
+
+/// Length of the shortest directed cycle passing through `start`.
+fn shortest_directed_cycle_through<G>(graph: &G, start: VertexId) -> Option<usize>
+where
+    G: QueryableGraph,
+{
+    let mut dists = HashMap::with_hasher(new_random_state());
+    let mut queue = VecDeque::new();
+    dists.insert(start, 0);
+    queue.push_back(start);
+    while let Some(u) = queue.pop_front() {
+        let d = dists[&u];
+        for e in graph.out_edges(&u) {
+            if e.sink == start {
+                return Some(d + 1);
+            }
+            if let std::collections::hash_map::Entry::Vacant(x) = dists.entry(e.sink) {
+                x.insert(d + 1);
+                queue.push_back(e.sink);
+            }
+        }
+    }
+    None
+}
+
+/// Length of the shortest undirected cycle found by a breadth-first search from `start`.
+///
+/// Minimizing it over all vertices gives the girth.
+fn shortest_undirected_cycle_around<G>(graph: &G, start: VertexId) -> Option<usize>
+where
+    G: QueryableGraph,
+{
+    let mut res: Option<usize> = None;
+    // distances and edges from parents
+    let mut visited: HashMap<VertexId, (usize, Option<EdgeId>), RandomState> =
+        HashMap::with_hasher(new_random_state());
+    let mut queue = VecDeque::new();
+    visited.insert(start, (0, None));
+    queue.push_back(start);
+    while let Some(u) = queue.pop_front() {
+        let (d, come_from) = visited[&u];
+        if let Some(found) = res {
+            // no shorter cycle can be found from farther vertices.
+            if 2 * d >= found {
+                break;
+            }
+        }
+        for e in graph.out_edges(&u) {
+            if Some(e.id) == come_from {
+                continue;
+            }
+            match visited.get(&e.sink) {
+                Some((d_sink, _)) => {
+                    let found = d + d_sink + 1;
+                    res = Some(res.map_or(found, |x| x.min(found)));
+                }
+                None => {
+                    visited.insert(e.sink, (d + 1, Some(e.id)));
+                    queue.push_back(e.sink);
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ring<G: GrowableGraph>(n: usize) -> G {
+        let mut g = G::new();
+        let vs: Vec<_> = (0..n).map(|_| g.add_vertex()).collect();
+        for (u, v) in vs.iter().zip(vs.iter().cycle().skip(1)) {
+            g.add_edge(*u, *v);
+        }
+        g
+    }
+
+    #[test]
+    fn triangle() {
+        let g: undirected::TreeBackedGraph = ring(3);
+        assert_eq!(g.girth(), Some(3));
+        let g: directed::TreeBackedGraph = ring(3);
+        assert_eq!(g.girth(), Some(3));
+    }
+
+    #[test]
+    fn square() {
+        let g: undirected::TreeBackedGraph = ring(4);
+        assert_eq!(g.girth(), Some(4));
+        let g: directed::TreeBackedGraph = ring(4);
+        assert_eq!(g.girth(), Some(4));
+    }
+
+    #[test]
+    fn square_with_a_chord() {
+        let mut g: undirected::TreeBackedGraph = ring(4);
+        let vs: Vec<_> = g.iter_vertices().collect();
+        g.add_edge(vs[0], vs[2]);
+        assert_eq!(g.girth(), Some(3));
+    }
+
+    #[test]
+    fn tree() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v2);
+        g.add_edge(v3, v2);
+        assert_eq!(g.girth(), None);
+    }
+
+    #[test]
+    fn directed_without_directed_cycles() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v2);
+        g.add_edge(v1, v2);
+        assert_eq!(g.girth(), None);
+    }
+
+    #[test]
+    fn self_loop_and_parallel_edges() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v1);
+        assert_eq!(g.girth(), Some(2));
+        g.add_edge(v1, v1);
+        assert_eq!(g.girth(), Some(1));
+    }
+}
@@ -1,7 +1,8 @@
 //! Visualize tagged graphs in the graphviz format.
+use crate::random_state::new_random_state;
 use crate::{graph::*, tagged::Edge as _Edge};
-use ahash::RandomState;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
 
 /**
  * Provides graphviz labels for vertices.
@@ -182,33 +183,59 @@ where
     where
         W: std::io::Write,
     {
-        if Self::DIRECTED_OR_NOT {
-            writeln!(out, "digraph {} {{", graph_name)?;
-        } else {
-            writeln!(out, "graph {} {{", graph_name)?;
-        }
-        let mut vkey = HashMap::with_hasher(RandomState::new());
-        for (vid, vert) in self.iter_vertices() {
-            let (key, label) = vert.label();
-            if let Some(label) = label {
-                writeln!(out, "  {} [{}] ;", key, label)?;
-            } else {
-                writeln!(out, "  {} ;", key)?;
-            }
-            vkey.insert(vid, key);
-        }
-        let dir = if Self::DIRECTED_OR_NOT { "->" } else { "--" };
-        for (_, e) in self.iter_edges() {
-            let src = vkey.get(&e.source()).unwrap();
-            let snk = vkey.get(&e.sink()).unwrap();
-            if let Some(label) = e.label() {
-                writeln!(out, "  {} {} {} [{}] ;", src, dir, snk, label)?;
-            } else {
-                writeln!(out, "  {} {} {} ;", src, dir, snk)?;
-            }
-        }
-        writeln!(out, "}}")?;
-        Ok(())
+        dump(self, out, graph_name, |_| None, |_, _| None)
+    }
+
+    /**
+     * Same as [DumpInGraphviz::dump_in_graphviz], but edges are as thick as `weight` tells.
+     *
+     * `weight` gives `penwidth` of every edge, so scale weights within it as needed.
+     * The `penwidth` attribute is appended to labels of edges.
+     */
+    fn dump_in_graphviz_weighted<W, F>(
+        &self,
+        out: &mut W,
+        graph_name: &str,
+        weight: F,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+        F: Fn(&Self::Edge) -> f64,
+    {
+        dump(
+            self,
+            out,
+            graph_name,
+            |_| None,
+            |_, e| Some(format!("penwidth={}", weight(e))),
+        )
+    }
+
+    /**
+     * Same as [DumpInGraphviz::dump_in_graphviz], but given vertices and edges are in red.
+     *
+     * The `color=red` attribute is appended to labels of highlighted vertices and edges.
+     * The others are dumped as usual.
+     */
+    fn dump_in_graphviz_highlight<W, S>(
+        &self,
+        out: &mut W,
+        graph_name: &str,
+        highlight_vertices: &HashSet<VertexId, S>,
+        highlight_edges: &HashSet<EdgeId, S>,
+    ) -> std::io::Result<()>
+    where
+        W: std::io::Write,
+        S: BuildHasher,
+    {
+        let red = || "color=red".to_owned();
+        dump(
+            self,
+            out,
+            graph_name,
+            |v| highlight_vertices.contains(v).then(red),
+            |e, _| highlight_edges.contains(e).then(red),
+        )
     }
 }
 
@@ -220,3 +247,150 @@ where
     G::Edge: GraphvizLabelForEdge,
 {
 }
+
+fn dump<G, W, FV, FE>(
+    graph: &G,
+    out: &mut W,
+    graph_name: &str,
+    vertex_attrs: FV,
+    edge_attrs: FE,
+) -> std::io::Result<()>
+where
+    G: crate::tagged::QueryableTaggedGraph + DirectedOrNot + ?Sized,
+    G::LowerGraph: QueryableGraph,
+    G::Vertex: GraphvizLabelForVertex,
+    G::Edge: GraphvizLabelForEdge,
+    W: std::io::Write,
+    FV: Fn(&VertexId) -> Option<String>,
+    FE: Fn(&EdgeId, &G::Edge) -> Option<String>,
+{
+    if G::DIRECTED_OR_NOT {
+        writeln!(out, "digraph {} {{", graph_name)?;
+    } else {
+        writeln!(out, "graph {} {{", graph_name)?;
+    }
+    let mut vkey = HashMap::with_hasher(new_random_state());
+    for (vid, vert) in graph.iter_vertices() {
+        let (key, label) = vert.label();
+        if let Some(label) = merge_attrs(label, vertex_attrs(&vid)) {
+            writeln!(out, "  {} [{}] ;", key, label)?;
+        } else {
+            writeln!(out, "  {} ;", key)?;
+        }
+        vkey.insert(vid, key);
+    }
+    let dir = if G::DIRECTED_OR_NOT { "->" } else { "--" };
+    for (eid, e) in graph.iter_edges() {
+        let src = vkey.get(&e.source()).unwrap();
+        let snk = vkey.get(&e.sink()).unwrap();
+        if let Some(label) = merge_attrs(e.label(), edge_attrs(&eid, e)) {
+            writeln!(out, "  {} {} {} [{}] ;", src, dir, snk, label)?;
+        } else {
+            writeln!(out, "  {} {} {} ;", src, dir, snk)?;
+        }
+    }
+    writeln!(out, "}}")?;
+    Ok(())
+}
+
+fn merge_attrs(x: Option<String>, y: Option<String>) -> Option<String> {
+    match (x, y) {
+        (Some(x), Some(y)) => Some(format!("{}, {}", x, y)),
+        (x, None) => x,
+        (None, y) => y,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tagged::test_edges::WeightedEdge;
+    use crate::tagged::*;
+
+    #[derive(Debug, Clone, Hash, PartialEq, Eq)]
+    struct KeyedVertex(usize);
+
+    impl GraphvizLabelForVertex for KeyedVertex {
+        fn label(&self) -> (String, Option<String>) {
+            (format!("{}", self.0), None)
+        }
+    }
+
+    impl GraphvizLabelForEdge for WeightedEdge {
+        fn label(&self) -> Option<String> {
+            Some(format!("label={}", self.weight))
+        }
+    }
+
+    fn to_string<F>(f: F) -> String
+    where
+        F: FnOnce(&mut Vec<u8>) -> std::io::Result<()>,
+    {
+        let mut buf = vec![];
+        f(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn weighted() {
+        let mut g = NaiveTaggedGraph::<KeyedVertex, WeightedEdge>::new();
+        let v0 = g.overwrite_vertex(KeyedVertex(0));
+        let v1 = g.overwrite_vertex(KeyedVertex(1));
+        g.add_edge(WeightedEdge {
+            src: v0,
+            snk: v1,
+            weight: 3,
+        });
+        g.add_edge(WeightedEdge {
+            src: v1,
+            snk: v0,
+            weight: 5,
+        });
+        let trial =
+            to_string(|out| g.dump_in_graphviz_weighted(out, "trial", |e| e.weight as f64 / 2.0));
+        assert!(
+            trial.contains("  0 -> 1 [label=3, penwidth=1.5] ;\n"),
+            "{}",
+            trial
+        );
+        assert!(
+            trial.contains("  1 -> 0 [label=5, penwidth=2.5] ;\n"),
+            "{}",
+            trial
+        );
+    }
+
+    #[test]
+    fn highlight() {
+        let mut g = NaiveTaggedGraph::<KeyedVertex, WeightedEdge>::new();
+        let vs: Vec<_> = (0..3).map(|i| g.overwrite_vertex(KeyedVertex(i))).collect();
+        let es: Vec<_> = [(0, 1, 1), (1, 2, 2), (2, 0, 3)]
+            .iter()
+            .map(|(src, snk, weight)| {
+                g.add_edge(WeightedEdge {
+                    src: vs[*src],
+                    snk: vs[*snk],
+                    weight: *weight,
+                })
+            })
+            .collect();
+        let highlight_vertices: HashSet<_> = [vs[0], vs[1]].into_iter().collect();
+        let highlight_edges: HashSet<_> = [es[0], es[1]].into_iter().collect();
+        let trial = to_string(|out| {
+            g.dump_in_graphviz_highlight(out, "trial", &highlight_vertices, &highlight_edges)
+        });
+        assert!(trial.contains("  0 [color=red] ;\n"), "{}", trial);
+        assert!(trial.contains("  2 ;\n"), "{}", trial);
+        assert!(
+            trial.contains("  0 -> 1 [label=1, color=red] ;\n"),
+            "{}",
+            trial
+        );
+        assert!(
+            trial.contains("  1 -> 2 [label=2, color=red] ;\n"),
+            "{}",
+            trial
+        );
+        assert!(trial.contains("  2 -> 0 [label=3] ;\n"), "{}", trial);
+    }
+}
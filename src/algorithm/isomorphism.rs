@@ -0,0 +1,376 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{BTreeSet, HashMap, VecDeque};
+
+/// Trait and default implementation of graph isomorphism testing.
+pub trait Isomorphism
+where
+    Self: QueryableGraph + DirectedOrNot + Sized,
+{
+    /// Whether `other` is isomorphic to the graph, regardless of their ID's.
+    ///
+    /// A directed graph is never isomorphic to an undirected one.
+    /// Parallel edges and self-loops are respected,
+    /// i.e., numbers of edges between corresponding vertices must be equal.
+    ///
+    /// It is a VF2-style backtracking search,
+    /// which takes exponential time in the worst cases.
+    /// So it is intended for small graphs.
+    fn is_isomorphic<G2>(&self, other: &G2) -> bool
+    where
+        G2: QueryableGraph + DirectedOrNot,
+    {
+        if Self::DIRECTED_OR_NOT != G2::DIRECTED_OR_NOT
+            || self.vertex_size() != other.vertex_size()
+            || self.edge_size() != other.edge_size()
+        {
+            return false;
+        }
+        let pattern = Indexed::new(other, G2::DIRECTED_OR_NOT);
+        let host = Indexed::new(self, Self::DIRECTED_OR_NOT);
+        Matcher::new(&pattern, &host, Mode::Isomorphism).search()
+    }
+
+    /// Searches an occurrence of `pattern` in the graph.
+    ///
+    /// It returns an injective mapping from vertices of `pattern` to those of the graph,
+    /// such that, for every pair of pattern vertices,
+    /// there are at least as many edges between their images.
+    /// In other words, the occurrence need not be an induced subgraph.
+    /// It returns `None` if there is no occurrence,
+    /// or if one of both graphs is directed but the other is not.
+    ///
+    /// Like [Isomorphism::is_isomorphic], it takes exponential time in the worst cases.
+    fn find_subgraph_isomorphism<G2>(
+        &self,
+        pattern: &G2,
+    ) -> Option<HashMap<VertexId, VertexId, RandomState>>
+    where
+        G2: QueryableGraph + DirectedOrNot,
+    {
+        if Self::DIRECTED_OR_NOT != G2::DIRECTED_OR_NOT
+            || self.vertex_size() < pattern.vertex_size()
+            || self.edge_size() < pattern.edge_size()
+        {
+            return None;
+        }
+        let pattern = Indexed::new(pattern, G2::DIRECTED_OR_NOT);
+        let host = Indexed::new(self, Self::DIRECTED_OR_NOT);
+        let mut matcher = Matcher::new(&pattern, &host, Mode::Monomorphism);
+        if matcher.search() {
+            Some(matcher.mapping())
+        } else {
+            None
+        }
+    }
+}
+
+impl<G: QueryableGraph + DirectedOrNot> Isomorphism for G {}
+
+/// A graph whose vertices are indexed in ascending order of their ID's.
+struct Indexed {
+    vertices: Vec<VertexId>,
+    /// numbers of edges from one vertex to another,
+    /// in both orders for undirected graphs
+    multiplicities: HashMap<(usize, usize), usize, RandomState>,
+    in_degrees: Vec<usize>,
+    out_degrees: Vec<usize>,
+    /// neighbors in the undirected view, excluding the vertex itself
+    neighbors: Vec<BTreeSet<usize>>,
+}
+
+impl Indexed {
+    fn new<G: QueryableGraph>(graph: &G, directed: bool) -> Self {
+        let mut vertices: Vec<_> = graph.iter_vertices().collect();
+        vertices.sort();
+        let n = vertices.len();
+        let mut res = Self {
+            vertices,
+            multiplicities: HashMap::with_hasher(new_random_state()),
+            in_degrees: vec![0; n],
+            out_degrees: vec![0; n],
+            neighbors: vec![BTreeSet::new(); n],
+        };
+        for e in graph.iter_edges() {
+            let src = res.vertices.binary_search(&e.source).unwrap();
+            let snk = res.vertices.binary_search(&e.sink).unwrap();
+            *res.multiplicities.entry((src, snk)).or_insert(0) += 1;
+            res.out_degrees[src] += 1;
+            res.in_degrees[snk] += 1;
+            if !directed {
+                if src != snk {
+                    *res.multiplicities.entry((snk, src)).or_insert(0) += 1;
+                }
+                res.out_degrees[snk] += 1;
+                res.in_degrees[src] += 1;
+            }
+            if src != snk {
+                res.neighbors[src].insert(snk);
+                res.neighbors[snk].insert(src);
+            }
+        }
+        res
+    }
+
+    fn multiplicity(&self, src: usize, snk: usize) -> usize {
+        self.multiplicities.get(&(src, snk)).copied().unwrap_or(0)
+    }
+}
+
+enum Mode {
+    /// Degrees and numbers of edges must be equal.
+    Isomorphism,
+    /// Degrees and numbers of edges in the host are no less than those in the pattern.
+    Monomorphism,
+}
+
+impl Mode {
+    fn fits(&self, pattern: usize, host: usize) -> bool {
+        match self {
+            Mode::Isomorphism => pattern == host,
+            Mode::Monomorphism => pattern <= host,
+        }
+    }
+}
+
+/// Backtracking search for mapping vertices of `pattern` to those of `host`.
+struct Matcher<'a> {
+    pattern: &'a Indexed,
+    host: &'a Indexed,
+    mode: Mode,
+    /// pattern vertices in the order to be mapped
+    order: Vec<usize>,
+    /// for each pattern vertex, a neighbor mapped before it, if any
+    parents: Vec<Option<usize>>,
+    /// from pattern vertices to host vertices
+    mapping: Vec<Option<usize>>,
+    /// whether host vertices are mapped
+    used: Vec<bool>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(pattern: &'a Indexed, host: &'a Indexed, mode: Mode) -> Self {
+        // Breadth-first, so that each pattern vertex but roots of components
+        // has a neighbor mapped before it.
+        // Roots are the ones with most neighbors.
+        let n = pattern.vertices.len();
+        let mut roots: Vec<_> = (0..n).collect();
+        roots.sort_by_key(|v| std::cmp::Reverse(pattern.neighbors[*v].len()));
+        let mut order = Vec::with_capacity(n);
+        let mut parents = vec![None; n];
+        let mut visited = vec![false; n];
+        for root in roots {
+            if visited[root] {
+                continue;
+            }
+            visited[root] = true;
+            let mut queue = VecDeque::new();
+            queue.push_back(root);
+            while let Some(v) = queue.pop_front() {
+                order.push(v);
+                for w in pattern.neighbors[v].iter() {
+                    if !visited[*w] {
+                        visited[*w] = true;
+                        parents[*w] = Some(v);
+                        queue.push_back(*w);
+                    }
+                }
+            }
+        }
+        Self {
+            pattern,
+            host,
+            mode,
+            order,
+            parents,
+            mapping: vec![None; n],
+            used: vec![false; host.vertices.len()],
+        }
+    }
+
+    fn search(&mut self) -> bool {
+        self.extend(0)
+    }
+
+    fn mapping(&self) -> HashMap<VertexId, VertexId, RandomState> {
+        let mut res = HashMap::with_hasher(new_random_state());
+        for (p, h) in self.mapping.iter().enumerate() {
+            res.insert(self.pattern.vertices[p], self.host.vertices[h.unwrap()]);
+        }
+        res
+    }
+
+    fn extend(&mut self, depth: usize) -> bool {
+        if depth == self.order.len() {
+            return true;
+        }
+        let p = self.order[depth];
+        let candidates: Vec<usize> = match self.parents[p] {
+            Some(parent) => {
+                let h = self.mapping[parent].unwrap();
+                self.host.neighbors[h].iter().copied().collect()
+            }
+            None => (0..self.host.vertices.len()).collect(),
+        };
+        for h in candidates {
+            if self.used[h] || !self.is_feasible(depth, p, h) {
+                continue;
+            }
+            self.mapping[p] = Some(h);
+            self.used[h] = true;
+            if self.extend(depth + 1) {
+                return true;
+            }
+            self.mapping[p] = None;
+            self.used[h] = false;
+        }
+        false
+    }
+
+    fn is_feasible(&self, depth: usize, p: usize, h: usize) -> bool {
+        let pattern = self.pattern;
+        let host = self.host;
+        let mode = &self.mode;
+        if !mode.fits(pattern.in_degrees[p], host.in_degrees[h])
+            || !mode.fits(pattern.out_degrees[p], host.out_degrees[h])
+            || !mode.fits(pattern.multiplicity(p, p), host.multiplicity(h, h))
+        {
+            return false;
+        }
+        self.order[..depth].iter().all(|q| {
+            let k = self.mapping[*q].unwrap();
+            mode.fits(pattern.multiplicity(p, *q), host.multiplicity(h, k))
+                && mode.fits(pattern.multiplicity(*q, p), host.multiplicity(k, h))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn build<G: GrowableGraph>(n: usize, edges: &[(usize, usize)]) -> G {
+        let mut g = G::new();
+        let vs: Vec<_> = (0..n).map(|_| g.add_vertex()).collect();
+        for (src, snk) in edges.iter() {
+            g.add_edge(vs[*src], vs[*snk]);
+        }
+        g
+    }
+
+    #[test]
+    fn triangles() {
+        let g1: undirected::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        // same shape but different ID's
+        let mut g2 = undirected::AdjacentListGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g2.add_vertex()).collect();
+        g2.remove_vertex(&vs[0]).for_each(drop);
+        g2.add_edge(vs[3], vs[1]);
+        g2.add_edge(vs[2], vs[3]);
+        g2.add_edge(vs[1], vs[2]);
+        assert!(g1.is_isomorphic(&g2));
+        assert!(g2.is_isomorphic(&g1));
+    }
+
+    #[test]
+    fn triangle_and_path() {
+        let triangle: undirected::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        let path: undirected::TreeBackedGraph = build(4, &[(0, 1), (1, 2), (2, 3)]);
+        assert!(!triangle.is_isomorphic(&path));
+        let path: undirected::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (1, 2)]);
+        assert!(!triangle.is_isomorphic(&path));
+    }
+
+    #[test]
+    fn directions() {
+        let cycle: directed::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        let acyclic: directed::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (0, 2)]);
+        assert!(!cycle.is_isomorphic(&acyclic));
+        let reversed: directed::AdjacentListGraph = build(3, &[(1, 0), (2, 1), (0, 2)]);
+        assert!(cycle.is_isomorphic(&reversed));
+        let undirected: undirected::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        assert!(!cycle.is_isomorphic(&undirected));
+    }
+
+    #[quickcheck]
+    fn isomorphic_to_relabeled(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        let relabeled: MappedGraph<directed::AdjacentListGraph> = g.transform();
+        assert!(g.graph.is_isomorphic(&relabeled.graph));
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let relabeled: MappedGraph<undirected::AdjacentListGraph> = g.transform();
+        assert!(g.graph.is_isomorphic(&relabeled.graph));
+    }
+
+    fn check_occurrence<G, P>(
+        host: &G,
+        pattern: &P,
+        mapping: &HashMap<VertexId, VertexId, RandomState>,
+    ) where
+        G: QueryableGraph,
+        P: QueryableGraph,
+    {
+        assert_eq!(mapping.len(), pattern.vertex_size());
+        let images: std::collections::HashSet<_> = mapping.values().collect();
+        assert_eq!(images.len(), mapping.len());
+        for e in pattern.iter_edges() {
+            let src = mapping[&e.source];
+            let snk = mapping[&e.sink];
+            assert!(
+                host.edges_connecting(&src, &snk).count()
+                    >= pattern.edges_connecting(&e.source, &e.sink).count()
+            );
+        }
+    }
+
+    #[test]
+    fn triangle_in_larger_graph() {
+        let triangle: undirected::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        let host: undirected::TreeBackedGraph = build(
+            6,
+            &[
+                (0, 1),
+                (1, 2),
+                (2, 3),
+                (3, 4),
+                (4, 5),
+                (5, 0),
+                (1, 4),
+                (4, 2),
+            ],
+        );
+        let mapping = host.find_subgraph_isomorphism(&triangle).unwrap();
+        check_occurrence(&host, &triangle, &mapping);
+        let mut images: Vec<_> = mapping.values().map(|v| v.0).collect();
+        images.sort();
+        assert_eq!(images, vec![1, 2, 4]);
+
+        let square: undirected::TreeBackedGraph = build(4, &[(0, 1), (1, 2), (2, 3), (3, 0)]);
+        assert!(square.find_subgraph_isomorphism(&triangle).is_none());
+    }
+
+    #[test]
+    fn directed_pattern() {
+        let cycle: directed::TreeBackedGraph = build(3, &[(0, 1), (1, 2), (2, 0)]);
+        let acyclic: directed::TreeBackedGraph = build(4, &[(0, 1), (1, 2), (0, 2), (2, 3)]);
+        assert!(acyclic.find_subgraph_isomorphism(&cycle).is_none());
+        let host: directed::TreeBackedGraph = build(4, &[(3, 1), (1, 2), (2, 3), (0, 3)]);
+        let mapping = host.find_subgraph_isomorphism(&cycle).unwrap();
+        check_occurrence(&host, &cycle, &mapping);
+    }
+
+    #[quickcheck]
+    fn subgraphs_occur(ops: Ops) {
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let mut sub = g.graph.clone();
+        let edges: Vec<_> = sub.iter_edges().step_by(2).map(|e| e.id).collect();
+        for eid in edges {
+            sub.remove_edge(&eid);
+        }
+        let mapping = g.graph.find_subgraph_isomorphism(&sub).unwrap();
+        check_occurrence(&g.graph, &sub, &mapping);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use bimap::BiHashMap;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Trait and default implementation of building line graphs.
+pub trait LineGraph
+where
+    Self: QueryableGraph + DirectedOrNot + Sized,
+{
+    /// The line graph, where each edge of this graph becomes a vertex,
+    /// along with a map between vertices of the line graph and their original edges.
+    ///
+    /// * For directed graphs, there is an edge from `e1` to `e2`
+    ///   whenever the sink of `e1` is the source of `e2`.
+    ///   A self-loop thus becomes a vertex with a self-loop.
+    /// * For undirected graphs, two distinct edges are adjacent
+    ///   whenever they share at least one endpoint.
+    ///   Each pair of them is connected once.
+    fn line_graph<G2: GrowableGraph>(
+        &self,
+    ) -> (G2, BiHashMap<VertexId, EdgeId, RandomState, RandomState>) {
+        let mut edges: Vec<_> = self.iter_edges().collect();
+        edges.sort();
+        let mut graph = G2::with_capacity(edges.len(), 0);
+        let mut vmap = BiHashMap::with_hashers(new_random_state(), new_random_state());
+        for e in edges.iter() {
+            let new_v = graph.add_vertex();
+            vmap.insert(new_v, e.id);
+        }
+        let to_new = |e: EdgeId| *vmap.get_by_right(&e).unwrap();
+        let mut pairs = BTreeSet::new();
+        if Self::DIRECTED_OR_NOT {
+            for e1 in edges.iter() {
+                for e2 in self.out_edges(&e1.sink) {
+                    pairs.insert((e1.id, e2.id));
+                }
+            }
+        } else {
+            let mut incidence: BTreeMap<VertexId, Vec<EdgeId>> = BTreeMap::new();
+            for e in edges.iter() {
+                incidence.entry(e.source).or_default().push(e.id);
+                if e.sink != e.source {
+                    incidence.entry(e.sink).or_default().push(e.id);
+                }
+            }
+            for incident in incidence.values() {
+                for (i, e1) in incident.iter().enumerate() {
+                    for e2 in incident[i + 1..].iter() {
+                        pairs.insert((*e1, *e2));
+                    }
+                }
+            }
+        }
+        for (e1, e2) in pairs {
+            graph.add_edge(to_new(e1), to_new(e2));
+        }
+        (graph, vmap)
+    }
+}
+
+impl<G: QueryableGraph + DirectedOrNot> LineGraph for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Edges of the line graph, as pairs of original edges.
+    fn sorted_adjacency<G: QueryableGraph>(
+        g: &G,
+        vmap: &BiHashMap<VertexId, EdgeId, RandomState, RandomState>,
+    ) -> Vec<(EdgeId, EdgeId)> {
+        let to_old = |v: &VertexId| *vmap.get_by_left(v).unwrap();
+        let mut res: Vec<_> = g
+            .iter_edges()
+            .map(|e| (to_old(&e.source), to_old(&e.sink)))
+            .collect();
+        res.sort();
+        res
+    }
+
+    #[test]
+    fn directed_path() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let es: Vec<_> = (0..3).map(|i| g.add_edge(vs[i], vs[i + 1])).collect();
+        let (lg, vmap): (directed::TreeBackedGraph, _) = g.line_graph();
+        assert_eq!(lg.vertex_size(), 3);
+        let mut old: Vec<_> = lg
+            .iter_vertices()
+            .map(|v| *vmap.get_by_left(&v).unwrap())
+            .collect();
+        old.sort();
+        assert_eq!(old, es);
+        assert_eq!(
+            sorted_adjacency(&lg, &vmap),
+            vec![(es[0], es[1]), (es[1], es[2])]
+        );
+    }
+
+    #[test]
+    fn directed_path_reversed_edge() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let e0 = g.add_edge(vs[0], vs[1]);
+        let e1 = g.add_edge(vs[2], vs[1]);
+        let e2 = g.add_edge(vs[2], vs[3]);
+        let (lg, vmap): (directed::TreeBackedGraph, _) = g.line_graph();
+        assert_eq!(lg.vertex_size(), 3);
+        assert_eq!(lg.edge_size(), 0);
+        for e in [e0, e1, e2] {
+            assert!(lg.contains_vertex(vmap.get_by_right(&e).unwrap()));
+        }
+    }
+
+    #[test]
+    fn undirected_path() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let e0 = g.add_edge(vs[0], vs[1]);
+        let e1 = g.add_edge(vs[2], vs[1]);
+        let e2 = g.add_edge(vs[2], vs[3]);
+        let (lg, vmap): (undirected::TreeBackedGraph, _) = g.line_graph();
+        assert_eq!(lg.vertex_size(), 3);
+        assert_eq!(lg.edge_size(), 2);
+        assert_eq!(sorted_adjacency(&lg, &vmap), vec![(e0, e1), (e1, e2)]);
+    }
+
+    #[test]
+    fn undirected_parallel_edges() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v0);
+        g.add_edge(v1, v1);
+        let (lg, _): (undirected::TreeBackedGraph, _) = g.line_graph();
+        assert_eq!(lg.vertex_size(), 3);
+        // a triangle
+        assert_eq!(lg.edge_size(), 3);
+    }
+}
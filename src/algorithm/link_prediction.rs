@@ -0,0 +1,97 @@
+use crate::graph::*;
+use std::collections::BTreeSet;
+
+/// Trait and default implementation of link-prediction scores on the undirected view.
+///
+/// Directions of edges are ignored.
+/// Neighbors exclude the vertex itself, i.e., self-loops, and parallel edges count once.
+pub trait LinkPrediction
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Jaccard similarity between neighbors of `a` and `b`,
+    /// i.e., $\|N(a) \cap N(b)\| / \|N(a) \cup N(b)\|$.
+    ///
+    /// It is 0 when neither has any neighbors.
+    fn jaccard_similarity(&self, a: &VertexId, b: &VertexId) -> f64 {
+        let na = neighbors(self, a);
+        let nb = neighbors(self, b);
+        let union = na.union(&nb).count();
+        if union == 0 {
+            return 0.0;
+        }
+        na.intersection(&nb).count() as f64 / union as f64
+    }
+
+    /// Adamic-Adar index of `a` and `b`,
+    /// i.e., $\sum_{z \in N(a) \cap N(b)} 1 / \ln \|N(z)\|$.
+    ///
+    /// Common neighbors of degree 1, which only occur when `a == b`,
+    /// are skipped rather than contributing infinity.
+    fn adamic_adar(&self, a: &VertexId, b: &VertexId) -> f64 {
+        let na = neighbors(self, a);
+        let nb = neighbors(self, b);
+        na.intersection(&nb)
+            .map(|z| neighbors(self, z).len())
+            .filter(|d| *d > 1)
+            .map(|d| 1.0 / (d as f64).ln())
+            .sum()
+    }
+}
+
+impl<G: QueryableGraph> LinkPrediction for G {}
+
+fn neighbors<G: QueryableGraph>(graph: &G, v: &VertexId) -> BTreeSet<VertexId> {
+    graph
+        .out_edges(v)
+        .map(|e| e.sink)
+        .chain(graph.in_edges(v).map(|e| e.source))
+        .filter(|u| u != v)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx_eq(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    fn check<G>()
+    where
+        G: GrowableGraph + QueryableGraph,
+    {
+        // a and b share c and d; a also knows e, and b knows f.
+        // d also knows f.
+        let mut g = G::new();
+        let vs: Vec<_> = (0..7).map(|_| g.add_vertex()).collect();
+        let (a, b, c, d, e, f, lonely) = (vs[0], vs[1], vs[2], vs[3], vs[4], vs[5], vs[6]);
+        g.add_edge(a, c);
+        g.add_edge(c, b);
+        g.add_edge(a, d);
+        g.add_edge(a, d);
+        g.add_edge(b, d);
+        g.add_edge(a, e);
+        g.add_edge(b, f);
+        g.add_edge(d, f);
+        g.add_edge(a, a);
+        // N(a) = {c, d, e} and N(b) = {c, d, f}
+        assert!(approx_eq(g.jaccard_similarity(&a, &b), 2.0 / 4.0));
+        assert!(approx_eq(g.jaccard_similarity(&b, &a), 2.0 / 4.0));
+        assert!(approx_eq(g.jaccard_similarity(&a, &lonely), 0.0));
+        assert!(approx_eq(g.jaccard_similarity(&lonely, &lonely), 0.0));
+        // N(c) = {a, b} and N(d) = {a, b, f}
+        let expected = 1.0 / 2f64.ln() + 1.0 / 3f64.ln();
+        assert!(approx_eq(g.adamic_adar(&a, &b), expected));
+        assert!(approx_eq(g.adamic_adar(&a, &lonely), 0.0));
+        // N(e) = {a}
+        assert!(approx_eq(g.adamic_adar(&e, &e), 1.0 / 3f64.ln()));
+    }
+
+    #[test]
+    fn small_graph() {
+        check::<directed::TreeBackedGraph>();
+        check::<undirected::TreeBackedGraph>();
+    }
+}
@@ -0,0 +1,142 @@
+use super::disjoint_set::DisjointSet;
+use crate::graph::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+
+/// Trait and default implementation of randomized global minimum cuts.
+pub trait KargerMinCut
+where
+    Self: QueryableGraph + Sized,
+{
+    /// A global minimum cut of the undirected view of the graph, with high probability.
+    ///
+    /// It returns the size of the cut and its edges in ascending order of their ID's.
+    /// Directions of edges are ignored and self-loops never cross a cut.
+    /// A disconnected graph, or one with less than 2 vertices, has an empty cut.
+    ///
+    /// Each trial contracts edges in a random order until two supernodes remain.
+    /// The best cut across `trials` trials, at least one, is returned.
+    /// A single trial finds a minimum cut with probability at least $2 / (\|V\|(\|V\|-1))$,
+    /// so $O(\|V\|^2 \log \|V\|)$ trials find one with high probability.
+    fn karger_min_cut<R: Rng>(&self, rng: &mut R, trials: usize) -> (usize, Vec<EdgeId>) {
+        let mut vertices: Vec<_> = self.iter_vertices().collect();
+        vertices.sort();
+        if vertices.len() < 2 {
+            return (0, vec![]);
+        }
+        let mut edges: Vec<_> = self
+            .iter_edges()
+            .filter(|e| e.source != e.sink)
+            .map(|e| {
+                let src = vertices.binary_search(&e.source).unwrap();
+                let snk = vertices.binary_search(&e.sink).unwrap();
+                (e.id, src, snk)
+            })
+            .collect();
+        edges.sort();
+
+        let mut best: Option<Vec<EdgeId>> = None;
+        let mut order = edges.clone();
+        for _ in 0..trials.max(1) {
+            order.shuffle(rng);
+            let mut supernodes = DisjointSet::new(vertices.len());
+            let mut remaining = vertices.len();
+            for (_, src, snk) in order.iter() {
+                if remaining == 2 {
+                    break;
+                }
+                if supernodes.union(*src, *snk) {
+                    remaining -= 1;
+                }
+            }
+            let cut: Vec<_> = edges
+                .iter()
+                .filter(|(_, src, snk)| supernodes.find(*src) != supernodes.find(*snk))
+                .map(|(eid, _, _)| *eid)
+                .collect();
+            if best.as_ref().map_or(true, |best| cut.len() < best.len()) {
+                best = Some(cut);
+            }
+        }
+        let best = best.unwrap();
+        (best.len(), best)
+    }
+}
+
+impl<G: QueryableGraph> KargerMinCut for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn bridged_cliques() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let left: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        let right: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        for side in [&left, &right] {
+            for (i, u) in side.iter().enumerate() {
+                for v in side[i + 1..].iter() {
+                    g.add_edge(*u, *v);
+                }
+            }
+        }
+        let bridge0 = g.add_edge(left[0], right[0]);
+        let bridge1 = g.add_edge(right[1], left[1]);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let (size, cut) = g.karger_min_cut(&mut rng, 200);
+        assert_eq!(size, 2);
+        assert_eq!(cut, vec![bridge0, bridge1]);
+    }
+
+    #[test]
+    fn trivial() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut g = directed::TreeBackedGraph::new();
+        assert_eq!(g.karger_min_cut(&mut rng, 1), (0, vec![]));
+        let v0 = g.add_vertex();
+        g.add_edge(v0, v0);
+        assert_eq!(g.karger_min_cut(&mut rng, 1), (0, vec![]));
+        let v1 = g.add_vertex();
+        let e0 = g.add_edge(v1, v0);
+        let e1 = g.add_edge(v0, v1);
+        assert_eq!(g.karger_min_cut(&mut rng, 1), (2, vec![e0, e1]));
+    }
+
+    #[quickcheck]
+    fn cuts_disconnect(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        let mut rng = StdRng::seed_from_u64(0);
+        let (size, cut) = g.graph.karger_min_cut(&mut rng, 3);
+        assert_eq!(size, cut.len());
+        if g.graph.vertex_size() < 2 {
+            return;
+        }
+        let mut rest = g.graph.clone();
+        for eid in cut.iter() {
+            rest.remove_edge(eid).unwrap();
+        }
+        let start = rest.iter_vertices().next().unwrap();
+        let mut visited = vec![start];
+        let mut i = 0;
+        while i < visited.len() {
+            let v = visited[i];
+            i += 1;
+            let neighbors: Vec<_> = rest
+                .out_edges(&v)
+                .map(|e| e.sink)
+                .chain(rest.in_edges(&v).map(|e| e.source))
+                .collect();
+            for w in neighbors {
+                if !visited.contains(&w) {
+                    visited.push(w);
+                }
+            }
+        }
+        assert!(visited.len() < rest.vertex_size());
+    }
+}
@@ -3,4 +3,71 @@ mod simple_cycle;
 pub use self::simple_cycle::*;
 mod toposort;
 pub use self::toposort::*;
+mod cycle_detection;
+pub use self::cycle_detection::*;
+mod girth;
+pub use self::girth::*;
+mod neighborhood;
+pub use self::neighborhood::*;
+mod triangle;
+pub use self::triangle::*;
+mod clique;
+pub use self::clique::*;
+mod biconnected;
+pub use self::biconnected::*;
+mod floyd_warshall;
+pub use self::floyd_warshall::*;
+mod dijkstra;
+pub use self::dijkstra::*;
+mod bipartite_matching;
+pub use self::bipartite_matching::*;
+mod vertex_cover;
+pub use self::vertex_cover::*;
+mod min_cut;
+pub use self::min_cut::*;
+mod isomorphism;
+pub use self::isomorphism::*;
+mod spanning_tree_count;
+pub use self::spanning_tree_count::*;
+mod line_graph;
+pub use self::line_graph::*;
+mod ear_decomposition;
+pub use self::ear_decomposition::*;
+mod bfs_layers;
+pub use self::bfs_layers::*;
+mod reachability;
+pub use self::reachability::*;
+mod coloring;
+pub use self::coloring::*;
+mod traversal_tree;
+pub use self::traversal_tree::*;
+mod sample_edges;
+pub use self::sample_edges::*;
+mod cut;
+pub use self::cut::*;
+mod modularity;
+pub use self::modularity::*;
+mod wl_hash;
+pub use self::wl_hash::*;
+mod tree_depth;
+pub use self::tree_depth::*;
+mod eccentricity;
+pub use self::eccentricity::*;
+mod components;
+pub use self::components::*;
+mod link_prediction;
+pub use self::link_prediction::*;
+mod multi_source_bfs;
+pub use self::multi_source_bfs::*;
+mod path_count;
+pub use self::path_count::*;
+mod betweenness;
+pub use self::betweenness::*;
+mod planarity;
+pub use self::planarity::*;
 pub mod graphviz;
+
+mod disjoint_set;
+#[cfg(test)]
+mod test_graphs;
+mod undirected_view;
@@ -0,0 +1,132 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// Trait and default implementation of evaluating community partitions.
+pub trait Modularity
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Newman's modularity of a partition of vertices into communities on the undirected view of the graph.
+    ///
+    /// It is $\sum_c \left( L_c / m - (d_c / 2m)^2 \right)$,
+    /// where $m$ is the number of edges,
+    /// $L_c$ is the number of edges inside community $c$,
+    /// and $d_c$ is the total degree of vertices in $c$.
+    /// Directions of edges are ignored, parallel edges count separately,
+    /// and a self-loop is an inner edge adding 2 to the degree.
+    /// A graph without edges has modularity 0.
+    ///
+    /// # Panics
+    ///
+    /// `communities` must partition vertices of the graph,
+    /// i.e., each vertex is in exactly one community,
+    /// and no community contains any other vertex.
+    fn modularity(&self, communities: &[Vec<VertexId>]) -> f64 {
+        let mut community_of: HashMap<VertexId, usize, RandomState> =
+            HashMap::with_hasher(new_random_state());
+        for (i, c) in communities.iter().enumerate() {
+            for v in c.iter() {
+                assert!(self.contains_vertex(v), "unknown vertex {:?}", v);
+                assert!(
+                    community_of.insert(*v, i).is_none(),
+                    "vertex {:?} in multiple communities",
+                    v
+                );
+            }
+        }
+        assert_eq!(
+            community_of.len(),
+            self.vertex_size(),
+            "vertices out of communities"
+        );
+
+        let mut inner = vec![0usize; communities.len()];
+        let mut degrees = vec![0usize; communities.len()];
+        let mut m = 0usize;
+        for e in self.iter_edges() {
+            let c_src = community_of[&e.source];
+            let c_snk = community_of[&e.sink];
+            m += 1;
+            degrees[c_src] += 1;
+            degrees[c_snk] += 1;
+            if c_src == c_snk {
+                inner[c_src] += 1;
+            }
+        }
+        if m == 0 {
+            return 0.0;
+        }
+        let m = m as f64;
+        inner
+            .iter()
+            .zip(degrees.iter())
+            .map(|(l, d)| {
+                let d = *d as f64 / (2.0 * m);
+                *l as f64 / m - d * d
+            })
+            .sum()
+    }
+}
+
+impl<G: QueryableGraph> Modularity for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two cliques of 4 vertices joined by one edge.
+    fn two_cliques() -> (undirected::TreeBackedGraph, Vec<VertexId>) {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..8).map(|_| g.add_vertex()).collect();
+        for side in [&vs[..4], &vs[4..]] {
+            for (i, u) in side.iter().enumerate() {
+                for v in side[i + 1..].iter() {
+                    g.add_edge(*u, *v);
+                }
+            }
+        }
+        g.add_edge(vs[3], vs[4]);
+        (g, vs)
+    }
+
+    #[test]
+    fn natural_partition() {
+        let (g, vs) = two_cliques();
+        let natural = g.modularity(&[vs[..4].to_vec(), vs[4..].to_vec()]);
+        // m = 13, L_c = 6 and d_c = 13 for both communities
+        let expected = 2.0 * (6.0 / 13.0 - 0.25);
+        assert!((natural - expected).abs() < 1e-12);
+        assert!(natural > 0.4);
+
+        let single = g.modularity(std::slice::from_ref(&vs));
+        assert!(single.abs() < 1e-12);
+        let mixed = g.modularity(&[
+            vec![vs[0], vs[1], vs[4], vs[5]],
+            vec![vs[2], vs[3], vs[6], vs[7]],
+        ]);
+        assert!(mixed < natural);
+    }
+
+    #[test]
+    fn no_edges() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v = g.add_vertex();
+        assert_eq!(g.modularity(&[vec![v]]), 0.0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_a_partition() {
+        let (g, vs) = two_cliques();
+        g.modularity(&[vs[..4].to_vec(), vs[3..].to_vec()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn not_covering() {
+        let (g, vs) = two_cliques();
+        g.modularity(&[vs[..4].to_vec()]);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{hash_map::Entry, HashMap};
+
+/// Trait and default implementation of breadth-first search from multiple sources.
+pub trait MultiSourceBfs
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Hops along out-edges from the nearest of `sources` to every reachable vertex.
+    ///
+    /// Sources are of distance 0.
+    /// Sources not in the graph are ignored, and duplicated ones are fine.
+    /// Unreachable vertices are absent.
+    ///
+    /// See `FrozenGraph::par_multi_source_bfs`
+    /// for a parallel counterpart behind the `rayon` feature.
+    fn multi_source_bfs<I>(&self, sources: I) -> HashMap<VertexId, usize, RandomState>
+    where
+        I: IntoIterator<Item = VertexId>,
+    {
+        let mut res = HashMap::with_hasher(new_random_state());
+        let mut current = vec![];
+        for v in sources {
+            if !self.contains_vertex(&v) {
+                continue;
+            }
+            if let Entry::Vacant(x) = res.entry(v) {
+                x.insert(0);
+                current.push(v);
+            }
+        }
+        let mut d = 0;
+        while !current.is_empty() {
+            d += 1;
+            let mut next = vec![];
+            for v in current.iter() {
+                for e in self.out_edges(v) {
+                    if let Entry::Vacant(x) = res.entry(e.sink) {
+                        x.insert(d);
+                        next.push(e.sink);
+                    }
+                }
+            }
+            current = next;
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> MultiSourceBfs for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::{Ops, TreeBackedGraph};
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn nearest_source(ops: Ops, picks: Vec<usize>) {
+        let graph = MappedGraph::<TreeBackedGraph>::from(&ops).graph;
+        let vertices: Vec<_> = graph.iter_vertices().collect();
+        if vertices.is_empty() {
+            return;
+        }
+        let sources: Vec<_> = picks
+            .iter()
+            .map(|i| vertices[i % vertices.len()])
+            .chain(Some(VertexId(1000)))
+            .collect();
+        let trial = graph.multi_source_bfs(sources.iter().copied());
+        for v in vertices.iter() {
+            let oracle = sources.iter().filter_map(|s| graph.distance(s, v)).min();
+            assert_eq!(trial.get(v).copied(), oracle);
+        }
+        assert_eq!(
+            trial.len(),
+            trial.keys().filter(|v| graph.contains_vertex(v)).count()
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::{HashMap, VecDeque};
+
+/// Trait and default implementation of finding vertices within a number of hops.
+pub trait Neighborhood
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Vertices within `radius` hops from `start` along out-edges, with their distances.
+    ///
+    /// `start` itself is included with distance 0,
+    /// unless it is not in the graph, where the result is empty.
+    fn neighborhood(
+        &self,
+        start: &VertexId,
+        radius: usize,
+    ) -> HashMap<VertexId, usize, RandomState> {
+        bounded_bfs(self, start, radius, |v| self.out_edges(v).map(|e| e.sink))
+    }
+
+    /// Same as [Neighborhood::neighborhood] but following both out-edges and in-edges.
+    fn undirected_neighborhood(
+        &self,
+        start: &VertexId,
+        radius: usize,
+    ) -> HashMap<VertexId, usize, RandomState> {
+        bounded_bfs(self, start, radius, |v| {
+            self.out_edges(v)
+                .map(|e| e.sink)
+                .chain(self.in_edges(v).map(|e| e.source))
+        })
+    }
+}
+
+impl<G: QueryableGraph> Neighborhood for G {}
+
+fn bounded_bfs<'a, G, F, I>(
+    graph: &'a G,
+    start: &VertexId,
+    radius: usize,
+    next_vertices: F,
+) -> HashMap<VertexId, usize, RandomState>
+where
+    G: QueryableGraph,
+    F: Fn(&VertexId) -> I,
+    I: Iterator<Item = VertexId> + 'a,
+{
+    let mut res = HashMap::with_hasher(new_random_state());
+    if !graph.contains_vertex(start) {
+        return res;
+    }
+    let mut queue = VecDeque::new();
+    res.insert(*start, 0);
+    queue.push_back((*start, 0));
+    while let Some((v, d)) = queue.pop_front() {
+        if d == radius {
+            continue;
+        }
+        for nxt in next_vertices(&v) {
+            if let std::collections::hash_map::Entry::Vacant(e) = res.entry(nxt) {
+                e.insert(d + 1);
+                queue.push_back((nxt, d + 1));
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn path() -> (directed::TreeBackedGraph, Vec<VertexId>) {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        for (u, v) in vs.iter().zip(vs.iter().skip(1)) {
+            g.add_edge(*u, *v);
+        }
+        (g, vs)
+    }
+
+    #[test]
+    fn neighborhood_on_path() {
+        let (g, vs) = path();
+        let trial: BTreeMap<_, _> = g.neighborhood(&vs[1], 2).into_iter().collect();
+        let oracle: BTreeMap<_, _> = [(vs[1], 0), (vs[2], 1), (vs[3], 2)].into_iter().collect();
+        assert_eq!(trial, oracle);
+    }
+
+    #[test]
+    fn undirected_neighborhood_on_path() {
+        let (g, vs) = path();
+        let trial: BTreeMap<_, _> = g.undirected_neighborhood(&vs[1], 2).into_iter().collect();
+        let oracle: BTreeMap<_, _> = [(vs[0], 1), (vs[1], 0), (vs[2], 1), (vs[3], 2)]
+            .into_iter()
+            .collect();
+        assert_eq!(trial, oracle);
+    }
+
+    #[test]
+    fn zero_radius() {
+        let (g, vs) = path();
+        let trial: BTreeMap<_, _> = g.neighborhood(&vs[4], 0).into_iter().collect();
+        let oracle: BTreeMap<_, _> = [(vs[4], 0)].into_iter().collect();
+        assert_eq!(trial, oracle);
+    }
+}
@@ -0,0 +1,103 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use std::collections::HashMap;
+
+/// Trait and default implementation of counting walks of a given length.
+pub trait PathCount
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Number of walks of exactly `length` edges from `source` to `sink` along out-edges.
+    ///
+    /// Walks, unlike simple paths, may visit a vertex or an edge more than once,
+    /// e.g., going around a cycle.
+    /// Parallel edges make distinct walks.
+    /// Thus, it is the entry of the `length`-th power of the adjacency matrix.
+    /// The only walk of length 0 is the one staying at `source`,
+    /// and there is no walk if either endpoint is not in the graph.
+    ///
+    /// It is computed by dynamic programming over layers, i.e.,
+    /// numbers of walks from `source` to every vertex are extended by one edge at a time,
+    /// in $O(\text{length} \cdot \|E\|)$.
+    ///
+    /// # Panics
+    ///
+    /// It panics when the number, or any intermediate one, overflows `u64`.
+    fn count_paths(&self, source: &VertexId, sink: &VertexId, length: usize) -> u64 {
+        if !self.contains_vertex(source) || !self.contains_vertex(sink) {
+            return 0;
+        }
+        let mut counts = HashMap::with_hasher(new_random_state());
+        counts.insert(*source, 1u64);
+        for _ in 0..length {
+            let mut next = HashMap::with_hasher(new_random_state());
+            for (v, n) in counts.iter() {
+                self.for_each_out_edge(v, |e| {
+                    let x = next.entry(e.sink).or_insert(0u64);
+                    *x = x.checked_add(*n).expect("overflow on counting walks");
+                });
+            }
+            if next.is_empty() {
+                return 0;
+            }
+            counts = next;
+        }
+        counts.get(sink).copied().unwrap_or(0)
+    }
+}
+
+impl<G: QueryableGraph> PathCount for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::{Ops, TreeBackedGraph};
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn hand_computed() {
+        //  0 ==> 1 --> 3 --> 0
+        //  |     |     ^
+        //  |     v     |
+        //  +---> 2 ----+
+        let mut g = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        for (u, v) in [(0, 1), (0, 1), (0, 2), (1, 2), (1, 3), (2, 3), (3, 0)] {
+            g.add_edge(vs[u], vs[v]);
+        }
+        assert_eq!(g.count_paths(&vs[0], &vs[0], 0), 1);
+        assert_eq!(g.count_paths(&vs[0], &vs[1], 0), 0);
+        assert_eq!(g.count_paths(&vs[0], &vs[1], 1), 2);
+        // 0=1-3 twice, 0-2-3
+        assert_eq!(g.count_paths(&vs[0], &vs[3], 2), 3);
+        // 0=1-2-3 twice
+        assert_eq!(g.count_paths(&vs[0], &vs[3], 3), 2);
+        // back to 0 by 0=1-3-0 twice and 0-2-3-0
+        assert_eq!(g.count_paths(&vs[0], &vs[0], 3), 3);
+        // one of the 3 walks of length 2, and then one of 3-0=1-3 twice and 3-0-2-3
+        assert_eq!(g.count_paths(&vs[0], &vs[3], 5), 3 * 3);
+        assert_eq!(g.count_paths(&vs[0], &VertexId(100), 0), 0);
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        // 0-1-0-1, 0-1-2-1
+        assert_eq!(g.count_paths(&vs[0], &vs[1], 3), 2);
+        assert_eq!(g.count_paths(&vs[0], &vs[1], 2), 0);
+    }
+
+    #[quickcheck]
+    fn single_edges(ops: Ops) {
+        let g = MappedGraph::<TreeBackedGraph>::from(&ops).graph;
+        for u in g.iter_vertices() {
+            assert_eq!(g.count_paths(&u, &u, 0), 1);
+            for v in g.iter_vertices() {
+                assert_eq!(g.count_paths(&u, &v, 1), g.edge_multiplicity(&u, &v) as u64);
+            }
+        }
+    }
+}
@@ -0,0 +1,413 @@
+use super::undirected_view::undirected_adjacency;
+use super::BiconnectedComponents;
+use crate::graph::*;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+
+/// Trait and default implementation of testing planarity.
+pub trait Planarity
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Whether the undirected view of the graph can be drawn in the plane without crossing edges.
+    ///
+    /// Directions of edges are ignored.
+    /// Self-loops and parallel edges do not affect planarity, so they are ignored as well.
+    ///
+    /// Graphs violating Euler's bound $\|E\| \le 3 \|V\| - 6$ are rejected right away.
+    /// Otherwise, a graph is planar iff all its biconnected components are,
+    /// and each of them is embedded face by face by
+    /// the path addition algorithm of Demoucron, Malgrange and Pertuiset.
+    /// It is exact, and takes $O(\|V\|^3)$ per component on graphs within Euler's bound.
+    fn is_planar(&self) -> bool {
+        let adj = undirected_adjacency(self);
+        let edges: usize = adj.values().map(|x| x.len()).sum::<usize>() / 2;
+        if violates_euler_bound(adj.len(), edges) {
+            return false;
+        }
+        for component in self.biconnected_components() {
+            let mut block: BTreeMap<VertexId, BTreeSet<VertexId>> = BTreeMap::new();
+            for e in component.iter() {
+                let e = self.find_edge(e).unwrap();
+                if e.source == e.sink {
+                    continue;
+                }
+                block.entry(e.source).or_default().insert(e.sink);
+                block.entry(e.sink).or_default().insert(e.source);
+            }
+            if !is_biconnected_planar(&block) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<G: QueryableGraph> Planarity for G {}
+
+fn violates_euler_bound(vertices: usize, edges: usize) -> bool {
+    vertices >= 3 && edges > 3 * vertices - 6
+}
+
+/// The path addition algorithm on a biconnected simple graph.
+///
+/// Starting from a cycle, which splits the plane into two faces,
+/// it repeatedly embeds a path from a fragment, i.e.,
+/// an unembedded edge between embedded vertices or
+/// a component of unembedded vertices along with edges attaching it to embedded ones.
+/// A fragment fits in a face only if all its attachments are on the face.
+/// The graph is planar iff no fragment ends up fitting in no face,
+/// provided fragments fitting in only one face are embedded first.
+fn is_biconnected_planar(block: &BTreeMap<VertexId, BTreeSet<VertexId>>) -> bool {
+    let n = block.len();
+    let edge_size: usize = block.values().map(|x| x.len()).sum::<usize>() / 2;
+    if n <= 4 || edge_size <= n {
+        // at most 4 vertices, or a single cycle
+        return true;
+    }
+    if violates_euler_bound(n, edge_size) {
+        return false;
+    }
+    let vertices: Vec<_> = block.keys().copied().collect();
+    let adj: Vec<Vec<usize>> = block
+        .values()
+        .map(|ns| {
+            ns.iter()
+                .map(|v| vertices.binary_search(v).unwrap())
+                .collect()
+        })
+        .collect();
+
+    let mut embedded_vertices = vec![false; n];
+    let mut embedded_edges = BTreeSet::new();
+    let cycle = initial_cycle(&adj);
+    for (i, v) in cycle.iter().enumerate() {
+        embedded_vertices[*v] = true;
+        let w = cycle[(i + 1) % cycle.len()];
+        embedded_edges.insert(((*v).min(w), (*v).max(w)));
+    }
+    let mut faces = vec![cycle.clone(), cycle];
+    while embedded_edges.len() < edge_size {
+        let fragments = fragments(&adj, &embedded_vertices, &embedded_edges);
+        let mut chosen = None;
+        for fragment in fragments.iter() {
+            let admissible: Vec<_> = faces
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| fragment.attachments.iter().all(|a| f.contains(a)))
+                .map(|(i, _)| i)
+                .collect();
+            match admissible.len() {
+                0 => return false,
+                1 => {
+                    chosen = Some((fragment, admissible[0]));
+                    break;
+                }
+                _ => {
+                    if chosen.is_none() {
+                        chosen = Some((fragment, admissible[0]));
+                    }
+                }
+            }
+        }
+        let (fragment, face) = chosen.unwrap();
+        let path = fragment.path(&adj, &embedded_vertices);
+        for (i, v) in path.iter().enumerate() {
+            embedded_vertices[*v] = true;
+            if let Some(w) = path.get(i + 1) {
+                embedded_edges.insert(((*v).min(*w), (*v).max(*w)));
+            }
+        }
+        let (f1, f2) = split_face(&faces[face], &path);
+        faces[face] = f1;
+        faces.push(f2);
+    }
+    true
+}
+
+/// A cycle through vertex 0, which exists because the graph is biconnected.
+fn initial_cycle(adj: &[Vec<usize>]) -> Vec<usize> {
+    let start = adj[0][0];
+    // a shortest path from `start` to 0 avoiding the edge between them
+    let mut parents = vec![usize::MAX; adj.len()];
+    parents[start] = start;
+    let mut queue = VecDeque::from(vec![start]);
+    while let Some(v) = queue.pop_front() {
+        for w in adj[v].iter() {
+            if v == start && *w == 0 {
+                continue;
+            }
+            if parents[*w] == usize::MAX {
+                parents[*w] = v;
+                queue.push_back(*w);
+            }
+        }
+    }
+    let mut res = vec![0];
+    let mut v = parents[0];
+    while v != start {
+        res.push(v);
+        v = parents[v];
+    }
+    res.push(start);
+    res
+}
+
+struct Fragment {
+    /// sorted embedded vertices which the fragment attaches to
+    attachments: Vec<usize>,
+    /// unembedded vertices of the fragment, empty for a single edge
+    inner: BTreeSet<usize>,
+}
+
+impl Fragment {
+    /// A path through the fragment between two distinct attachments.
+    fn path(&self, adj: &[Vec<usize>], embedded_vertices: &[bool]) -> Vec<usize> {
+        let from = self.attachments[0];
+        if self.inner.is_empty() {
+            return vec![from, self.attachments[1]];
+        }
+        let mut parents = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        for w in adj[from].iter().filter(|w| self.inner.contains(w)) {
+            parents.insert(*w, from);
+            queue.push_back(*w);
+        }
+        while let Some(v) = queue.pop_front() {
+            for w in adj[v].iter() {
+                if embedded_vertices[*w] {
+                    if *w == from {
+                        continue;
+                    }
+                    let mut res = vec![*w, v];
+                    let mut u = v;
+                    while let Some(p) = parents.get(&u) {
+                        res.push(*p);
+                        if *p == from {
+                            break;
+                        }
+                        u = *p;
+                    }
+                    res.reverse();
+                    return res;
+                }
+                if !parents.contains_key(w) {
+                    parents.insert(*w, v);
+                    queue.push_back(*w);
+                }
+            }
+        }
+        unreachable!("a fragment of a biconnected graph has at least two attachments")
+    }
+}
+
+fn fragments(
+    adj: &[Vec<usize>],
+    embedded_vertices: &[bool],
+    embedded_edges: &BTreeSet<(usize, usize)>,
+) -> Vec<Fragment> {
+    let mut res = vec![];
+    for (v, ns) in adj.iter().enumerate() {
+        if !embedded_vertices[v] {
+            continue;
+        }
+        for w in ns.iter() {
+            if v < *w && embedded_vertices[*w] && !embedded_edges.contains(&(v, *w)) {
+                res.push(Fragment {
+                    attachments: vec![v, *w],
+                    inner: BTreeSet::new(),
+                });
+            }
+        }
+    }
+    let mut visited = embedded_vertices.to_vec();
+    for root in 0..adj.len() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut inner = BTreeSet::new();
+        let mut attachments = BTreeSet::new();
+        let mut queue = VecDeque::from(vec![root]);
+        while let Some(v) = queue.pop_front() {
+            inner.insert(v);
+            for w in adj[v].iter() {
+                if embedded_vertices[*w] {
+                    attachments.insert(*w);
+                } else if !visited[*w] {
+                    visited[*w] = true;
+                    queue.push_back(*w);
+                }
+            }
+        }
+        res.push(Fragment {
+            attachments: attachments.into_iter().collect(),
+            inner,
+        });
+    }
+    res
+}
+
+/// Splits a face, as a cyclic sequence of vertices, by a path between two vertices on it.
+fn split_face(face: &[usize], path: &[usize]) -> (Vec<usize>, Vec<usize>) {
+    let len = face.len();
+    let i = face.iter().position(|v| *v == path[0]).unwrap();
+    let j = face
+        .iter()
+        .position(|v| *v == *path.last().unwrap())
+        .unwrap();
+    let interior = &path[1..path.len() - 1];
+    let walk = |from: usize, to: usize| {
+        let mut res = vec![];
+        let mut k = from;
+        loop {
+            res.push(face[k]);
+            if k == to {
+                break;
+            }
+            k = (k + 1) % len;
+        }
+        res
+    };
+    let mut f1 = walk(i, j);
+    f1.extend(interior.iter().rev());
+    let mut f2 = walk(j, i);
+    f2.extend(interior.iter());
+    (f1, f2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_graphs::complete;
+    use super::*;
+    use crate::graph::directed::{Ops, TreeBackedGraph};
+    use quickcheck_macros::quickcheck;
+
+    fn complete_bipartite(m: usize, n: usize) -> undirected::TreeBackedGraph {
+        let mut g = undirected::TreeBackedGraph::new();
+        let us: Vec<_> = (0..m).map(|_| g.add_vertex()).collect();
+        let vs: Vec<_> = (0..n).map(|_| g.add_vertex()).collect();
+        for u in us.iter() {
+            for v in vs.iter() {
+                g.add_edge(*u, *v);
+            }
+        }
+        g
+    }
+
+    #[test]
+    fn kuratowski() {
+        assert!(complete::<undirected::TreeBackedGraph>(4).is_planar());
+        assert!(!complete::<undirected::TreeBackedGraph>(5).is_planar());
+        assert!(complete_bipartite(2, 5).is_planar());
+        assert!(!complete_bipartite(3, 3).is_planar());
+    }
+
+    #[test]
+    fn within_euler_bound() {
+        // K5 minus an edge
+        let mut g: undirected::TreeBackedGraph = complete(5);
+        let e = g.iter_edges().next().unwrap();
+        g.remove_edge(&e.id);
+        assert!(g.is_planar());
+
+        // K3,3 with an edge subdivided, which has 7 vertices and 10 edges
+        let mut g = complete_bipartite(3, 3);
+        let e = g.iter_edges().next().unwrap();
+        g.remove_edge(&e.id);
+        let mid = g.add_vertex();
+        g.add_edge(e.source, mid);
+        g.add_edge(mid, e.sink);
+        assert!(!g.is_planar());
+
+        // Petersen graph
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..10).map(|_| g.add_vertex()).collect();
+        for i in 0..5 {
+            g.add_edge(vs[i], vs[(i + 1) % 5]);
+            g.add_edge(vs[i], vs[i + 5]);
+            g.add_edge(vs[i + 5], vs[(i + 2) % 5 + 5]);
+        }
+        assert!(!g.is_planar());
+
+        // the cube
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..8).map(|_| g.add_vertex()).collect();
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    g.add_edge(vs[i], vs[i | bit]);
+                }
+            }
+        }
+        assert!(g.is_planar());
+    }
+
+    #[test]
+    fn maximal_planar() {
+        // a triangulation by repeatedly putting a vertex into a triangular face
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        g.add_edge(vs[2], vs[0]);
+        let mut faces = vec![[vs[0], vs[1], vs[2]], [vs[0], vs[1], vs[2]]];
+        for i in 0..9 {
+            let [a, b, c] = faces.swap_remove(i * 7 % faces.len());
+            let v = g.add_vertex();
+            for u in [a, b, c] {
+                g.add_edge(u, v);
+            }
+            faces.extend([[a, b, v], [b, c, v], [c, a, v]]);
+        }
+        assert_eq!(g.edge_size(), 3 * g.vertex_size() - 6);
+        assert!(g.is_planar());
+        let vs: Vec<_> = g.iter_vertices().collect();
+        for (i, u) in vs.iter().enumerate() {
+            for v in vs[i + 1..].iter() {
+                if g.edge_multiplicity(u, v) == 0 {
+                    let e = g.add_edge(*u, *v);
+                    assert!(!g.is_planar());
+                    // a subdivided edge keeps it out of reach of Euler's bound
+                    g.remove_edge(&e);
+                    let mid = g.add_vertex();
+                    g.add_edge(*u, mid);
+                    g.add_edge(mid, *v);
+                    assert!(!g.is_planar());
+                    assert_eq!(g.remove_vertex(&mid).count(), 2);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn blocks_and_multigraphs() {
+        // two K4's sharing a vertex, with parallel edges, self-loops and directions
+        let mut g = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..7).map(|_| g.add_vertex()).collect();
+        for side in [&vs[..4], &vs[3..]] {
+            for (i, u) in side.iter().enumerate() {
+                for v in side[i + 1..].iter() {
+                    g.add_edge(*u, *v);
+                    g.add_edge(*v, *u);
+                }
+            }
+        }
+        g.add_edge(vs[0], vs[0]);
+        assert!(g.is_planar());
+        assert!(TreeBackedGraph::new().is_planar());
+    }
+
+    #[quickcheck]
+    fn closed_under_removing_edges(ops: Ops) {
+        let mut g = MappedGraph::<TreeBackedGraph>::from(&ops).graph;
+        let mut planar = g.is_planar();
+        let edges: Vec<_> = g.iter_edges().collect();
+        for e in edges {
+            g.remove_edge(&e.id);
+            let now = g.is_planar();
+            assert!(!planar || now);
+            planar = now;
+        }
+        assert!(planar);
+    }
+}
@@ -0,0 +1,182 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// Trait and default implementation of precomputing reachability.
+pub trait ReachabilityIndex
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Precomputes reachability along out-edges between all pairs of vertices.
+    ///
+    /// Vertices are condensed into strongly connected components,
+    /// each of which has a bitset of components it can reach.
+    /// Bitsets are unions of those of successors, in reverse topological order.
+    /// It takes $O(\|V\| + \|E\| \cdot \|C\| / 64)$ time and $O(\|C\|^2 / 64)$ words of space,
+    /// where $C$ is the set of components.
+    fn reachability(&self) -> Reachability {
+        let components = strongly_connected_components(self);
+        let mut component_of: HashMap<VertexId, usize, RandomState> =
+            HashMap::with_hasher(new_random_state());
+        for (i, c) in components.iter().enumerate() {
+            for v in c.iter() {
+                component_of.insert(*v, i);
+            }
+        }
+        let words = (components.len() + 63) / 64;
+        let mut bits: Vec<Vec<u64>> = Vec::with_capacity(components.len());
+        for (i, c) in components.iter().enumerate() {
+            let mut row = vec![0u64; words];
+            row[i / 64] |= 1 << (i % 64);
+            for v in c.iter() {
+                for e in self.out_edges(v) {
+                    let j = component_of[&e.sink];
+                    if j != i {
+                        for (x, y) in row.iter_mut().zip(bits[j].iter()) {
+                            *x |= *y;
+                        }
+                    }
+                }
+            }
+            bits.push(row);
+        }
+        Reachability { component_of, bits }
+    }
+}
+
+impl<G: QueryableGraph> ReachabilityIndex for G {}
+
+/// Precomputed reachability of a graph, by [ReachabilityIndex::reachability].
+///
+/// It is a snapshot and does not follow later changes of the graph.
+#[derive(Debug, Clone)]
+pub struct Reachability {
+    component_of: HashMap<VertexId, usize, RandomState>,
+    bits: Vec<Vec<u64>>,
+}
+
+impl Reachability {
+    /// Whether there is a path along out-edges from `a` to `b`.
+    ///
+    /// Every vertex reaches itself.
+    /// It is false if either is not in the graph.
+    pub fn can_reach(&self, a: &VertexId, b: &VertexId) -> bool {
+        match (self.component_of.get(a), self.component_of.get(b)) {
+            (Some(i), Some(j)) => self.bits[*i][j / 64] & (1 << (j % 64)) != 0,
+            _ => false,
+        }
+    }
+}
+
+/// Strongly connected components in reverse topological order of the condensation.
+///
+/// That is, if there is an edge from one component to another,
+/// the latter comes first.
+/// It is Tarjan's algorithm in $O(\|V\| + \|E\|)$.
+pub(crate) fn strongly_connected_components<G>(graph: &G) -> Vec<Vec<VertexId>>
+where
+    G: QueryableGraph,
+{
+    let mut res = vec![];
+    // discovery times and low-links
+    let mut times: HashMap<VertexId, (usize, usize), RandomState> =
+        HashMap::with_hasher(new_random_state());
+    let mut on_stack: HashMap<VertexId, bool, RandomState> =
+        HashMap::with_hasher(new_random_state());
+    let mut scc_stack = vec![];
+    let mut roots: Vec<_> = graph.iter_vertices().collect();
+    roots.sort();
+    for root in roots {
+        if times.contains_key(&root) {
+            continue;
+        }
+        times.insert(root, (times.len(), times.len()));
+        on_stack.insert(root, true);
+        scc_stack.push(root);
+        let mut stack = vec![(root, graph.out_edges(&root))];
+        while let Some((v, out_edges)) = stack.last_mut() {
+            let v = *v;
+            if let Some(e) = out_edges.next() {
+                let w = e.sink;
+                match times.get(&w) {
+                    None => {
+                        times.insert(w, (times.len(), times.len()));
+                        on_stack.insert(w, true);
+                        scc_stack.push(w);
+                        stack.push((w, graph.out_edges(&w)));
+                    }
+                    Some((disc_w, _)) if on_stack[&w] => {
+                        let disc_w = *disc_w;
+                        let low_v = &mut times.get_mut(&v).unwrap().1;
+                        *low_v = (*low_v).min(disc_w);
+                    }
+                    Some(_) => {}
+                }
+            } else {
+                stack.pop();
+                let (disc_v, low_v) = times[&v];
+                if let Some((u, _)) = stack.last() {
+                    let low_u = &mut times.get_mut(u).unwrap().1;
+                    *low_u = (*low_u).min(low_v);
+                }
+                if disc_v == low_v {
+                    let mut component = vec![];
+                    while let Some(w) = scc_stack.pop() {
+                        on_stack.insert(w, false);
+                        component.push(w);
+                        if w == v {
+                            break;
+                        }
+                    }
+                    res.push(component);
+                }
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn check<G: QueryableGraph>(g: &G) {
+        let reach = g.reachability();
+        for a in g.iter_vertices() {
+            for b in g.iter_vertices() {
+                assert_eq!(reach.can_reach(&a, &b), g.distance(&a, &b).is_some());
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn consistent_with_bfs(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&g.graph);
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&g.graph);
+    }
+
+    #[test]
+    fn cycle_and_tail() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        g.add_edge(vs[2], vs[0]);
+        g.add_edge(vs[2], vs[3]);
+        let reach = g.reachability();
+        assert!(reach.can_reach(&vs[1], &vs[0]));
+        assert!(reach.can_reach(&vs[0], &vs[3]));
+        assert!(reach.can_reach(&vs[3], &vs[3]));
+        assert!(!reach.can_reach(&vs[3], &vs[0]));
+        assert!(!reach.can_reach(&vs[0], &VertexId(100)));
+        let components = strongly_connected_components(&g);
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0], vec![vs[3]]);
+        assert_eq!(components[1].len(), 3);
+    }
+}
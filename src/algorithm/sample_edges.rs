@@ -0,0 +1,144 @@
+use crate::graph::*;
+use rand::Rng;
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+
+/// Trait and default implementation of weighted random sampling of edges.
+pub trait SampleEdges
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Samples `k` distinct edges, each of which is picked with probability proportional to its weight.
+    ///
+    /// It returns sampled edges in ascending order of their ID's.
+    /// Edges whose weights are not positive, including NaN, are never sampled.
+    /// If there are not enough such edges, all of them are returned.
+    ///
+    /// It is the weighted reservoir sampling by Efraimidis and Spirakis,
+    /// in a single pass over `iter_edges` with $O(k)$ extra space.
+    /// Each edge gets a random key $u^{1/w}$, where $u$ is uniform in $(0, 1]$,
+    /// and those with the `k` largest keys are kept.
+    /// With a seeded `rng` and a graph iterating edges in a fixed order,
+    /// results are reproducible.
+    fn sample_edges<F, R>(&self, k: usize, weight: F, rng: &mut R) -> Vec<EdgeId>
+    where
+        F: Fn(&Edge) -> f64,
+        R: Rng,
+    {
+        if k == 0 {
+            return vec![];
+        }
+        let mut reservoir: BinaryHeap<Reverse<(Key, EdgeId)>> = BinaryHeap::with_capacity(k);
+        for e in self.iter_edges() {
+            let w = weight(&e);
+            if w.is_nan() || w <= 0.0 {
+                continue;
+            }
+            let u: f64 = 1.0 - rng.gen::<f64>();
+            // logarithm of u^(1/w), to keep precision for large weights
+            let key = Key(u.ln() / w);
+            if reservoir.len() < k {
+                reservoir.push(Reverse((key, e.id)));
+            } else if reservoir.peek().unwrap().0 .0 < key {
+                reservoir.pop();
+                reservoir.push(Reverse((key, e.id)));
+            }
+        }
+        let mut res: Vec<_> = reservoir.into_iter().map(|Reverse((_, e))| e).collect();
+        res.sort();
+        res
+    }
+}
+
+impl<G: QueryableGraph> SampleEdges for G {}
+
+/// A key in the reservoir, which is never NaN.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Key(f64);
+
+impl Eq for Key {}
+
+impl PartialOrd for Key {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Key {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn star(n: usize) -> (directed::TreeBackedGraph, Vec<EdgeId>) {
+        let mut g = directed::TreeBackedGraph::new();
+        let center = g.add_vertex();
+        let es = (0..n)
+            .map(|_| {
+                let v = g.add_vertex();
+                g.add_edge(center, v)
+            })
+            .collect();
+        (g, es)
+    }
+
+    #[test]
+    fn reproducible() {
+        let (g, _) = star(20);
+        let weight = |e: &Edge| e.id.0 as f64;
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let first = g.sample_edges(5, weight, &mut rng);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let second = g.sample_edges(5, weight, &mut rng);
+        assert_eq!(first, second);
+        assert_eq!(first.len(), 5);
+        let mut dedup = first.clone();
+        dedup.dedup();
+        assert_eq!(dedup, first);
+    }
+
+    #[test]
+    fn zero_weights_never_sampled() {
+        let (g, es) = star(10);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let weight = |e: &Edge| {
+            if e.id.0 % 2 == 0 {
+                0.0
+            } else if e.id.0 % 3 == 0 {
+                f64::NAN
+            } else {
+                1.0
+            }
+        };
+        let positive: Vec<_> = es
+            .iter()
+            .copied()
+            .filter(|e| e.0 % 2 != 0 && e.0 % 3 != 0)
+            .collect();
+        for _ in 0..100 {
+            let sampled = g.sample_edges(2, weight, &mut rng);
+            assert_eq!(sampled.len(), 2);
+            assert!(sampled.iter().all(|e| positive.contains(e)));
+        }
+        let sampled = g.sample_edges(100, weight, &mut rng);
+        assert_eq!(sampled, positive);
+        assert!(g.sample_edges(0, weight, &mut rng).is_empty());
+    }
+
+    #[test]
+    fn heavier_edges_more_likely() {
+        let (g, es) = star(2);
+        let mut rng = StdRng::seed_from_u64(0x5eed);
+        let weight = |e: &Edge| if e.id == es[0] { 9.0 } else { 1.0 };
+        let heavy = (0..1000)
+            .filter(|_| g.sample_edges(1, weight, &mut rng) == vec![es[0]])
+            .count();
+        assert!((850..950).contains(&heavy), "{}", heavy);
+    }
+}
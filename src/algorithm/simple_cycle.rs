@@ -1,4 +1,5 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
 use ahash::RandomState;
 use std::collections::{HashMap, HashSet};
 
@@ -19,6 +20,49 @@ where
     ) -> Box<dyn Iterator<Item = Box<dyn Iterator<Item = Edge> + '_>> + '_> {
         Box::new(CycleIterator::start_from(self, vert))
     }
+
+    /// Collects cycles from [SimpleCycles::simple_cycles] in a canonical order.
+    ///
+    /// Each cycle is rotated to start from its least vertex.
+    /// Then cycles are sorted by their sequences of vertices,
+    /// and by ID's of edges to break ties among parallel edges.
+    /// Therefore, the result is reproducible regardless of how hashers are seeded.
+    fn simple_cycles_ordered(&self) -> Vec<Vec<Edge>> {
+        let mut res: Vec<Vec<Edge>> = self
+            .simple_cycles()
+            .map(|cycle| {
+                let mut cycle: Vec<_> = cycle.collect();
+                let start = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, e)| e.source)
+                    .map_or(0, |(idx, _)| idx);
+                cycle.rotate_left(start);
+                cycle
+            })
+            .collect();
+        res.sort_by_cached_key(|cycle| {
+            cycle
+                .iter()
+                .map(|e| (e.source, e.sink, e.id))
+                .collect::<Vec<_>>()
+        });
+        res
+    }
+
+    /// Number of simple cycles that [SimpleCycles::simple_cycles] iterates over.
+    ///
+    /// It saves collecting edges of each cycle.
+    fn simple_cycle_count(&self) -> usize {
+        CycleIterator::exhaust(self).count_cycles()
+    }
+
+    /// Number of simple cycles that [SimpleCycles::simple_cycles_reachable_from] iterates over.
+    ///
+    /// It saves collecting edges of each cycle.
+    fn simple_cycle_count_reachable_from(&self, vert: &VertexId) -> usize {
+        CycleIterator::start_from(self, vert).count_cycles()
+    }
 }
 
 impl<G: QueryableGraph> SimpleCycles for G {}
@@ -79,7 +123,11 @@ where
 {
     graph: ShadowedSubgraph<'a, G>,
     to_exhaust_vertices: Vec<VertexId>,
-    come_from: HashMap<VertexId, Option<Edge>, RandomState>,
+    /// Positions of visited vertices on `path`.
+    /// A vertex at depth `d` is reached by `path[d - 1]`.
+    depths: HashMap<VertexId, usize, RandomState>,
+    /// Edges from the root to the vertex currently being explored.
+    path: Vec<Edge>,
     stack: Vec<StackItem>,
     exhausted_vertices: HashSet<VertexId, RandomState>,
 }
@@ -91,11 +139,57 @@ where
     type Item = Box<dyn Iterator<Item = Edge> + 'a>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        self.advance().map(|(depth, edge)| {
+            let mut res = Vec::with_capacity(self.path.len() - depth + 1);
+            res.extend_from_slice(&self.path[depth..]);
+            res.push(edge);
+            let it: Self::Item = Box::new(res.into_iter());
+            it
+        })
+    }
+}
+
+impl<'a, G> CycleIterator<'a, G>
+where
+    G: QueryableGraph,
+{
+    fn new(graph: &'a G) -> Self {
+        Self {
+            graph: ShadowedSubgraph::new(graph),
+            to_exhaust_vertices: vec![],
+            depths: HashMap::with_hasher(new_random_state()),
+            path: vec![],
+            stack: vec![],
+            exhausted_vertices: HashSet::with_hasher(new_random_state()),
+        }
+    }
+
+    fn exhaust(graph: &'a G) -> Self {
+        let mut res = Self::new(graph);
+        for v in graph.iter_vertices() {
+            res.to_exhaust_vertices.push(v);
+        }
+        res
+    }
+
+    fn start_from(graph: &'a G, vert: &VertexId) -> Self {
+        let mut res = Self::new(graph);
+        res.to_exhaust_vertices.push(*vert);
+        res
+    }
+
+    /// Searches for the next cycle.
+    ///
+    /// The cycle consists of edges in `path` from the returned depth on,
+    /// and then the returned edge.
+    fn advance(&mut self) -> Option<(usize, Edge)> {
         loop {
             if let Some(top_item) = self.stack.pop() {
                 match top_item {
                     StackItem::Vertex(vert) => {
                         self.exhausted_vertices.insert(vert);
+                        // the root of a search has no edge on `path`.
+                        self.path.pop();
                     }
                     StackItem::Edge(edge) => {
                         if !self.graph.contains_edge(&edge.id) {
@@ -104,31 +198,21 @@ where
                             self.graph.remove_edge(&edge.id);
                             if self.exhausted_vertices.contains(&edge.sink) {
                                 // intend to do nothing
-                            } else if self.come_from.get(&edge.sink).is_some() {
-                                let terminal = edge.sink;
-                                let mut res = vec![edge.clone()];
-                                let mut edge = edge;
-                                loop {
-                                    if edge.source == terminal {
-                                        break;
-                                    }
-                                    let e = self.come_from.get(&edge.source).unwrap();
-                                    let e = e.clone().unwrap();
-                                    res.push(e.clone());
-                                    edge = e;
-                                }
-                                res.reverse();
-                                return Some(Box::new(res.into_iter()));
+                            } else if let Some(depth) = self.depths.get(&edge.sink) {
+                                return Some((*depth, edge));
                             } else {
-                                self.come_from.insert(edge.sink, Some(edge.clone()));
-                                self.extend_stack(edge.sink);
+                                let sink = edge.sink;
+                                self.path.push(edge);
+                                self.depths.insert(sink, self.path.len());
+                                self.extend_stack(sink);
                             }
                         }
                     }
                 }
             } else if let Some(vert) = self.to_exhaust_vertices.pop() {
-                if let std::collections::hash_map::Entry::Vacant(e) = self.come_from.entry(vert) {
-                    e.insert(None);
+                if let std::collections::hash_map::Entry::Vacant(e) = self.depths.entry(vert) {
+                    debug_assert!(self.path.is_empty());
+                    e.insert(0);
                     self.extend_stack(vert);
                 }
             } else {
@@ -136,36 +220,16 @@ where
             }
         }
     }
-}
 
-impl<'a, G> CycleIterator<'a, G>
-where
-    G: QueryableGraph,
-{
-    fn new(graph: &'a G) -> Self {
-        Self {
-            graph: ShadowedSubgraph::new(graph),
-            to_exhaust_vertices: vec![],
-            come_from: HashMap::with_hasher(RandomState::new()),
-            stack: vec![],
-            exhausted_vertices: HashSet::with_hasher(RandomState::new()),
-        }
-    }
-
-    fn exhaust(graph: &'a G) -> Self {
-        let mut res = Self::new(graph);
-        for v in graph.iter_vertices() {
-            res.to_exhaust_vertices.push(v);
+    /// Counts the remaining cycles without collecting their edges.
+    fn count_cycles(mut self) -> usize {
+        let mut res = 0;
+        while self.advance().is_some() {
+            res += 1;
         }
         res
     }
 
-    fn start_from(graph: &'a G, vert: &VertexId) -> Self {
-        let mut res = Self::new(graph);
-        res.to_exhaust_vertices.push(*vert);
-        res
-    }
-
     fn extend_stack(&mut self, vert: VertexId) {
         self.stack.push(StackItem::Vertex(vert));
         for nxt_edge in self.graph.out_edges(&vert) {
@@ -200,6 +264,7 @@ mod tests {
             let trial: Vec<_> = g.simple_cycles().map(super::fmt_cycle).collect();
             let oracle = vec![format!("{v:?} -> {v:?}")];
             assert_eq!(trial, oracle);
+            assert_eq!(g.simple_cycle_count(), 1);
         }
 
         #[test]
@@ -209,6 +274,8 @@ mod tests {
             let v1 = g.add_vertex();
             g.add_edge(v0, v1);
             g.add_edge(v1, v0);
+            assert_eq!(g.simple_cycle_count(), 1);
+            assert_eq!(g.simple_cycle_count_reachable_from(&v1), 1);
             let trial: Vec<_> = g
                 .simple_cycles_reachable_from(&v0)
                 .map(super::fmt_cycle)
@@ -242,6 +309,32 @@ mod tests {
                 }
             }
         }
+
+        #[quickcheck]
+        fn simple_cycles_ordered(ops: Ops) {
+            let ops_formed: MappedGraph<TreeBackedGraph> = (&ops).into();
+            let graph = &ops_formed.graph;
+            let trial = graph.simple_cycles_ordered();
+            assert_eq!(trial, graph.simple_cycles_ordered());
+            assert_eq!(trial.len(), graph.simple_cycle_count());
+            for cycle in trial.iter() {
+                assert!(super::is_cyclic(cycle.clone().into_iter()));
+                assert!(cycle.iter().all(|e| cycle[0].source <= e.source));
+            }
+        }
+
+        #[quickcheck]
+        fn simple_cycle_count(ops: Ops) {
+            let ops_formed: MappedGraph<TreeBackedGraph> = (&ops).into();
+            let graph = &ops_formed.graph;
+            assert_eq!(graph.simple_cycle_count(), graph.simple_cycles().count());
+            for v in graph.iter_vertices() {
+                assert_eq!(
+                    graph.simple_cycle_count_reachable_from(&v),
+                    graph.simple_cycles_reachable_from(&v).count()
+                );
+            }
+        }
     }
 
     mod undirected {
@@ -313,6 +406,19 @@ mod tests {
                 }
             }
         }
+
+        #[quickcheck]
+        fn simple_cycle_count(ops: Ops) {
+            let ops_formed: MappedGraph<TreeBackedGraph> = (&ops).into();
+            let graph = &ops_formed.graph;
+            assert_eq!(graph.simple_cycle_count(), graph.simple_cycles().count());
+            for v in graph.iter_vertices() {
+                assert_eq!(
+                    graph.simple_cycle_count_reachable_from(&v),
+                    graph.simple_cycles_reachable_from(&v).count()
+                );
+            }
+        }
     }
 
     fn is_cyclic<I>(cycle: I) -> bool
@@ -0,0 +1,137 @@
+use super::undirected_view::undirected_adjacency;
+use crate::graph::*;
+
+/// Trait and default implementation of counting spanning trees.
+pub trait SpanningTreeCount
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Number of spanning trees of the undirected simple view of the graph.
+    ///
+    /// Directions of edges are ignored.
+    /// Self-loops are ignored and parallel edges count once.
+    /// A disconnected graph, as well as an empty one, has no spanning tree.
+    ///
+    /// By Kirchhoff's matrix-tree theorem, it is a determinant of a minor of the Laplacian matrix.
+    /// The determinant is evaluated by fraction-free Gaussian elimination (Bareiss' algorithm)
+    /// in $O(\|V\|^3)$ integer operations, so that the result is exact.
+    ///
+    /// The elimination runs on signed `i128` entries,
+    /// and it returns `None` when intermediate products overflow them.
+    /// They are products of two minors of the Laplacian matrix,
+    /// so a safe rule of thumb is that the result fits in `u64`.
+    /// E.g., the complete graph of 17 vertices, with $17^{15} \approx 2.9 \times 10^{18}$ spanning trees,
+    /// is fine.
+    fn spanning_tree_count(&self) -> Option<u128> {
+        let adj = undirected_adjacency(self);
+        let vertices: Vec<_> = adj.keys().copied().collect();
+        if vertices.is_empty() {
+            return Some(0);
+        }
+        let n = vertices.len() - 1;
+        // the Laplacian matrix without the row and the column of the last vertex
+        let mut m = vec![vec![0i128; n]; n];
+        for (i, row) in m.iter_mut().enumerate() {
+            let neighbors = &adj[&vertices[i]];
+            row[i] = neighbors.len() as i128;
+            for v in neighbors.iter() {
+                let j = vertices.binary_search(v).unwrap();
+                if j < n {
+                    row[j] = -1;
+                }
+            }
+        }
+        let det = bareiss_determinant(m)?;
+        debug_assert!(det >= 0);
+        Some(det as u128)
+    }
+}
+
+impl<G: QueryableGraph> SpanningTreeCount for G {}
+
+fn bareiss_determinant(mut m: Vec<Vec<i128>>) -> Option<i128> {
+    let n = m.len();
+    let mut sign = 1;
+    let mut prev_pivot = 1;
+    for k in 0..n {
+        if m[k][k] == 0 {
+            match (k + 1..n).find(|i| m[*i][k] != 0) {
+                Some(i) => {
+                    m.swap(k, i);
+                    sign = -sign;
+                }
+                None => return Some(0),
+            }
+        }
+        let (upper, lower) = m.split_at_mut(k + 1);
+        let pivot_row = &upper[k];
+        for row in lower.iter_mut() {
+            for j in k + 1..n {
+                let x = pivot_row[k]
+                    .checked_mul(row[j])?
+                    .checked_sub(row[k].checked_mul(pivot_row[j])?)?;
+                row[j] = x / prev_pivot;
+            }
+            row[k] = 0;
+        }
+        prev_pivot = m[k][k];
+    }
+    if n == 0 {
+        Some(1)
+    } else {
+        m[n - 1][n - 1].checked_mul(sign)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_graphs::complete;
+    use super::*;
+
+    #[test]
+    fn complete_graphs() {
+        let g: undirected::TreeBackedGraph = complete(3);
+        assert_eq!(g.spanning_tree_count(), Some(3));
+        let g: undirected::TreeBackedGraph = complete(4);
+        assert_eq!(g.spanning_tree_count(), Some(16));
+        let g: directed::TreeBackedGraph = complete(5);
+        assert_eq!(g.spanning_tree_count(), Some(125));
+        // Cayley's formula at the documented limit
+        let g: directed::AdjacentListGraph = complete(17);
+        assert_eq!(g.spanning_tree_count(), Some(17u128.pow(15)));
+    }
+
+    #[test]
+    fn overflow() {
+        // intermediate products of 40^38 spanning trees are far beyond i128
+        let g: undirected::TreeBackedGraph = complete(40);
+        assert_eq!(g.spanning_tree_count(), None);
+    }
+
+    #[test]
+    fn trivial() {
+        let mut g = undirected::TreeBackedGraph::new();
+        assert_eq!(g.spanning_tree_count(), Some(0));
+        let v0 = g.add_vertex();
+        g.add_edge(v0, v0);
+        assert_eq!(g.spanning_tree_count(), Some(1));
+        let v1 = g.add_vertex();
+        assert_eq!(g.spanning_tree_count(), Some(0));
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v0);
+        assert_eq!(g.spanning_tree_count(), Some(1));
+    }
+
+    #[test]
+    fn cycle_with_chord() {
+        // a 4-cycle with a chord has 8 spanning trees
+        let mut g = undirected::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        for i in 0..4 {
+            g.add_edge(vs[i], vs[(i + 1) % 4]);
+        }
+        assert_eq!(g.spanning_tree_count(), Some(4));
+        g.add_edge(vs[0], vs[2]);
+        assert_eq!(g.spanning_tree_count(), Some(8));
+    }
+}
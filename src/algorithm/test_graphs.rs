@@ -0,0 +1,14 @@
+//! Graphs shared by tests.
+use crate::graph::*;
+
+/// The complete graph of `n` vertices, with one edge between each pair of them.
+pub fn complete<G: GrowableGraph>(n: usize) -> G {
+    let mut g = G::new();
+    let vs: Vec<_> = (0..n).map(|_| g.add_vertex()).collect();
+    for (i, u) in vs.iter().enumerate() {
+        for v in vs[i + 1..].iter() {
+            g.add_edge(*u, *v);
+        }
+    }
+    g
+}
@@ -1,7 +1,9 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
 use ahash::RandomState;
 use keyed_priority_queue::KeyedPriorityQueue;
 use std::cmp::Reverse;
+use std::collections::HashMap;
 
 /// Trait and default implementation of topological sorting.
 pub trait TopologicalSort
@@ -9,8 +11,59 @@ where
     Self: QueryableGraph + Sized,
 {
     /// Iterates over vertices in the topological order.
+    ///
+    /// Among vertices ready at the same time, the smallest one comes first.
     fn toposort(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
-        Box::new(ToposortIter::new(self))
+        Box::new(ToposortIter::new(self, false))
+    }
+
+    /// Iterates over vertices in the reverse topological order, i.e., sinks first.
+    ///
+    /// It is Kahn's algorithm on out-degrees instead of in-degrees.
+    /// Among vertices ready at the same time, the smallest one comes first.
+    fn toposort_reverse(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        Box::new(ToposortIter::new(self, true))
+    }
+
+    /// All edges sorted by topological ranks of their sources, and then by their ID's.
+    ///
+    /// Thus, every edge comes after all edges into its source.
+    /// Ranks are positions in [TopologicalSort::toposort],
+    /// which always picks the smallest ready vertex, so the result is deterministic.
+    /// On graphs with cycles, vertices which the topological sort cannot reach,
+    /// i.e., those on or downstream of cycles,
+    /// rank after all the others in ascending order of their ID's.
+    fn edges_in_topo_order(&self) -> Vec<Edge> {
+        let mut ranks: HashMap<VertexId, usize, RandomState> =
+            HashMap::with_hasher(new_random_state());
+        for v in self.toposort() {
+            ranks.insert(v, ranks.len());
+        }
+        let mut rest: Vec<_> = self
+            .iter_vertices()
+            .filter(|v| !ranks.contains_key(v))
+            .collect();
+        rest.sort();
+        for v in rest {
+            ranks.insert(v, ranks.len());
+        }
+        let mut res: Vec<_> = self.iter_edges().collect();
+        res.sort_by_key(|e| (ranks[&e.source], e.id));
+        res
+    }
+
+    /// Strongly connected components in the topological order of the condensation.
+    ///
+    /// Unlike [TopologicalSort::toposort], it works on graphs with cycles:
+    /// if there is an edge from one component to another, the former comes first.
+    /// Vertices in each component are sorted.
+    fn scc_topo_order(&self) -> Vec<Vec<VertexId>> {
+        let mut res = super::reachability::strongly_connected_components(self);
+        res.reverse();
+        for c in res.iter_mut() {
+            c.sort();
+        }
+        res
     }
 }
 
@@ -30,6 +83,15 @@ where
             .map(|vid| self.vertex_by_id(&vid).unwrap());
         Box::new(it)
     }
+
+    /// Iterates over vertices in the reverse topological order, i.e., sinks first.
+    fn toposort_reverse(&self) -> Box<dyn Iterator<Item = &Self::Vertex> + '_> {
+        let it = self
+            .lower_graph()
+            .toposort_reverse()
+            .map(|vid| self.vertex_by_id(&vid).unwrap());
+        Box::new(it)
+    }
 }
 
 struct ToposortIter<'a, G>
@@ -37,7 +99,10 @@ where
     G: QueryableGraph,
 {
     graph: ShadowedSubgraph<'a, G>,
-    degree_queue: KeyedPriorityQueue<VertexId, Reverse<usize>, RandomState>,
+    /// prioritized by degrees, and then by ID's to break ties
+    degree_queue: KeyedPriorityQueue<VertexId, Reverse<(usize, VertexId)>, RandomState>,
+    /// whether to go against directions of edges
+    reverse: bool,
 }
 
 impl<'a, G> Iterator for ToposortIter<'a, G>
@@ -47,14 +112,15 @@ where
     type Item = VertexId;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some((vert, in_degree)) = self.degree_queue.pop() {
-            if in_degree.0 > 0 {
+        if let Some((vert, Reverse((degree, _)))) = self.degree_queue.pop() {
+            if degree > 0 {
                 None
             } else {
                 for e in self.graph.remove_vertex(&vert) {
-                    let in_degree = self.degree_queue.get_priority(&e.sink).unwrap();
+                    let next = if self.reverse { e.source } else { e.sink };
+                    let Reverse((degree, _)) = *self.degree_queue.get_priority(&next).unwrap();
                     self.degree_queue
-                        .set_priority(&e.sink, Reverse(in_degree.0 - 1))
+                        .set_priority(&next, Reverse((degree - 1, next)))
                         .unwrap();
                 }
                 Some(vert)
@@ -69,17 +135,22 @@ impl<'a, G> ToposortIter<'a, G>
 where
     G: QueryableGraph,
 {
-    fn new(graph: &'a G) -> Self {
+    fn new(graph: &'a G, reverse: bool) -> Self {
         let mut res = Self {
             graph: ShadowedSubgraph::new(graph),
             degree_queue: KeyedPriorityQueue::with_capacity_and_hasher(
                 graph.vertex_size(),
-                RandomState::new(),
+                new_random_state(),
             ),
+            reverse,
         };
         for v in graph.iter_vertices() {
-            let in_degree = graph.in_edges(&v).count();
-            res.degree_queue.push(v, Reverse(in_degree));
+            let degree = if reverse {
+                graph.out_degree(&v)
+            } else {
+                graph.in_degree(&v)
+            };
+            res.degree_queue.push(v, Reverse((degree, v)));
         }
         res
     }
@@ -115,4 +186,120 @@ mod tests {
         }
         assert_eq!(cloned_graph.vertex_size(), 0);
     }
+
+    #[quickcheck]
+    fn toposort_reverse(ops: Ops) {
+        let graph_from_ops: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let mut graph = graph_from_ops.graph;
+        loop {
+            let to_remove: Vec<_> = graph
+                .simple_cycles()
+                .map(|mut cyc| cyc.next().unwrap())
+                .collect();
+            if to_remove.is_empty() {
+                break;
+            }
+            for e in to_remove.into_iter() {
+                graph.remove_edge(&e.id);
+            }
+        }
+        let mut cloned_graph = graph.clone();
+        for v in graph.toposort_reverse() {
+            assert_eq!(cloned_graph.out_edges(&v).collect::<Vec<_>>(), vec![]);
+            let _ = cloned_graph.remove_vertex(&v);
+        }
+        assert_eq!(cloned_graph.vertex_size(), 0);
+    }
+
+    #[test]
+    fn toposort_reverse_is_reversed_toposort() {
+        // a DAG with a unique topological order: 0 -> 1 -> 2 -> 3 plus shortcuts
+        let mut graph = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        for i in 0..vs.len() {
+            for j in (i + 1)..vs.len() {
+                graph.add_edge(vs[i], vs[j]);
+            }
+        }
+        let mut forward: Vec<_> = graph.toposort().collect();
+        forward.reverse();
+        let backward: Vec<_> = graph.toposort_reverse().collect();
+        assert_eq!(forward, vs.iter().rev().copied().collect::<Vec<_>>());
+        assert_eq!(backward, forward);
+    }
+
+    #[test]
+    fn toposort_breaks_ties_by_id() {
+        let mut graph = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        graph.add_edge(vs[3], vs[0]);
+        let trial: Vec<_> = graph.toposort().collect();
+        assert_eq!(trial, vec![vs[1], vs[2], vs[3], vs[0]]);
+        let trial: Vec<_> = graph.toposort_reverse().collect();
+        assert_eq!(trial, vec![vs[0], vs[1], vs[2], vs[3]]);
+    }
+
+    #[test]
+    fn scc_topo_order() {
+        let mut graph = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        // {2, 3} -> {0, 1}
+        graph.add_edge(vs[0], vs[1]);
+        graph.add_edge(vs[1], vs[0]);
+        graph.add_edge(vs[2], vs[3]);
+        graph.add_edge(vs[3], vs[2]);
+        graph.add_edge(vs[3], vs[0]);
+        assert_eq!(
+            graph.scc_topo_order(),
+            vec![vec![vs[2], vs[3]], vec![vs[0], vs[1]]]
+        );
+    }
+
+    #[test]
+    fn edges_in_topo_order() {
+        // 3 -> 1 -> 0 -> 2, with edges added in reverse
+        let mut graph = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        let es = [
+            graph.add_edge(vs[0], vs[2]),
+            graph.add_edge(vs[1], vs[0]),
+            graph.add_edge(vs[3], vs[1]),
+            graph.add_edge(vs[3], vs[0]),
+        ];
+        let trial: Vec<_> = graph.edges_in_topo_order().iter().map(|e| e.id).collect();
+        assert_eq!(trial, vec![es[2], es[3], es[1], es[0]]);
+
+        // a cycle 2 -> 0 -> 2 downstream of 1, and 3 -> 1
+        let mut graph = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| graph.add_vertex()).collect();
+        let es = [
+            graph.add_edge(vs[2], vs[0]),
+            graph.add_edge(vs[0], vs[2]),
+            graph.add_edge(vs[1], vs[2]),
+            graph.add_edge(vs[3], vs[1]),
+        ];
+        let trial: Vec<_> = graph.edges_in_topo_order().iter().map(|e| e.id).collect();
+        assert_eq!(trial, vec![es[3], es[2], es[1], es[0]]);
+    }
+
+    #[quickcheck]
+    fn contract_scc_into_condensation(ops: Ops) {
+        let mut graph = MappedGraph::<TreeBackedGraph>::from(&ops).graph;
+        let components = graph.scc_topo_order();
+        let biggest = match components.iter().max_by_key(|c| c.len()) {
+            None => return,
+            Some(c) => c.clone(),
+        };
+        let survivor = graph.contract_vertices(biggest.iter().copied());
+        assert_eq!(survivor, Some(biggest[0]));
+        assert_eq!(graph.edge_multiplicity(&biggest[0], &biggest[0]), 0);
+        let mut oracle: Vec<_> = components
+            .into_iter()
+            .map(|c| if c == biggest { vec![biggest[0]] } else { c })
+            .collect();
+        oracle.sort();
+        let mut trial = graph.scc_topo_order();
+        trial.sort();
+        assert_eq!(trial, oracle);
+    }
 }
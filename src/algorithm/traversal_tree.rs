@@ -0,0 +1,249 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use bimap::BiHashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Trait and default implementation of exporting traversal trees.
+pub trait TraversalTree
+where
+    Self: QueryableGraph + Sized,
+{
+    /// The breadth-first search tree along out-edges from `start`.
+    ///
+    /// It consists of vertices reachable from `start` and edges through which they are discovered,
+    /// oriented from parents to children.
+    /// In the returned [MappedGraph], vertices and edges are mapped to the original ones.
+    /// If `start` is not in the graph, the tree is empty.
+    fn bfs_tree<G2: GrowableGraph>(&self, start: &VertexId) -> MappedGraph<G2> {
+        let mut res = MappedGraph {
+            graph: G2::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            emap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+        };
+        if !self.contains_vertex(start) {
+            return res;
+        }
+        let mut visited: HashSet<VertexId, RandomState> = HashSet::with_hasher(new_random_state());
+        let mut queue = VecDeque::new();
+        visited.insert(*start);
+        queue.push_back((*start, res.graph.add_vertex()));
+        res.vmap.insert(queue[0].1, *start);
+        while let Some((v, new_v)) = queue.pop_front() {
+            for e in self.out_edges(&v) {
+                if visited.insert(e.sink) {
+                    let new_w = res.graph.add_vertex();
+                    res.vmap.insert(new_w, e.sink);
+                    let new_e = res.graph.add_edge(new_v, new_w);
+                    res.emap.insert(new_e, e.id);
+                    queue.push_back((e.sink, new_w));
+                }
+            }
+        }
+        res
+    }
+
+    /// The depth-first search tree along out-edges from `start`,
+    /// along with kinds of all edges explored.
+    ///
+    /// Edges are explored in the order of `out_edges`.
+    /// The tree is built in the same way as [TraversalTree::bfs_tree].
+    /// Edges unreachable from `start` are not classified.
+    /// For undirected graphs,
+    /// each edge is classified when it is explored for the first time,
+    /// so there are only tree edges and back edges.
+    /// Self-loops are back edges.
+    fn dfs_tree_classified<G2: GrowableGraph>(
+        &self,
+        start: &VertexId,
+    ) -> (MappedGraph<G2>, HashMap<EdgeId, EdgeKind, RandomState>) {
+        let mut tree = MappedGraph {
+            graph: G2::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            emap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+        };
+        let mut kinds = HashMap::with_hasher(new_random_state());
+        if !self.contains_vertex(start) {
+            return (tree, kinds);
+        }
+        // discovery times, and whether vertices are still on the stack
+        let mut times: HashMap<VertexId, (usize, bool), RandomState> =
+            HashMap::with_hasher(new_random_state());
+        let new_start = tree.graph.add_vertex();
+        tree.vmap.insert(new_start, *start);
+        times.insert(*start, (0, true));
+        let mut stack = vec![(*start, new_start, self.out_edges(start))];
+        while let Some((v, new_v, out_edges)) = stack.last_mut() {
+            let v = *v;
+            let new_v = *new_v;
+            if let Some(e) = out_edges.next() {
+                if kinds.contains_key(&e.id) {
+                    continue;
+                }
+                let kind = match times.get(&e.sink) {
+                    None => {
+                        let new_w = tree.graph.add_vertex();
+                        tree.vmap.insert(new_w, e.sink);
+                        let new_e = tree.graph.add_edge(new_v, new_w);
+                        tree.emap.insert(new_e, e.id);
+                        times.insert(e.sink, (times.len(), true));
+                        stack.push((e.sink, new_w, self.out_edges(&e.sink)));
+                        EdgeKind::Tree
+                    }
+                    Some((_, true)) => EdgeKind::Back,
+                    Some((disc_w, false)) if *disc_w > times[&v].0 => EdgeKind::Forward,
+                    Some(_) => EdgeKind::Cross,
+                };
+                kinds.insert(e.id, kind);
+            } else {
+                times.get_mut(&v).unwrap().1 = false;
+                stack.pop();
+            }
+        }
+        (tree, kinds)
+    }
+}
+
+impl<G: QueryableGraph> TraversalTree for G {}
+
+/// Kinds of edges in a depth-first search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// An edge through which a vertex is discovered.
+    Tree,
+    /// An edge to an ancestor, or a self-loop.
+    Back,
+    /// A non-tree edge to a descendant.
+    Forward,
+    /// An edge to a vertex which is neither an ancestor nor a descendant.
+    Cross,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn check_bfs_tree<G: QueryableGraph>(g: &G, start: &VertexId) {
+        let tree: MappedGraph<directed::TreeBackedGraph> = g.bfs_tree(start);
+        let reachable = g
+            .iter_vertices()
+            .filter(|v| g.distance(start, v).is_some())
+            .count();
+        assert_eq!(tree.vertex_size(), reachable);
+        assert_eq!(tree.edge_size(), reachable.saturating_sub(1));
+        for v in tree.iter_vertices() {
+            let in_edges: Vec<_> = tree.in_edges(&v).collect();
+            if v == *start {
+                assert!(in_edges.is_empty());
+            } else {
+                assert_eq!(in_edges.len(), 1);
+                let e = &in_edges[0];
+                let original = g.find_edge(&e.id).unwrap();
+                assert!(
+                    (original.source, original.sink) == (e.source, e.sink)
+                        || (original.source, original.sink) == (e.sink, e.source)
+                );
+                assert_eq!(
+                    tree.distance(start, &v),
+                    g.distance(start, &v),
+                    "tree paths are shortest"
+                );
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn bfs_tree(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        for v in g.graph.iter_vertices() {
+            check_bfs_tree(&g.graph, &v);
+        }
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        for v in g.graph.iter_vertices() {
+            check_bfs_tree(&g.graph, &v);
+        }
+    }
+
+    #[test]
+    fn bfs_tree_of_absent_vertex() {
+        let g = directed::TreeBackedGraph::new();
+        let tree: MappedGraph<directed::TreeBackedGraph> = g.bfs_tree(&VertexId(0));
+        assert_eq!(tree.vertex_size(), 0);
+    }
+
+    #[test]
+    fn dfs_tree_classified() {
+        //   0 -> 1 -> 2
+        //   |  ^ |
+        //   |  | v
+        //   +--- 3 <- 4
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+        let e01 = g.add_edge(vs[0], vs[1]);
+        let e12 = g.add_edge(vs[1], vs[2]);
+        let e13 = g.add_edge(vs[1], vs[3]);
+        let e31 = g.add_edge(vs[3], vs[1]);
+        let e03 = g.add_edge(vs[0], vs[3]);
+        let e43 = g.add_edge(vs[4], vs[3]);
+        let (tree, kinds): (MappedGraph<directed::TreeBackedGraph>, _) =
+            g.dfs_tree_classified(&vs[0]);
+        assert_eq!(tree.vertex_size(), 4);
+        let mut tree_edges: Vec<_> = tree.iter_edges().map(|e| e.id).collect();
+        tree_edges.sort();
+        assert_eq!(tree_edges, vec![e01, e12, e13]);
+        assert_eq!(kinds[&e01], EdgeKind::Tree);
+        assert_eq!(kinds[&e12], EdgeKind::Tree);
+        assert_eq!(kinds[&e13], EdgeKind::Tree);
+        assert_eq!(kinds[&e31], EdgeKind::Back);
+        assert_eq!(kinds[&e03], EdgeKind::Forward);
+        assert!(!kinds.contains_key(&e43));
+
+        let (_, kinds): (MappedGraph<directed::TreeBackedGraph>, _) = g.dfs_tree_classified(&vs[4]);
+        assert_eq!(kinds[&e43], EdgeKind::Tree);
+        assert_eq!(kinds[&e31], EdgeKind::Tree);
+        assert_eq!(kinds[&e13], EdgeKind::Back);
+        assert_eq!(kinds[&e12], EdgeKind::Tree);
+        assert_eq!(kinds.len(), 4);
+
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let e01 = g.add_edge(vs[0], vs[1]);
+        let e02 = g.add_edge(vs[0], vs[2]);
+        let e21 = g.add_edge(vs[2], vs[1]);
+        let (_, kinds): (MappedGraph<directed::TreeBackedGraph>, _) = g.dfs_tree_classified(&vs[0]);
+        assert_eq!(kinds[&e01], EdgeKind::Tree);
+        assert_eq!(kinds[&e02], EdgeKind::Tree);
+        assert_eq!(kinds[&e21], EdgeKind::Cross);
+    }
+
+    #[quickcheck]
+    fn dfs_tree_classified_undirected(ops: Ops) {
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        for v in g.graph.iter_vertices() {
+            let (tree, kinds): (MappedGraph<undirected::TreeBackedGraph>, _) =
+                g.graph.dfs_tree_classified(&v);
+            let reachable = g
+                .graph
+                .iter_vertices()
+                .filter(|w| g.graph.distance(&v, w).is_some())
+                .collect::<Vec<_>>();
+            assert_eq!(tree.vertex_size(), reachable.len());
+            assert_eq!(tree.edge_size(), reachable.len() - 1);
+            let incident = g
+                .graph
+                .iter_edges()
+                .filter(|e| reachable.contains(&e.source))
+                .count();
+            assert_eq!(kinds.len(), incident);
+            assert_eq!(
+                kinds.values().filter(|k| **k == EdgeKind::Tree).count(),
+                tree.edge_size()
+            );
+            assert!(kinds
+                .values()
+                .all(|k| *k == EdgeKind::Tree || *k == EdgeKind::Back));
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use super::BfsLayers;
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// Trait and default implementation of depths and heights of rooted trees.
+pub trait TreeDepths
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Depths of vertices in the tree rooted at `root`, following out-edges.
+    ///
+    /// The root is of depth 0.
+    /// It is meant for trees and forests, but it is a breadth-first search,
+    /// so on general graphs the depth of a vertex is its distance from `root`
+    /// and cycles are fine.
+    /// Unreachable vertices are absent, and so is everything when `root` is not in the graph.
+    fn tree_depths(&self, root: &VertexId) -> HashMap<VertexId, usize, RandomState> {
+        let mut res = HashMap::with_hasher(new_random_state());
+        for (d, layer) in self.bfs_layers(root).enumerate() {
+            for v in layer {
+                res.insert(v, d);
+            }
+        }
+        res
+    }
+
+    /// Height of the tree rooted at `root`, i.e., the maximum of [TreeDepths::tree_depths].
+    ///
+    /// A single root is of height 0, and so is a `root` not in the graph.
+    fn tree_height(&self, root: &VertexId) -> usize {
+        self.bfs_layers(root).count().saturating_sub(1)
+    }
+}
+
+impl<G: QueryableGraph> TreeDepths for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rooted_tree() {
+        //       0
+        //     /   \
+        //    1     2
+        //   /       \
+        //  3         4
+        //            |
+        //            5
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..6).map(|_| g.add_vertex()).collect();
+        for (p, c) in [(0, 1), (0, 2), (1, 3), (2, 4), (4, 5)] {
+            g.add_edge(vs[p], vs[c]);
+        }
+        let depths = g.tree_depths(&vs[0]);
+        let trial: Vec<_> = vs.iter().map(|v| depths[v]).collect();
+        assert_eq!(trial, vec![0, 1, 1, 2, 2, 3]);
+        assert_eq!(g.tree_height(&vs[0]), 3);
+
+        let depths = g.tree_depths(&vs[2]);
+        assert_eq!(depths.len(), 3);
+        assert_eq!(depths[&vs[5]], 2);
+        assert_eq!(g.tree_height(&vs[2]), 2);
+        assert_eq!(g.tree_height(&vs[3]), 0);
+        assert!(g.tree_depths(&VertexId(100)).is_empty());
+        assert_eq!(g.tree_height(&VertexId(100)), 0);
+    }
+
+    #[test]
+    fn cycle() {
+        let mut g = directed::TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        for i in 0..3 {
+            g.add_edge(vs[i], vs[(i + 1) % 3]);
+        }
+        let depths = g.tree_depths(&vs[0]);
+        assert_eq!(depths[&vs[2]], 2);
+        assert_eq!(g.tree_height(&vs[0]), 2);
+    }
+}
@@ -0,0 +1,74 @@
+use super::undirected_view::undirected_adjacency;
+use crate::graph::*;
+
+/// Trait and default implementation of counting triangles.
+pub trait TriangleCount
+where
+    Self: QueryableGraph + Sized,
+{
+    /// Number of triangles in the undirected view of the graph.
+    ///
+    /// Directions of edges are ignored.
+    /// Self-loops are ignored and parallel edges count once.
+    ///
+    /// It intersects sorted neighbors,
+    /// so that each triangle is counted exactly once from its least vertex.
+    fn triangle_count(&self) -> usize {
+        let adj = undirected_adjacency(self);
+        let mut res = 0;
+        for (u, u_neighbors) in adj.iter() {
+            for v in u_neighbors.range(u.next()..) {
+                let v_neighbors = adj.get(v).unwrap();
+                res += u_neighbors
+                    .range(v.next()..)
+                    .filter(|w| v_neighbors.contains(w))
+                    .count();
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> TriangleCount for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::super::test_graphs::complete;
+    use super::*;
+
+    #[test]
+    fn k4() {
+        let g: directed::TreeBackedGraph = complete(4);
+        assert_eq!(g.triangle_count(), 4);
+        let g: undirected::TreeBackedGraph = complete(4);
+        assert_eq!(g.triangle_count(), 4);
+    }
+
+    #[test]
+    fn opposite_directions_parallel_edges_and_self_loops() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v0);
+        g.add_edge(v2, v1);
+        g.add_edge(v2, v0);
+        g.add_edge(v2, v0);
+        g.add_edge(v2, v2);
+        assert_eq!(g.triangle_count(), 1);
+    }
+
+    #[test]
+    fn bipartite() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let left: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        let right: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        for u in left.iter() {
+            for v in right.iter() {
+                g.add_edge(*u, *v);
+            }
+        }
+        assert_eq!(g.triangle_count(), 0);
+    }
+}
@@ -0,0 +1,44 @@
+use crate::graph::*;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Sorted adjacency of the undirected view of a graph.
+///
+/// Directions of edges are ignored.
+/// Self-loops are dropped and parallel edges are collapsed.
+pub(crate) fn undirected_adjacency<G>(graph: &G) -> BTreeMap<VertexId, BTreeSet<VertexId>>
+where
+    G: QueryableGraph,
+{
+    let mut res: BTreeMap<VertexId, BTreeSet<VertexId>> = graph
+        .iter_vertices()
+        .map(|v| (v, BTreeSet::new()))
+        .collect();
+    for e in graph.iter_edges() {
+        if e.source == e.sink {
+            continue;
+        }
+        res.get_mut(&e.source).unwrap().insert(e.sink);
+        res.get_mut(&e.sink).unwrap().insert(e.source);
+    }
+    res
+}
+
+/// Incident edges, with the other endpoints, of the undirected view of a graph.
+///
+/// Directions of edges are ignored.
+/// Self-loops are dropped but parallel edges are kept.
+pub(crate) fn undirected_incidence<G>(graph: &G) -> BTreeMap<VertexId, Vec<(EdgeId, VertexId)>>
+where
+    G: QueryableGraph,
+{
+    let mut res: BTreeMap<VertexId, Vec<(EdgeId, VertexId)>> =
+        graph.iter_vertices().map(|v| (v, vec![])).collect();
+    for e in graph.iter_edges() {
+        if e.source == e.sink {
+            continue;
+        }
+        res.get_mut(&e.source).unwrap().push((e.id, e.sink));
+        res.get_mut(&e.sink).unwrap().push((e.id, e.source));
+    }
+    res
+}
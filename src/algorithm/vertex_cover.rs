@@ -0,0 +1,74 @@
+use crate::graph::*;
+use std::collections::BTreeSet;
+
+/// Trait and default implementation of approximating minimum vertex covers.
+pub trait VertexCover
+where
+    Self: QueryableGraph + Sized,
+{
+    /// A vertex cover, as sorted vertex ID's, at most twice as large as a minimum one.
+    ///
+    /// Directions of edges are ignored.
+    /// Every edge, including self-loops, has at least one endpoint in the result.
+    ///
+    /// It picks edges in ascending order of their ID's.
+    /// Whenever an edge is uncovered, both its endpoints join the cover.
+    /// Picked edges form a maximal matching,
+    /// and any cover must contain one endpoint of each of them.
+    fn vertex_cover_2approx(&self) -> Vec<VertexId> {
+        let mut edges: Vec<_> = self.iter_edges().collect();
+        edges.sort();
+        let mut cover = BTreeSet::new();
+        for e in edges {
+            if !cover.contains(&e.source) && !cover.contains(&e.sink) {
+                cover.insert(e.source);
+                cover.insert(e.sink);
+            }
+        }
+        cover.into_iter().collect()
+    }
+}
+
+impl<G: QueryableGraph> VertexCover for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn check<G: QueryableGraph>(g: &G) {
+        let cover = g.vertex_cover_2approx();
+        assert!(cover.windows(2).all(|x| x[0] < x[1]));
+        for e in g.iter_edges() {
+            assert!(cover.binary_search(&e.source).is_ok() || cover.binary_search(&e.sink).is_ok());
+        }
+    }
+
+    #[quickcheck]
+    fn covers_every_edge(ops: Ops) {
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+    }
+
+    #[test]
+    fn star() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let center = g.add_vertex();
+        for _ in 0..5 {
+            let v = g.add_vertex();
+            g.add_edge(center, v);
+        }
+        assert_eq!(g.vertex_cover_2approx().len(), 2);
+    }
+
+    #[test]
+    fn self_loop() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v = g.add_vertex();
+        g.add_edge(v, v);
+        assert_eq!(g.vertex_cover_2approx(), vec![v]);
+    }
+}
@@ -0,0 +1,75 @@
+use crate::graph::*;
+
+/// A trait with default implementation for exporting a graph as a boolean adjacency matrix.
+pub trait ToAdjacencyMatrix: QueryableGraph + DirectedOrNot {
+    /// Exports the graph as a square boolean adjacency matrix.
+    ///
+    /// It returns vertices in ascending order of their ID's,
+    /// and a matrix whose `[i][j]` cell tells whether there is an edge from the `i`-th vertex to the `j`-th.
+    /// Parallel edges collapse into a single `true`, and self-loops set the diagonal.
+    /// For undirected graphs, the matrix is symmetric.
+    ///
+    /// It takes O(V^2) memory regardless of how sparse the graph is.
+    fn to_adjacency_matrix(&self) -> (Vec<VertexId>, Vec<Vec<bool>>) {
+        let mut order: Vec<_> = self.iter_vertices().collect();
+        order.sort();
+        let mut matrix = vec![vec![false; order.len()]; order.len()];
+        for e in self.iter_edges() {
+            let src = order.binary_search(&e.source).unwrap();
+            let snk = order.binary_search(&e.sink).unwrap();
+            matrix[src][snk] = true;
+            if !Self::DIRECTED_OR_NOT {
+                matrix[snk][src] = true;
+            }
+        }
+        (order, matrix)
+    }
+}
+
+impl<G: QueryableGraph + DirectedOrNot> ToAdjacencyMatrix for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn directed() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v1);
+        g.add_edge(v2, v2);
+        let (order, matrix) = g.to_adjacency_matrix();
+        assert_eq!(order, vec![v0, v1, v2]);
+        assert_eq!(
+            matrix,
+            vec![
+                vec![false, true, false],
+                vec![false, false, false],
+                vec![false, false, true],
+            ]
+        );
+    }
+
+    #[quickcheck]
+    fn undirected_symmetric(ops: Ops) {
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let (order, matrix) = g.graph.to_adjacency_matrix();
+        assert_eq!(order.len(), g.graph.vertex_size());
+        for i in 0..order.len() {
+            for j in 0..order.len() {
+                assert_eq!(matrix[i][j], matrix[j][i]);
+                let connected = g
+                    .graph
+                    .edges_connecting(&order[i], &order[j])
+                    .next()
+                    .is_some();
+                assert_eq!(matrix[i][j], connected);
+            }
+        }
+    }
+}
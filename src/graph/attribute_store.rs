@@ -0,0 +1,122 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Attributes of vertices or edges, kept aside from a plain graph.
+///
+/// `K` is either [VertexId] or [EdgeId].
+/// It does not watch the graph,
+/// so it is up to users to drop attributes of removed vertices and edges,
+/// e.g., by [AttributeStore::remove_edges] with edges returned from
+/// [VertexShrinkableGraph::remove_vertex].
+#[derive(Debug, Clone)]
+pub struct AttributeStore<K, T> {
+    attributes: HashMap<K, T, RandomState>,
+}
+
+impl<K, T> Default for AttributeStore<K, T>
+where
+    K: Hash + Eq + Copy,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, T> AttributeStore<K, T>
+where
+    K: Hash + Eq + Copy,
+{
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self {
+            attributes: HashMap::with_hasher(new_random_state()),
+        }
+    }
+
+    /// Attribute of `id`, or `None` if it is not set.
+    pub fn get(&self, id: &K) -> Option<&T> {
+        self.attributes.get(id)
+    }
+
+    /// Mutable attribute of `id`, or `None` if it is not set.
+    pub fn get_mut(&mut self, id: &K) -> Option<&mut T> {
+        self.attributes.get_mut(id)
+    }
+
+    /// Sets the attribute of `id` and returns the old one if any.
+    pub fn set(&mut self, id: K, attr: T) -> Option<T> {
+        self.attributes.insert(id, attr)
+    }
+
+    /// Removes the attribute of `id` and returns it if any.
+    pub fn remove(&mut self, id: &K) -> Option<T> {
+        self.attributes.remove(id)
+    }
+
+    /// Iterates over ID's with their attributes, without any specific order.
+    pub fn iter(&self) -> impl Iterator<Item = (K, &T)> + '_ {
+        self.attributes.iter().map(|(id, attr)| (*id, attr))
+    }
+
+    /// Number of ID's with attributes.
+    pub fn len(&self) -> usize {
+        self.attributes.len()
+    }
+
+    /// Whether there is no attribute at all.
+    pub fn is_empty(&self) -> bool {
+        self.attributes.is_empty()
+    }
+}
+
+impl<T> AttributeStore<EdgeId, T> {
+    /// Drops attributes of removed edges,
+    /// e.g., those returned from [VertexShrinkableGraph::remove_vertex].
+    pub fn remove_edges<I>(&mut self, removed: I)
+    where
+        I: IntoIterator<Item = Edge>,
+    {
+        for e in removed {
+            self.attributes.remove(&e.id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_sync_with_remove_vertex() {
+        let mut g = directed::TreeBackedGraph::new();
+        let mut names = AttributeStore::new();
+        let mut lengths = AttributeStore::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        for (i, v) in vs.iter().enumerate() {
+            names.set(*v, format!("v{}", i));
+        }
+        let e01 = g.add_edge(vs[0], vs[1]);
+        let e12 = g.add_edge(vs[1], vs[2]);
+        let e20 = g.add_edge(vs[2], vs[0]);
+        lengths.set(e01, 1.0);
+        lengths.set(e12, 2.0);
+        lengths.set(e20, 3.0);
+        assert_eq!(lengths.set(e20, 4.0), Some(3.0));
+        *lengths.get_mut(&e01).unwrap() += 1.0;
+
+        names.remove(&vs[1]);
+        lengths.remove_edges(g.remove_vertex(&vs[1]));
+        assert_eq!(names.len(), g.vertex_size());
+        assert_eq!(lengths.len(), g.edge_size());
+        assert_eq!(names.get(&vs[1]), None);
+        assert_eq!(names.get(&vs[2]).map(|s| s.as_str()), Some("v2"));
+        let remaining: Vec<_> = lengths.iter().map(|(e, l)| (e, *l)).collect();
+        assert_eq!(remaining, vec![(e20, 4.0)]);
+
+        lengths.remove_edges(g.remove_vertex(&vs[0]));
+        assert!(lengths.is_empty());
+    }
+}
@@ -0,0 +1,230 @@
+//! Checks shared by tests of directed and undirected graphs.
+//!
+//! Expectations which depend on directedness follow [DirectedOrNot::DIRECTED_OR_NOT].
+use crate::graph::directed::Ops;
+use crate::graph::*;
+
+pub fn with_capacity<G>()
+where
+    G: GrowableGraph + QueryableGraph,
+{
+    let mut g = G::with_capacity(2, 1);
+    let v0 = g.add_vertex();
+    let v1 = g.add_vertex();
+    let e = g.add_edge(v0, v1);
+    assert_eq!(g.vertex_size(), 2);
+    assert_eq!(g.edge_size(), 1);
+    assert_eq!(
+        g.find_edge(&e),
+        Some(Edge {
+            id: e,
+            source: v0,
+            sink: v1
+        })
+    );
+}
+
+pub fn edge_multiplicity<G>()
+where
+    G: GrowableGraph + QueryableGraph + DirectedOrNot,
+{
+    let mut g = G::new();
+    let v0 = g.add_vertex();
+    let v1 = g.add_vertex();
+    g.add_edge(v0, v1);
+    g.add_edge(v0, v1);
+    g.add_edge(v1, v1);
+    g.add_edge(v1, v0);
+    g.add_edge(v1, v1);
+    let (forward, backward) = if G::DIRECTED_OR_NOT { (2, 1) } else { (3, 3) };
+    assert_eq!(g.edge_multiplicity(&v0, &v1), forward);
+    assert_eq!(g.edge_multiplicity(&v1, &v0), backward);
+    assert_eq!(g.edge_multiplicity(&v0, &v0), 0);
+    assert_eq!(g.edge_multiplicity(&v1, &v1), 2);
+}
+
+pub fn try_add_edge<G>()
+where
+    G: GrowableGraph + QueryableGraph,
+{
+    let mut g = G::new();
+    let v0 = g.add_vertex();
+    let v1 = g.add_vertex();
+    let e = g.try_add_edge(v0, v1).unwrap();
+    assert!(g.contains_edge(&e));
+    let missing = VertexId(100);
+    assert_eq!(
+        g.try_add_edge(v0, missing),
+        Err(GraphError::VertexNotFound(missing))
+    );
+    assert_eq!(
+        g.try_add_edge(missing, v1),
+        Err(GraphError::VertexNotFound(missing))
+    );
+    assert_eq!(g.edge_size(), 1);
+}
+
+pub fn in_same_component<G>()
+where
+    G: GrowableGraph + QueryableGraph,
+{
+    let mut g = G::new();
+    let vs: Vec<_> = (0..6).map(|_| g.add_vertex()).collect();
+    // {0, 1, 2, 3} and {4, 5}
+    g.add_edge(vs[0], vs[1]);
+    g.add_edge(vs[2], vs[1]);
+    g.add_edge(vs[3], vs[2]);
+    g.add_edge(vs[4], vs[5]);
+    assert!(g.in_same_component(&vs[0], &vs[3]));
+    assert!(g.in_same_component(&vs[3], &vs[0]));
+    assert!(g.in_same_component(&vs[5], &vs[4]));
+    assert!(g.in_same_component(&vs[2], &vs[2]));
+    assert!(!g.in_same_component(&vs[0], &vs[4]));
+    assert!(!g.in_same_component(&vs[5], &vs[1]));
+    assert!(!g.in_same_component(&vs[0], &VertexId(100)));
+}
+
+pub fn contract_vertices<G>()
+where
+    G: GrowableGraph + QueryableGraph + VertexShrinkableGraph,
+{
+    let mut g = G::new();
+    let vs: Vec<_> = (0..5).map(|_| g.add_vertex()).collect();
+    // {1, 2, 3}, with an edge from 0 to 1, two from 2 to 4 and one from 3 to 4
+    g.add_edge(vs[0], vs[1]);
+    g.add_edge(vs[1], vs[2]);
+    g.add_edge(vs[2], vs[3]);
+    g.add_edge(vs[3], vs[1]);
+    g.add_edge(vs[1], vs[1]);
+    g.add_edge(vs[2], vs[4]);
+    g.add_edge(vs[2], vs[4]);
+    g.add_edge(vs[3], vs[4]);
+    let survivor = g.contract_vertices([vs[3], vs[2], vs[1], vs[3]]);
+    assert_eq!(survivor, Some(vs[1]));
+    let mut trial: Vec<_> = g.iter_vertices().collect();
+    trial.sort();
+    assert_eq!(trial, vec![vs[0], vs[1], vs[4]]);
+    assert_eq!(g.edge_size(), 4);
+    assert_eq!(g.edge_multiplicity(&vs[0], &vs[1]), 1);
+    assert_eq!(g.edge_multiplicity(&vs[1], &vs[4]), 3);
+    assert_eq!(g.edge_multiplicity(&vs[1], &vs[1]), 0);
+    assert_eq!(g.contract_vertices([VertexId(100)]), None);
+    assert_eq!(g.contract_vertices([vs[4]]), Some(vs[4]));
+    assert_eq!(g.edge_size(), 4);
+}
+
+pub fn incident_edges<G>()
+where
+    G: GrowableGraph + QueryableGraph + DirectedOrNot,
+{
+    let mut g = G::new();
+    let v0 = g.add_vertex();
+    let v1 = g.add_vertex();
+    let v2 = g.add_vertex();
+    let e0 = g.add_edge(v0, v1);
+    let e1 = g.add_edge(v1, v2);
+    let e2 = g.add_edge(v1, v1);
+    let e3 = g.add_edge(v2, v1);
+    // undirected graphs see every incident edge from `v1`
+    let into_v1 = |id, other| {
+        if G::DIRECTED_OR_NOT {
+            Edge {
+                id,
+                source: other,
+                sink: v1,
+            }
+        } else {
+            Edge {
+                id,
+                source: v1,
+                sink: other,
+            }
+        }
+    };
+    let mut trial: Vec<_> = g.incident_edges(&v1).collect();
+    trial.sort();
+    assert_eq!(
+        trial,
+        vec![
+            into_v1(e0, v0),
+            Edge {
+                id: e1,
+                source: v1,
+                sink: v2,
+            },
+            Edge {
+                id: e2,
+                source: v1,
+                sink: v1,
+            },
+            into_v1(e3, v2),
+        ]
+    );
+    assert_eq!(g.incident_edges(&v0).count(), 1);
+}
+
+pub fn double_removal<G>(ops: &Ops)
+where
+    G: GrowableGraph + QueryableGraph + VertexShrinkableGraph,
+{
+    let mut g: MappedGraph<G> = ops.into();
+    let mut vs: Vec<_> = g.graph.iter_vertices().collect();
+    vs.sort();
+    let mut es: Vec<_> = g.graph.iter_edges().map(|e| e.id).collect();
+    es.sort();
+    let vertex_size = g.graph.vertex_size();
+    for v in vs.iter().step_by(2) {
+        g.graph.remove_vertex(v).for_each(drop);
+        assert!(g.graph.remove_vertex(v).next().is_none());
+        assert_eq!(g.graph.in_edges(v).count(), 0);
+        assert_eq!(g.graph.out_edges(v).count(), 0);
+        for u in vs.iter() {
+            assert_eq!(g.graph.edges_connecting(u, v).count(), 0);
+            assert_eq!(g.graph.edges_connecting(v, u).count(), 0);
+        }
+    }
+    assert_eq!(g.graph.vertex_size(), vertex_size - (vs.len() + 1) / 2);
+    for e in es.iter() {
+        let present = g.graph.contains_edge(e);
+        assert_eq!(g.graph.remove_edge(e).is_some(), present);
+        assert!(g.graph.remove_edge(e).is_none());
+    }
+    assert_eq!(g.graph.edge_size(), 0);
+    for v in vs.iter() {
+        assert_eq!(g.graph.in_edges(v).count(), 0);
+        assert_eq!(g.graph.out_edges(v).count(), 0);
+    }
+}
+
+pub fn for_each_out_edge<G>(ops: &Ops)
+where
+    G: GrowableGraph + QueryableGraph + VertexShrinkableGraph,
+{
+    let g: MappedGraph<G> = ops.into();
+    for v in g.graph.iter_vertices() {
+        let mut trial = vec![];
+        g.graph.for_each_out_edge(&v, |e| trial.push(e.clone()));
+        let oracle: Vec<_> = g.graph.out_edges(&v).collect();
+        assert_eq!(trial, oracle);
+    }
+}
+
+pub fn into_iterator<G>()
+where
+    G: GrowableGraph + VertexShrinkableGraph,
+    for<'a> &'a G: IntoIterator<Item = VertexId>,
+{
+    let mut g = G::new();
+    let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+    let mut trial = vec![];
+    for v in &g {
+        trial.push(v);
+    }
+    trial.sort();
+    assert_eq!(trial, vs);
+
+    g.remove_vertex(&vs[1]).for_each(drop);
+    let mut trial: Vec<_> = (&g).into_iter().collect();
+    trial.sort();
+    assert_eq!(trial, vec![vs[0], vs[2]]);
+}
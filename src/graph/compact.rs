@@ -0,0 +1,90 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use bimap::BiHashMap;
+
+/// A trait with default implementation for relabeling a graph with contiguous ID's.
+pub trait Compact: GrowableGraph + QueryableGraph + Sized {
+    /// A copy of the graph whose vertex ID's are `0..vertex_size()`
+    /// and edge ID's are `0..edge_size()`,
+    /// with maps from new ID's to the original ones.
+    ///
+    /// Vertices and edges keep their relative orders of ID's.
+    ///
+    /// It relies on a new graph allocating ID's contiguously from 0,
+    /// which holds for all graphs in this crate.
+    fn compact(&self) -> MappedGraph<Self> {
+        let mut vertices: Vec<_> = self.iter_vertices().collect();
+        vertices.sort();
+        let mut edges: Vec<_> = self.iter_edges().collect();
+        edges.sort();
+        let mut graph = Self::with_capacity(vertices.len(), edges.len());
+        let mut vmap = BiHashMap::with_capacity_and_hashers(
+            vertices.len(),
+            new_random_state(),
+            new_random_state(),
+        );
+        let mut emap = BiHashMap::with_capacity_and_hashers(
+            edges.len(),
+            new_random_state(),
+            new_random_state(),
+        );
+        for v in vertices {
+            let new_v = graph.add_vertex();
+            vmap.insert(new_v, v);
+        }
+        for e in edges {
+            let src = vmap.get_by_right(&e.source).unwrap();
+            let snk = vmap.get_by_right(&e.sink).unwrap();
+            let new_e = graph.add_edge(*src, *snk);
+            emap.insert(new_e, e.id);
+        }
+        MappedGraph { graph, vmap, emap }
+    }
+}
+
+impl<G: GrowableGraph + QueryableGraph> Compact for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    fn check<G>(g: &G)
+    where
+        G: GrowableGraph + QueryableGraph,
+    {
+        let compacted = g.compact();
+        let mut vs: Vec<_> = compacted.graph.iter_vertices().map(|v| v.0).collect();
+        vs.sort();
+        assert_eq!(vs, (0..g.vertex_size()).collect::<Vec<_>>());
+        let mut es: Vec<_> = compacted.graph.iter_edges().map(|e| e.id.0).collect();
+        es.sort();
+        assert_eq!(es, (0..g.edge_size()).collect::<Vec<_>>());
+
+        for e in compacted.graph.iter_edges() {
+            let original = g.find_edge(compacted.emap.get_by_left(&e.id).unwrap());
+            let mapped = Edge {
+                id: *compacted.emap.get_by_left(&e.id).unwrap(),
+                source: *compacted.vmap.get_by_left(&e.source).unwrap(),
+                sink: *compacted.vmap.get_by_left(&e.sink).unwrap(),
+            };
+            assert_eq!(original, Some(mapped));
+        }
+        for v in g.iter_vertices() {
+            let new_v = compacted.vmap.get_by_right(&v).unwrap();
+            assert_eq!(compacted.graph.out_degree(new_v), g.out_degree(&v));
+            assert_eq!(compacted.graph.in_degree(new_v), g.in_degree(&v));
+        }
+    }
+
+    #[quickcheck]
+    fn contiguous_and_equal(ops: Ops) {
+        let dig: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+        let dig: MappedGraph<directed::AdjacentListGraph> = (&ops).into();
+        check(&dig.graph);
+    }
+}
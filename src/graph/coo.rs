@@ -0,0 +1,157 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use bimap::BiHashMap;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Loads a graph from the coordinate format of Matrix Market files.
+///
+/// ```plain
+/// %%MatrixMarket matrix coordinate real general
+/// % comments
+/// 3 3 2
+/// 1 2 0.5
+/// 3 1 1.0
+/// ```
+///
+/// Lines starting with `%`, as well as blank lines, are skipped.
+/// The first remaining line tells numbers of rows, columns and entries.
+/// Each following line is an entry `row col [value]`,
+/// which becomes an edge from vertex `row` to vertex `col`.
+/// Values are ignored.
+///
+/// There are vertices for all indices from 1 to the larger of numbers of rows and columns.
+/// In the returned [MappedGraph], a vertex is mapped from `VertexId(i)` for file index `i`,
+/// and an edge from `EdgeId(k)` for the `k`-th entry, starting from 0.
+///
+/// Malformed input fails with [io::ErrorKind::InvalidData].
+/// Numbers in the header are not trusted for allocation:
+/// entries are all read and checked against the header before the graph is built.
+pub fn load_coo<R, G>(r: &mut R) -> io::Result<MappedGraph<G>>
+where
+    R: Read,
+    G: GrowableGraph,
+{
+    fn invalid(msg: String) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, msg)
+    }
+
+    fn parse_index(field: Option<&str>, line_no: usize) -> io::Result<usize> {
+        field
+            .and_then(|x| x.parse().ok())
+            .ok_or_else(|| invalid(format!("line {}: expect an index", line_no)))
+    }
+
+    let mut lines = BufReader::new(r)
+        .lines()
+        .enumerate()
+        .map(|(i, line)| line.map(|line| (i + 1, line)))
+        .filter(|line| match line {
+            Ok((_, line)) => {
+                let line = line.trim();
+                !line.is_empty() && !line.starts_with('%')
+            }
+            Err(_) => true,
+        });
+
+    let (line_no, header) = lines
+        .next()
+        .ok_or_else(|| invalid("missing header".to_string()))??;
+    let mut fields = header.split_whitespace();
+    let rows = parse_index(fields.next(), line_no)?;
+    let cols = parse_index(fields.next(), line_no)?;
+    let entries = parse_index(fields.next(), line_no)?;
+
+    let mut coords = vec![];
+    for line in lines {
+        let (line_no, line) = line?;
+        let mut fields = line.split_whitespace();
+        let row = parse_index(fields.next(), line_no)?;
+        let col = parse_index(fields.next(), line_no)?;
+        if row == 0 || row > rows || col == 0 || col > cols {
+            return Err(invalid(format!("line {}: index out of range", line_no)));
+        }
+        if coords.len() == entries {
+            return Err(invalid(format!("line {}: too many entries", line_no)));
+        }
+        coords.push((row, col));
+    }
+    if coords.len() != entries {
+        return Err(invalid(format!(
+            "expect {} entries but {} found",
+            entries,
+            coords.len()
+        )));
+    }
+
+    let mut graph = G::new();
+    let mut vmap = BiHashMap::with_hashers(new_random_state(), new_random_state());
+    let mut emap = BiHashMap::with_hashers(new_random_state(), new_random_state());
+    for i in 1..=rows.max(cols) {
+        vmap.insert(graph.add_vertex(), VertexId(i));
+    }
+    for (row, col) in coords {
+        let src = *vmap.get_by_right(&VertexId(row)).unwrap();
+        let snk = *vmap.get_by_right(&VertexId(col)).unwrap();
+        let eid = graph.add_edge(src, snk);
+        emap.insert(eid, EdgeId(emap.len()));
+    }
+    Ok(MappedGraph { graph, vmap, emap })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const COO: &str = "%%MatrixMarket matrix coordinate real general
+% a small graph
+%
+4 3 4
+1 2 0.5
+3 1 1.0
+
+% a self-loop
+2 2 -1
+4 3
+";
+
+    #[test]
+    fn load() {
+        let g: MappedGraph<directed::TreeBackedGraph> = load_coo(&mut COO.as_bytes()).unwrap();
+        assert_eq!(g.graph.vertex_size(), 4);
+        assert_eq!(g.graph.edge_size(), 4);
+        let mut trial: Vec<_> = g
+            .graph
+            .iter_edges()
+            .map(|e| {
+                (
+                    g.emap.get_by_left(&e.id).unwrap().0,
+                    g.vmap.get_by_left(&e.source).unwrap().0,
+                    g.vmap.get_by_left(&e.sink).unwrap().0,
+                )
+            })
+            .collect();
+        trial.sort();
+        assert_eq!(trial, vec![(0, 1, 2), (1, 3, 1), (2, 2, 2), (3, 4, 3)]);
+    }
+
+    #[test]
+    fn malformed() {
+        let cases = [
+            "% only comments\n",
+            "2 2\n",
+            "2 2 1\n1 3\n",
+            "2 2 1\n1 x\n",
+            "2 2 2\n1 2\n",
+            "2 2 1\n1 2\n2 1\n",
+            // huge numbers in headers
+            "1 1 18446744073709551615\n1 1\n",
+            "18446744073709551615 18446744073709551615 18446744073709551615\n1 1\n",
+        ];
+        for case in cases {
+            let err = load_coo::<_, undirected::TreeBackedGraph>(&mut case.as_bytes())
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{:?}", case);
+        }
+    }
+}
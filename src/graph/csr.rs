@@ -0,0 +1,91 @@
+use crate::graph::*;
+
+/// A graph in the compressed sparse row format.
+///
+/// Out-neighbors of `vertex_order[i]` are `targets[offsets[i]..offsets[i + 1]]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrGraph {
+    /// `vertex_order.len() + 1` offsets into `targets`.
+    pub offsets: Vec<usize>,
+    /// Sinks of out-edges, grouped by their sources.
+    pub targets: Vec<VertexId>,
+    /// Vertices in ascending order of their ID's.
+    pub vertex_order: Vec<VertexId>,
+}
+
+impl CsrGraph {
+    /// Out-neighbors of the `idx`-th vertex in `vertex_order`.
+    pub fn neighbors(&self, idx: usize) -> &[VertexId] {
+        &self.targets[self.offsets[idx]..self.offsets[idx + 1]]
+    }
+}
+
+/// A trait with default implementation for exporting a graph in the compressed sparse row format.
+pub trait ToCsr: QueryableGraph {
+    /// Exports the graph in the compressed sparse row format.
+    ///
+    /// For undirected graphs, each edge is an out-edge of both its endpoints,
+    /// except self-loops, which occur once.
+    fn to_csr(&self) -> CsrGraph
+    where
+        Self: Sized,
+    {
+        let mut vertex_order: Vec<_> = self.iter_vertices().collect();
+        vertex_order.sort();
+        let mut offsets = Vec::with_capacity(vertex_order.len() + 1);
+        let mut targets = Vec::with_capacity(self.edge_size());
+        offsets.push(0);
+        for v in vertex_order.iter() {
+            self.for_each_out_edge(v, |e| targets.push(e.sink));
+            offsets.push(targets.len());
+        }
+        CsrGraph {
+            offsets,
+            targets,
+            vertex_order,
+        }
+    }
+}
+
+impl<G: QueryableGraph> ToCsr for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn reconstruct_adjacency(ops: Ops) {
+        fn check<G: QueryableGraph>(g: &G) {
+            let csr = g.to_csr();
+            assert_eq!(csr.offsets.len(), csr.vertex_order.len() + 1);
+            assert!(csr.vertex_order.windows(2).all(|x| x[0] < x[1]));
+            let mut trial: Vec<_> = csr
+                .vertex_order
+                .iter()
+                .enumerate()
+                .map(|(i, v)| {
+                    let mut sinks = csr.neighbors(i).to_vec();
+                    sinks.sort();
+                    (*v, sinks)
+                })
+                .collect();
+            trial.sort();
+            let mut oracle: Vec<_> = g
+                .adjacency()
+                .map(|(v, mut sinks)| {
+                    sinks.sort();
+                    (v, sinks)
+                })
+                .collect();
+            oracle.sort();
+            assert_eq!(trial, oracle);
+        }
+
+        let dig: MappedGraph<directed::AdjacentListGraph> = (&ops).into();
+        check(&dig.graph);
+        let udg: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        check(&udg.graph);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::graph::*;
+
+/// Structural differences from one graph to another.
+///
+/// All fields are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GraphDiff {
+    /// Vertices only in the other graph.
+    pub added_vertices: Vec<VertexId>,
+    /// Vertices only in this graph.
+    pub removed_vertices: Vec<VertexId>,
+    /// Edges only in the other graph.
+    pub added_edges: Vec<Edge>,
+    /// Edges only in this graph.
+    pub removed_edges: Vec<Edge>,
+}
+
+impl GraphDiff {
+    /// Whether both graphs are structurally the same.
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.removed_vertices.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+    }
+}
+
+/// A trait with default implementation for diffing graphs.
+pub trait Diff: QueryableGraph {
+    /// Differences from `self` to `other`.
+    ///
+    /// Both graphs are assumed to share a same space of ID's,
+    /// e.g., `other` is a modified copy of `self`.
+    /// So vertices and edges are compared by their ID's.
+    /// An edge whose endpoints differ between both graphs
+    /// is reported as both removed and added.
+    ///
+    /// For graphs in different spaces of ID's, see [MappedGraph].
+    fn diff<G2: QueryableGraph>(&self, other: &G2) -> GraphDiff {
+        let mut added_vertices: Vec<_> = other
+            .iter_vertices()
+            .filter(|v| !self.contains_vertex(v))
+            .collect();
+        let mut removed_vertices: Vec<_> = self
+            .iter_vertices()
+            .filter(|v| !other.contains_vertex(v))
+            .collect();
+        let mut added_edges: Vec<_> = other
+            .iter_edges()
+            .filter(|e| self.find_edge(&e.id).as_ref() != Some(e))
+            .collect();
+        let mut removed_edges: Vec<_> = self
+            .iter_edges()
+            .filter(|e| other.find_edge(&e.id).as_ref() != Some(e))
+            .collect();
+        added_vertices.sort();
+        removed_vertices.sort();
+        added_edges.sort();
+        removed_edges.sort();
+        GraphDiff {
+            added_vertices,
+            removed_vertices,
+            added_edges,
+            removed_edges,
+        }
+    }
+}
+
+impl<G: QueryableGraph> Diff for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn modified_copy() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let e0 = g.add_edge(v0, v1);
+        g.add_edge(v1, v2);
+        let mut h = g.clone();
+        assert!(g.diff(&h).is_empty());
+
+        let removed: Vec<_> = h.remove_vertex(&v2).collect();
+        h.remove_edge(&e0);
+        let v3 = h.add_vertex();
+        let e2 = h.add_edge(v0, v3);
+        let diff = g.diff(&h);
+        assert_eq!(diff.added_vertices, vec![v3]);
+        assert_eq!(diff.removed_vertices, vec![v2]);
+        assert_eq!(
+            diff.added_edges,
+            vec![Edge {
+                id: e2,
+                source: v0,
+                sink: v3
+            }]
+        );
+        let mut oracle = removed;
+        oracle.push(g.find_edge(&e0).unwrap());
+        oracle.sort();
+        assert_eq!(diff.removed_edges, oracle);
+
+        let rev = h.diff(&g);
+        assert_eq!(rev.added_vertices, diff.removed_vertices);
+        assert_eq!(rev.removed_edges, diff.added_edges);
+    }
+
+    #[quickcheck]
+    fn from_empty(ops: Ops) {
+        let g: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let empty = undirected::TreeBackedGraph::new();
+        let diff = empty.diff(&g.graph);
+        assert_eq!(diff.added_vertices.len(), g.graph.vertex_size());
+        assert_eq!(diff.added_edges.len(), g.graph.edge_size());
+        assert!(diff.removed_vertices.is_empty());
+        assert!(diff.removed_edges.is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+use crate::graph::*;
+#[cfg(feature = "rayon")]
+use crate::random_state::new_random_state;
+#[cfg(feature = "rayon")]
+use ahash::RandomState;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "rayon")]
+use std::collections::HashMap;
+#[cfg(feature = "rayon")]
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A read-only directed graph by sorted vectors, made by [TreeBackedGraph::freeze](super::TreeBackedGraph::freeze).
+///
+/// |                    | Complexity                                                          |
+/// | ------------------ | ------------------------------------------------------------------- |
+/// | `vertex_size`      | $O(1)$                                                              |
+/// | `iter_vertices`    | $O(1)$ per call to `.next()`.                                       |
+/// | `contains_vertex`  | $O(\log \|V\|)$                                                     |
+/// | `edge_size`        | $O(1)$                                                              |
+/// | `iter_edges`       | $O(1)$ per call to `.next()`.                                       |
+/// | `contains_edge`    | $O(\log \|E\|)$                                                     |
+/// | `find_edge`        | $O(\log \|E\|)$                                                     |
+/// | `edges_connecting` | returns in $O(\log \|E\|)$. $O(1)$ on each call to `.next`.         |
+/// | `in_edges`         | returns in $O(\log \|E\|)$. $O(1)$ on each call to `.next`.         |
+/// | `out_edges`        | returns in $O(\log \|E\|)$. $O(1)$ on each call to `.next`.         |
+/// | `in_degree`        | $O(\log \|V\|)$                                                     |
+/// | `out_degree`       | $O(\log \|V\|)$                                                     |
+/// | `edge_multiplicity`| $O(\log \|E\|)$                                                     |
+///
+/// There is no way to mutate it.
+/// So it is `Send + Sync`, and can be shared across threads by, e.g., [std::sync::Arc].
+/// With the `rayon` feature, it also provides parallel algorithms,
+/// e.g., `FrozenGraph::par_multi_source_bfs`.
+/// Vertices and edges are iterated in ascending order of their ID's,
+/// the same as the [TreeBackedGraph](super::TreeBackedGraph) it is frozen from.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FrozenGraph {
+    /// vertices together with their in-degrees and out-degrees
+    pub(super) vertices: Vec<(VertexId, usize, usize)>,
+    pub(super) edges: Vec<(EdgeId, VertexId, VertexId)>,
+    pub(super) in_edges: Vec<(VertexId, VertexId, EdgeId)>,
+    pub(super) out_edges: Vec<(VertexId, VertexId, EdgeId)>,
+}
+
+impl DirectedOrNot for FrozenGraph {
+    const DIRECTED_OR_NOT: bool = true;
+}
+
+impl std::fmt::Debug for FrozenGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "FrozenGraph {{")?;
+        for v in self.vertices_iter() {
+            writeln!(f, "{:?}:", v)?;
+            for e in self.out_edges_iter(&v) {
+                writeln!(f, "  -> {:?} by {:?}", e.sink, e.id)?;
+            }
+        }
+        writeln!(f, "}}")?;
+        Ok(())
+    }
+}
+
+/// It fails unless the graph passes [FrozenGraph::check_invariants].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FrozenGraph {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "FrozenGraph")]
+        struct Repr {
+            vertices: Vec<(VertexId, usize, usize)>,
+            edges: Vec<(EdgeId, VertexId, VertexId)>,
+            in_edges: Vec<(VertexId, VertexId, EdgeId)>,
+            out_edges: Vec<(VertexId, VertexId, EdgeId)>,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        let res = Self {
+            vertices: repr.vertices,
+            edges: repr.edges,
+            in_edges: repr.in_edges,
+            out_edges: repr.out_edges,
+        };
+        res.check_invariants().map_err(serde::de::Error::custom)?;
+        Ok(res)
+    }
+}
+
+impl FrozenGraph {
+    /// Verifies that redundant indices agree with each other,
+    /// for debugging and for validating deserialized graphs.
+    ///
+    /// That is, vertices and edges are sorted by their ID's without duplicates,
+    /// endpoints of edges are vertices,
+    /// `in_edges` and `out_edges` are exactly `edges` in sorted order,
+    /// and degrees are those counted from edges.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if let Some(w) = self.vertices.windows(2).find(|w| w[0].0 >= w[1].0) {
+            return Err(format!(
+                "vertices are not strictly ascending at {:?}",
+                w[1].0
+            ));
+        }
+        if let Some(w) = self.edges.windows(2).find(|w| w[0].0 >= w[1].0) {
+            return Err(format!("edges are not strictly ascending at {:?}", w[1].0));
+        }
+        let mut degrees: Vec<_> = self.vertices.iter().map(|(v, _, _)| (*v, 0, 0)).collect();
+        let mut in_edges = Vec::with_capacity(self.edges.len());
+        let mut out_edges = Vec::with_capacity(self.edges.len());
+        for (e, src, snk) in self.edges.iter() {
+            for v in [src, snk] {
+                if self.position(v).is_none() {
+                    return Err(format!("endpoint {:?} of {:?} is not a vertex", v, e));
+                }
+            }
+            degrees[self.position(snk).unwrap()].1 += 1;
+            degrees[self.position(src).unwrap()].2 += 1;
+            in_edges.push((*snk, *src, *e));
+            out_edges.push((*src, *snk, *e));
+        }
+        in_edges.sort();
+        out_edges.sort();
+        if in_edges != self.in_edges {
+            return Err("in_edges differ from edges".to_string());
+        }
+        if out_edges != self.out_edges {
+            return Err("out_edges differ from edges".to_string());
+        }
+        if degrees != self.vertices {
+            return Err(format!(
+                "degrees {:?} differ from counted ones {:?}",
+                self.vertices, degrees
+            ));
+        }
+        Ok(())
+    }
+
+    /// Same as [QueryableGraph::iter_vertices] but without boxing the iterator.
+    pub fn vertices_iter(&self) -> impl Iterator<Item = VertexId> + '_ {
+        self.vertices.iter().map(|(v, _, _)| *v)
+    }
+
+    /// Same as [QueryableGraph::iter_edges] but without boxing the iterator.
+    pub fn edges_iter(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.edges.iter().map(|(e, src, snk)| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::in_edges] but without boxing the iterator.
+    pub fn in_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        let v = *v;
+        let start = self.in_edges.partition_point(|(snk, _, _)| *snk < v);
+        let end = self.in_edges.partition_point(|(snk, _, _)| *snk <= v);
+        self.in_edges[start..end].iter().map(|(snk, src, e)| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::out_edges] but without boxing the iterator.
+    pub fn out_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        let v = *v;
+        let start = self.out_edges.partition_point(|(src, _, _)| *src < v);
+        let end = self.out_edges.partition_point(|(src, _, _)| *src <= v);
+        self.out_edges[start..end].iter().map(|(src, snk, e)| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::edges_connecting] but without boxing the iterator.
+    pub fn edges_connecting_iter(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> impl Iterator<Item = Edge> + '_ {
+        let key = (*source, *sink);
+        let start = self
+            .out_edges
+            .partition_point(|(src, snk, _)| (*src, *snk) < key);
+        let end = self
+            .out_edges
+            .partition_point(|(src, snk, _)| (*src, *snk) <= key);
+        self.out_edges[start..end].iter().map(|(src, snk, e)| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [MultiSourceBfs::multi_source_bfs](crate::algorithm::MultiSourceBfs::multi_source_bfs),
+    /// but vertices of each frontier are expanded in parallel.
+    #[cfg(feature = "rayon")]
+    pub fn par_multi_source_bfs<I>(&self, sources: I) -> HashMap<VertexId, usize, RandomState>
+    where
+        I: IntoIterator<Item = VertexId>,
+    {
+        let visited: Vec<_> = self
+            .vertices
+            .iter()
+            .map(|_| AtomicBool::new(false))
+            .collect();
+        // claims a vertex exactly once among all threads
+        let claim = |v: &VertexId| match self.position(v) {
+            None => false,
+            Some(idx) => !visited[idx].swap(true, Ordering::Relaxed),
+        };
+        let mut res = HashMap::with_hasher(new_random_state());
+        let mut current: Vec<_> = sources.into_iter().filter(|v| claim(v)).collect();
+        let mut d = 0;
+        while !current.is_empty() {
+            res.extend(current.iter().map(|v| (*v, d)));
+            d += 1;
+            current = current
+                .par_iter()
+                .flat_map_iter(|v| self.out_edges_iter(v).map(|e| e.sink))
+                .filter(|v| claim(v))
+                .collect();
+        }
+        res
+    }
+
+    fn position(&self, v: &VertexId) -> Option<usize> {
+        self.vertices.binary_search_by_key(v, |(v, _, _)| *v).ok()
+    }
+
+    fn degrees(&self, v: &VertexId) -> Option<(usize, usize)> {
+        self.position(v)
+            .map(|idx| (self.vertices[idx].1, self.vertices[idx].2))
+    }
+}
+
+impl QueryableGraph for FrozenGraph {
+    fn vertex_size(&self) -> usize {
+        self.vertices.len()
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        Box::new(self.vertices_iter())
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        self.degrees(v).is_some()
+    }
+
+    fn edge_size(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.edges_iter())
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        self.find_edge(e).is_some()
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        self.edges
+            .binary_search_by_key(e, |(e, _, _)| *e)
+            .ok()
+            .map(|idx| {
+                let (id, source, sink) = self.edges[idx];
+                Edge { id, source, sink }
+            })
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.in_edges_iter(v))
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.out_edges_iter(v))
+    }
+
+    fn for_each_out_edge<F: FnMut(&Edge)>(&self, v: &VertexId, mut f: F) {
+        for e in self.out_edges_iter(v) {
+            f(&e);
+        }
+    }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.degrees(v).map_or(0, |(in_degree, _)| in_degree)
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.degrees(v).map_or(0, |(_, out_degree)| out_degree)
+    }
+
+    fn edge_multiplicity(&self, source: &VertexId, sink: &VertexId) -> usize {
+        self.edges_connecting_iter(source, sink).count()
+    }
+
+    fn edges_connecting<'a, 'b>(
+        &'a self,
+        source: &'b VertexId,
+        sink: &'b VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + 'a> {
+        Box::new(self.edges_connecting_iter(source, sink))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{directed::*, EdgeId, MappedGraph, QueryableGraph, VertexId};
+    use quickcheck_macros::*;
+    use std::sync::Arc;
+
+    #[quickcheck]
+    fn freeze(ops: Ops) {
+        let oracle: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let oracle = oracle.graph;
+        let trial = oracle.clone().freeze();
+        assert_eq!(trial.check_invariants(), Ok(()));
+        assert_eq!(trial.vertex_size(), oracle.vertex_size());
+        assert_eq!(trial.edge_size(), oracle.edge_size());
+        assert!(trial.iter_vertices().eq(oracle.iter_vertices()));
+        assert!(trial.iter_edges().eq(oracle.iter_edges()));
+        let absent_v = VertexId(1000);
+        let absent_e = EdgeId(1000);
+        assert!(!trial.contains_vertex(&absent_v));
+        assert!(!trial.contains_edge(&absent_e));
+        assert_eq!(trial.out_degree(&absent_v), 0);
+        for e in oracle.iter_edges() {
+            assert!(trial.contains_edge(&e.id));
+            assert_eq!(trial.find_edge(&e.id), Some(e));
+        }
+        for u in oracle.iter_vertices() {
+            assert!(trial.contains_vertex(&u));
+            assert!(trial.in_edges(&u).eq(oracle.in_edges(&u)));
+            assert!(trial.out_edges(&u).eq(oracle.out_edges(&u)));
+            assert_eq!(trial.in_degree(&u), oracle.in_degree(&u));
+            assert_eq!(trial.out_degree(&u), oracle.out_degree(&u));
+            for v in oracle.iter_vertices() {
+                assert!(trial
+                    .edges_connecting(&u, &v)
+                    .eq(oracle.edges_connecting(&u, &v)));
+                assert_eq!(
+                    trial.edge_multiplicity(&u, &v),
+                    oracle.edge_multiplicity(&u, &v)
+                );
+            }
+        }
+    }
+
+    #[quickcheck]
+    fn share_across_threads(ops: Ops) {
+        let oracle: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let oracle = oracle.graph;
+        let trial = Arc::new(oracle.clone().freeze());
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let g = Arc::clone(&trial);
+                std::thread::spawn(move || g.iter_edges().collect::<Vec<_>>())
+            })
+            .collect();
+        for h in handles {
+            assert!(h.join().unwrap().into_iter().eq(oracle.iter_edges()));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[quickcheck]
+    fn par_multi_source_bfs(ops: Ops, picks: Vec<usize>) {
+        use crate::algorithm::MultiSourceBfs;
+
+        let oracle: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let oracle = oracle.graph;
+        let vertices: Vec<_> = oracle.iter_vertices().collect();
+        let sources: Vec<_> = if vertices.is_empty() {
+            vec![VertexId(1000)]
+        } else {
+            picks
+                .iter()
+                .map(|i| vertices[i % vertices.len()])
+                .chain(Some(VertexId(1000)))
+                .collect()
+        };
+        let trial = oracle.clone().freeze();
+        assert_eq!(
+            trial.par_multi_source_bfs(sources.iter().copied()),
+            oracle.multi_source_bfs(sources.iter().copied())
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_inconsistent() {
+        use crate::graph::GrowableGraph;
+
+        let mut g = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..3).map(|_| g.add_vertex()).collect();
+        g.add_edge(vs[0], vs[1]);
+        g.add_edge(vs[1], vs[2]);
+        let json = serde_json::to_value(g.freeze()).unwrap();
+        let trial: FrozenGraph = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(trial.check_invariants(), Ok(()));
+
+        let mut broken = json.clone();
+        broken["vertices"].as_array_mut().unwrap().reverse();
+        assert!(serde_json::from_value::<FrozenGraph>(broken).is_err());
+
+        let mut broken = json.clone();
+        broken["vertices"][0][2] = serde_json::json!(2);
+        assert!(serde_json::from_value::<FrozenGraph>(broken).is_err());
+
+        let mut broken = json.clone();
+        broken["out_edges"].as_array_mut().unwrap().reverse();
+        assert!(serde_json::from_value::<FrozenGraph>(broken).is_err());
+
+        let mut broken = json;
+        broken["in_edges"].as_array_mut().unwrap().pop();
+        assert!(serde_json::from_value::<FrozenGraph>(broken).is_err());
+    }
+}
@@ -2,6 +2,8 @@
 
 mod adjacent_list;
 pub use self::adjacent_list::*;
+mod frozen;
+pub use self::frozen::*;
 mod tree_backed;
 pub use self::tree_backed::*;
 
@@ -10,7 +12,9 @@ pub use self::tests::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::graph::checks;
     use crate::graph::*;
+    use quickcheck_macros::quickcheck;
     use rs_quickcheck_util::*;
     use std::collections::BTreeSet;
 
@@ -120,6 +124,297 @@ mod tests {
         }
     }
 
+    #[test]
+    fn with_capacity() {
+        checks::with_capacity::<directed::AdjacentListGraph>();
+        checks::with_capacity::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn adjacency() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v0, v2);
+        g.add_edge(v0, v2);
+        g.add_edge(v2, v2);
+        let oracle: Vec<_> = g
+            .iter_vertices()
+            .map(|v| (v, g.out_edges(&v).map(|e| e.sink).collect::<Vec<_>>()))
+            .collect();
+        assert_eq!(g.adjacency().collect::<Vec<_>>(), oracle);
+        assert_eq!(
+            g.adjacency_in().collect::<Vec<_>>(),
+            vec![(v0, vec![]), (v1, vec![v0]), (v2, vec![v0, v0, v2])]
+        );
+    }
+
+    #[test]
+    fn edge_multiplicity() {
+        checks::edge_multiplicity::<directed::AdjacentListGraph>();
+        checks::edge_multiplicity::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn distance() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v2);
+        g.add_edge(v0, v2);
+        g.add_edge(v2, v2);
+        assert_eq!(g.distance(&v0, &v0), Some(0));
+        assert_eq!(g.distance(&v2, &v2), Some(0));
+        assert_eq!(g.distance(&v0, &v1), Some(1));
+        assert_eq!(g.distance(&v0, &v2), Some(1));
+        assert_eq!(g.distance(&v2, &v0), None);
+        assert_eq!(g.distance(&v0, &v3), None);
+        g.remove_vertex(&v3).for_each(drop);
+        assert_eq!(g.distance(&v3, &v3), None);
+    }
+
+    #[test]
+    fn sources_and_sinks() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph + VertexShrinkableGraph,
+        {
+            let mut g = G::new();
+            let v0 = g.add_vertex();
+            let v1 = g.add_vertex();
+            let v2 = g.add_vertex();
+            let v3 = g.add_vertex();
+            let v4 = g.add_vertex();
+            g.add_edge(v0, v2);
+            g.add_edge(v1, v2);
+            g.add_edge(v2, v3);
+            g.add_edge(v0, v3);
+            let mut sources: Vec<_> = g.sources().collect();
+            sources.sort();
+            assert_eq!(sources, vec![v0, v1, v4]);
+            let mut sinks: Vec<_> = g.sinks().collect();
+            sinks.sort();
+            assert_eq!(sinks, vec![v3, v4]);
+            g.remove_vertex(&v2).for_each(drop);
+            let mut sources: Vec<_> = g.sources().collect();
+            sources.sort();
+            assert_eq!(sources, vec![v0, v1, v4]);
+            let mut sinks: Vec<_> = g.sinks().collect();
+            sinks.sort();
+            assert_eq!(sinks, vec![v1, v3, v4]);
+        }
+        check::<directed::AdjacentListGraph>();
+        check::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn try_add_edge() {
+        checks::try_add_edge::<directed::AdjacentListGraph>();
+        checks::try_add_edge::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn adjacent_list_iteration_order() {
+        let mut g = directed::AdjacentListGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let es = [
+            g.add_edge(vs[3], vs[0]),
+            g.add_edge(vs[0], vs[2]),
+            g.add_edge(vs[2], vs[3]),
+            g.add_edge(vs[1], vs[3]),
+        ];
+        let _ = g.remove_vertex(&vs[1]);
+        g.remove_edge(&es[1]);
+        assert_eq!(
+            g.iter_vertices().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3]]
+        );
+        assert_eq!(
+            g.iter_edges().map(|e| e.id).collect::<Vec<_>>(),
+            vec![es[0], es[2]]
+        );
+        // ID's vacated above may be reused, but new ones still come last.
+        let v = g.add_vertex();
+        let e = g.add_edge(v, vs[0]);
+        assert_eq!(
+            g.iter_vertices().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3], v]
+        );
+        assert_eq!(
+            (&g).into_iter().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3], v]
+        );
+        assert_eq!(
+            g.iter_edges().map(|e| e.id).collect::<Vec<_>>(),
+            vec![es[0], es[2], e]
+        );
+    }
+
+    #[test]
+    fn try_add_edge_to_removed_vertex() {
+        let mut g = directed::AdjacentListGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let _ = g.remove_vertex(&v1);
+        assert_eq!(g.try_add_edge(v0, v1), Err(GraphError::VertexNotFound(v1)));
+        assert_eq!(g.edge_size(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "endpoint not in the graph")]
+    fn add_edge_to_removed_vertex() {
+        let mut g = directed::AdjacentListGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let _ = g.remove_vertex(&v1);
+        g.add_edge(v0, v1);
+    }
+
+    #[test]
+    fn in_same_component() {
+        checks::in_same_component::<directed::AdjacentListGraph>();
+        checks::in_same_component::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn common_successors_and_predecessors() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph,
+        {
+            let mut g = G::new();
+            let a = g.add_vertex();
+            let b = g.add_vertex();
+            let c = g.add_vertex();
+            let d = g.add_vertex();
+            g.add_edge(a, c);
+            g.add_edge(b, c);
+            g.add_edge(a, d);
+            g.add_edge(b, d);
+            g.add_edge(b, d);
+            g.add_edge(a, b);
+            assert_eq!(g.common_successors(&a, &b), vec![c, d]);
+            assert_eq!(g.common_successors(&b, &a), vec![c, d]);
+            assert_eq!(g.common_successors(&c, &d), vec![]);
+            assert_eq!(g.common_predecessors(&c, &d), vec![a, b]);
+            assert_eq!(g.common_predecessors(&a, &c), vec![]);
+            assert_eq!(g.common_predecessors(&d, &d), vec![a, b]);
+            assert_eq!(g.common_successors(&a, &VertexId(100)), vec![]);
+        }
+        check::<directed::AdjacentListGraph>();
+        check::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn remove_edges_between() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph + EdgeShrinkableGraph,
+        {
+            let mut g = G::new();
+            let v0 = g.add_vertex();
+            let v1 = g.add_vertex();
+            let e0 = g.add_edge(v0, v1);
+            let e1 = g.add_edge(v0, v1);
+            let e2 = g.add_edge(v0, v1);
+            let e3 = g.add_edge(v1, v0);
+            let e4 = g.add_edge(v1, v1);
+            let trial = g.remove_edges_between(&v0, &v1);
+            assert_eq!(
+                trial.iter().map(|e| e.id).collect::<Vec<_>>(),
+                vec![e0, e1, e2]
+            );
+            assert!(trial.iter().all(|e| e.source == v0 && e.sink == v1));
+            assert_eq!(g.edge_size(), 2);
+            assert!(g.contains_edge(&e3));
+            assert!(g.contains_edge(&e4));
+            assert_eq!(g.remove_edges_between(&v0, &v1), vec![]);
+        }
+        check::<directed::AdjacentListGraph>();
+        check::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn contract_vertices() {
+        checks::contract_vertices::<directed::AdjacentListGraph>();
+        checks::contract_vertices::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn prune_isolated() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph + VertexShrinkableGraph,
+        {
+            let mut g = G::new();
+            let v0 = g.add_vertex();
+            let v1 = g.add_vertex();
+            let v2 = g.add_vertex();
+            let v3 = g.add_vertex();
+            let es = [g.add_edge(v0, v1), g.add_edge(v1, v2), g.add_edge(v2, v2)];
+            assert_eq!(g.isolated_vertices().collect::<Vec<_>>(), vec![v3]);
+            for e in es.iter() {
+                g.remove_edge(e);
+            }
+            let mut isolated: Vec<_> = g.isolated_vertices().collect();
+            isolated.sort();
+            assert_eq!(isolated, vec![v0, v1, v2, v3]);
+            assert_eq!(g.prune_isolated(), 4);
+            assert_eq!(g.vertex_size(), 0);
+            assert_eq!(g.prune_isolated(), 0);
+        }
+        check::<directed::AdjacentListGraph>();
+        check::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn incident_edges() {
+        checks::incident_edges::<directed::AdjacentListGraph>();
+        checks::incident_edges::<directed::TreeBackedGraph>();
+    }
+
+    #[quickcheck]
+    fn double_removal(ops: Ops) {
+        checks::double_removal::<directed::AdjacentListGraph>(&ops);
+        checks::double_removal::<directed::TreeBackedGraph>(&ops);
+    }
+
+    #[quickcheck]
+    fn for_each_out_edge(ops: Ops) {
+        checks::for_each_out_edge::<directed::AdjacentListGraph>(&ops);
+        checks::for_each_out_edge::<directed::TreeBackedGraph>(&ops);
+    }
+
+    #[test]
+    fn into_iterator() {
+        checks::into_iterator::<directed::AdjacentListGraph>();
+        checks::into_iterator::<directed::TreeBackedGraph>();
+    }
+
+    #[test]
+    fn would_create_cycle() {
+        let mut g = directed::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v1, v2);
+        g.add_edge(v0, v3);
+        assert!(g.would_create_cycle(&v2, &v0));
+        assert!(g.would_create_cycle(&v1, &v0));
+        assert!(g.would_create_cycle(&v3, &v3));
+        assert!(!g.would_create_cycle(&v0, &v2));
+        assert!(!g.would_create_cycle(&v3, &v2));
+        assert!(!g.would_create_cycle(&v2, &v3));
+        assert_eq!(g.edge_size(), 3);
+    }
+
     #[test]
     fn to_graphviz() {
         let mut g = directed::AdjacentListGraph::new();
@@ -2,14 +2,17 @@ use super::VertexId;
 
 /// ID for edges, which are essentially `usize`.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeId(pub usize);
 
 /// A factory to generate `EdgeId` uniquely.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EdgeIdFactory(usize);
 
 /// Information about a low-level edge.
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Edge {
     pub id: EdgeId,
     pub source: VertexId,
@@ -49,4 +52,30 @@ impl EdgeId {
     pub fn next(&self) -> Self {
         Self(self.0 + 1)
     }
+
+    /// Converts into `u32`, or `None` if it does not fit in.
+    pub fn to_u32(&self) -> Option<u32> {
+        u32::try_from(self.0).ok()
+    }
+
+    /// Converts from `u32`, the inverse of `to_u32`.
+    pub fn from_u32(x: u32) -> Self {
+        Self(x as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u32_conversion() {
+        for x in [0, 1, u32::MAX - 1, u32::MAX] {
+            assert_eq!(EdgeId::from_u32(x).to_u32(), Some(x));
+        }
+        if let Some(x) = (u32::MAX as usize).checked_add(1) {
+            assert_eq!(EdgeId::new(x).to_u32(), None);
+        }
+        assert_eq!(EdgeId::MAX.to_u32(), u32::try_from(usize::MAX).ok());
+    }
 }
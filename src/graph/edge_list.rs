@@ -0,0 +1,120 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use bimap::BiHashMap;
+use std::io::{self, BufRead};
+
+/// Loads a graph from an edge list, one line at a time.
+///
+/// ```plain
+/// # comments
+/// 1 2
+/// 3 1
+/// ```
+///
+/// Lines starting with `#`, as well as blank lines, are skipped.
+/// Each remaining line is an edge `source sink`, where both are non-negative integers,
+/// and anything after them is ignored.
+/// Vertices are added when they first appear in edges.
+/// In the returned [MappedGraph], a vertex is mapped from `VertexId(i)` for label `i` in the input,
+/// and an edge from `EdgeId(k)` for the `k`-th edge, starting from 0.
+///
+/// Lines are read into a reused buffer and added to the graph right away,
+/// so the input is never held as a whole.
+/// Wrap unbuffered readers, e.g., files, by [std::io::BufReader].
+///
+/// Malformed input fails with [io::ErrorKind::InvalidData].
+pub fn stream_edge_list<R, G>(mut r: R) -> io::Result<MappedGraph<G>>
+where
+    R: BufRead,
+    G: GrowableGraph,
+{
+    fn parse_label(field: Option<&str>, line_no: usize) -> io::Result<VertexId> {
+        field
+            .and_then(|x| x.parse().ok())
+            .map(VertexId)
+            .ok_or_else(|| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("line {}: expect a vertex", line_no),
+                )
+            })
+    }
+
+    let mut res = MappedGraph {
+        graph: G::new(),
+        vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+        emap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+    };
+    let mut line = String::new();
+    let mut line_no = 0;
+    loop {
+        line.clear();
+        if r.read_line(&mut line)? == 0 {
+            break;
+        }
+        line_no += 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        let mut fields = trimmed.split_whitespace();
+        let labels = [
+            parse_label(fields.next(), line_no)?,
+            parse_label(fields.next(), line_no)?,
+        ];
+        let mut ends = [VertexId::MIN; 2];
+        for (label, end) in labels.iter().zip(ends.iter_mut()) {
+            *end = match res.vmap.get_by_right(label) {
+                Some(v) => *v,
+                None => {
+                    let v = res.graph.add_vertex();
+                    res.vmap.insert(v, *label);
+                    v
+                }
+            };
+        }
+        let eid = res.graph.add_edge(ends[0], ends[1]);
+        res.emap.insert(eid, EdgeId(res.emap.len()));
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn many_lines() {
+        let mut input = String::from("# a cycle of 1000 vertices with chords\n\n");
+        for i in 0..1000 {
+            input.push_str(&format!("{} {}\n", i, (i + 1) % 1000));
+            if i % 10 == 0 {
+                input.push_str(&format!("{}\t{} 0.5\n", i, (i + 500) % 1000));
+            }
+        }
+        let g: MappedGraph<directed::TreeBackedGraph> =
+            stream_edge_list(Cursor::new(input)).unwrap();
+        assert_eq!(g.graph.vertex_size(), 1000);
+        assert_eq!(g.graph.edge_size(), 1100);
+        let label = |v: &VertexId| g.vmap.get_by_left(v).unwrap().0;
+        for e in g.graph.iter_edges() {
+            let (src, snk) = (label(&e.source), label(&e.sink));
+            assert!(snk == (src + 1) % 1000 || snk == (src + 500) % 1000);
+        }
+        let first = g.emap.get_by_right(&EdgeId(0)).unwrap();
+        let e = g.graph.find_edge(first).unwrap();
+        assert_eq!((label(&e.source), label(&e.sink)), (0, 1));
+    }
+
+    #[test]
+    fn malformed() {
+        let cases = ["1\n", "1 x\n", "0 1\n-1 2\n"];
+        for case in cases {
+            let err = stream_edge_list::<_, undirected::TreeBackedGraph>(Cursor::new(case))
+                .err()
+                .unwrap();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "{:?}", case);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use super::VertexId;
+use core::fmt;
+
+/// Errors of checked graph operations, e.g., [GrowableGraph::try_add_edge](super::GrowableGraph::try_add_edge).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GraphError {
+    /// The vertex is not in the graph.
+    VertexNotFound(VertexId),
+}
+
+impl fmt::Display for GraphError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GraphError::VertexNotFound(v) => write!(f, "vertex {} is not in the graph", v.0),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GraphError {}
@@ -0,0 +1,184 @@
+use crate::graph::*;
+
+/// A lazy view of a graph, which hides vertices and edges failing predicates.
+///
+/// * A vertex is visible iff it passes `vertex_filter`.
+/// * An edge is visible iff it passes `edge_filter` and both its endpoints are visible.
+///
+/// Unlike [ShadowedSubgraph] and [SelectedSubgraph],
+/// nothing is precomputed.
+/// Predicates are evaluated on each query.
+/// Thus, `vertex_size` and `edge_size` take $O(\|V\|)$ and $O(\|E\|)$ respectively.
+pub struct FilteredGraph<'a, G, FV, FE> {
+    lower_graph: &'a G,
+    vertex_filter: FV,
+    edge_filter: FE,
+}
+
+impl<'a, G, FV, FE> FilteredGraph<'a, G, FV, FE>
+where
+    G: QueryableGraph,
+    FV: Fn(&VertexId) -> bool,
+    FE: Fn(&Edge) -> bool,
+{
+    pub fn new(lower_graph: &'a G, vertex_filter: FV, edge_filter: FE) -> Self {
+        Self {
+            lower_graph,
+            vertex_filter,
+            edge_filter,
+        }
+    }
+
+    fn is_visible_edge(&self, e: &Edge) -> bool {
+        (self.edge_filter)(e) && (self.vertex_filter)(&e.source) && (self.vertex_filter)(&e.sink)
+    }
+}
+
+impl<'a, G, FV, FE> DirectedOrNot for FilteredGraph<'a, G, FV, FE>
+where
+    G: DirectedOrNot,
+{
+    const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
+}
+
+impl<'a, G, FV, FE> QueryableGraph for FilteredGraph<'a, G, FV, FE>
+where
+    G: QueryableGraph,
+    FV: Fn(&VertexId) -> bool,
+    FE: Fn(&Edge) -> bool,
+{
+    fn vertex_size(&self) -> usize {
+        self.iter_vertices().count()
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        let it = self
+            .lower_graph
+            .iter_vertices()
+            .filter(|v| (self.vertex_filter)(v));
+        Box::new(it)
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        (self.vertex_filter)(v) && self.lower_graph.contains_vertex(v)
+    }
+
+    fn edge_size(&self) -> usize {
+        self.iter_edges().count()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        let it = self
+            .lower_graph
+            .iter_edges()
+            .filter(|e| self.is_visible_edge(e));
+        Box::new(it)
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        self.find_edge(e).is_some()
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        self.lower_graph
+            .find_edge(e)
+            .filter(|e| self.is_visible_edge(e))
+    }
+
+    fn edges_connecting(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + '_> {
+        if !(self.vertex_filter)(source) || !(self.vertex_filter)(sink) {
+            return Box::new(std::iter::empty());
+        }
+        let it = self
+            .lower_graph
+            .edges_connecting(source, sink)
+            .filter(|e| (self.edge_filter)(e));
+        Box::new(it)
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        if !(self.vertex_filter)(v) {
+            return Box::new(std::iter::empty());
+        }
+        let it = self
+            .lower_graph
+            .in_edges(v)
+            .filter(|e| self.is_visible_edge(e));
+        Box::new(it)
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        if !(self.vertex_filter)(v) {
+            return Box::new(std::iter::empty());
+        }
+        let it = self
+            .lower_graph
+            .out_edges(v)
+            .filter(|e| self.is_visible_edge(e));
+        Box::new(it)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::*;
+    use quickcheck_macros::quickcheck;
+    use std::collections::BTreeSet;
+
+    #[quickcheck]
+    fn filtered_graph(ops: Ops) {
+        let base: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let keep_vertex = |v: &VertexId| v.to_raw() % 3 != 0;
+        let keep_edge = |e: &Edge| e.id.to_raw() % 2 == 0;
+        let oracle = {
+            let mut g = SelectedSubgraph::new(&base.graph);
+            for v in base.graph.iter_vertices().filter(keep_vertex) {
+                g.disclose_vertex(v);
+            }
+            for e in base.graph.iter_edges() {
+                if keep_edge(&e) && keep_vertex(&e.source) && keep_vertex(&e.sink) {
+                    g.disclose_edge(e.id);
+                }
+            }
+            g
+        };
+        let trial = FilteredGraph::new(&base.graph, keep_vertex, keep_edge);
+
+        assert_eq!(oracle.vertex_size(), trial.vertex_size());
+        assert_eq!(
+            oracle.iter_vertices().collect::<BTreeSet<_>>(),
+            trial.iter_vertices().collect::<BTreeSet<_>>()
+        );
+        assert_eq!(oracle.edge_size(), trial.edge_size());
+        assert_eq!(
+            oracle.iter_edges().collect::<BTreeSet<_>>(),
+            trial.iter_edges().collect::<BTreeSet<_>>()
+        );
+        for v in base.graph.iter_vertices() {
+            assert_eq!(oracle.contains_vertex(&v), trial.contains_vertex(&v));
+            assert_eq!(
+                oracle.in_edges(&v).collect::<BTreeSet<_>>(),
+                trial.in_edges(&v).collect::<BTreeSet<_>>()
+            );
+            assert_eq!(
+                oracle.out_edges(&v).collect::<BTreeSet<_>>(),
+                trial.out_edges(&v).collect::<BTreeSet<_>>()
+            );
+            for u in base.graph.iter_vertices() {
+                assert_eq!(
+                    oracle.edges_connecting(&v, &u).collect::<BTreeSet<_>>(),
+                    trial.edges_connecting(&v, &u).collect::<BTreeSet<_>>()
+                );
+            }
+        }
+        for e in base.graph.iter_edges() {
+            assert_eq!(oracle.contains_edge(&e.id), trial.contains_edge(&e.id));
+            assert_eq!(oracle.find_edge(&e.id), trial.find_edge(&e.id));
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::graph::*;
+
+/// A trait for low-level growable graphs.
+pub trait GrowableGraph {
+    /// Generate a new and empty graph.
+    fn new() -> Self;
+    /// Generate a new and empty graph with room for at least `vertices` vertices and `edges` edges.
+    ///
+    /// Capacities are only hints.
+    /// By default, they are ignored and it is the same as `new`.
+    fn with_capacity(vertices: usize, edges: usize) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = (vertices, edges);
+        Self::new()
+    }
+    /// Add a new vertex into the graph.
+    fn add_vertex(&mut self) -> VertexId;
+    /// Add a new edge from `source` to `sink` for directed graphs or between them for undirected graphs.
+    fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId;
+    /// Same as [GrowableGraph::add_edge], but fails instead of corrupting the graph
+    /// when either endpoint is not in the graph.
+    #[cfg(feature = "std")]
+    fn try_add_edge(&mut self, source: VertexId, sink: VertexId) -> Result<EdgeId, GraphError>
+    where
+        Self: QueryableGraph + Sized,
+    {
+        for v in [source, sink] {
+            if !self.contains_vertex(&v) {
+                return Err(GraphError::VertexNotFound(v));
+            }
+        }
+        Ok(self.add_edge(source, sink))
+    }
+}
@@ -1,12 +1,14 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
 use bimap::BiHashMap;
 
 /// This wraps a graph and mappings of vertices and edges from another graph.
 #[derive(Clone)]
 pub struct MappedGraph<G> {
     pub graph: G,
-    pub vmap: BiHashMap<VertexId, VertexId>,
-    pub emap: BiHashMap<EdgeId, EdgeId>,
+    pub vmap: BiHashMap<VertexId, VertexId, RandomState, RandomState>,
+    pub emap: BiHashMap<EdgeId, EdgeId, RandomState, RandomState>,
 }
 
 impl<G> QueryableGraph for MappedGraph<G>
@@ -73,7 +75,7 @@ where
     ) -> Box<dyn Iterator<Item = Edge> + '_> {
         let src = *source;
         let snk = *sink;
-        match (self.vmap.get_by_right(source), self.vmap.get_by_left(sink)) {
+        match (self.vmap.get_by_right(source), self.vmap.get_by_right(sink)) {
             (Some(lsrc), Some(lsnk)) => {
                 let it = self.graph.edges_connecting(lsrc, lsnk).map(move |e| Edge {
                     id: *self.emap.get_by_left(&e.id).unwrap(),
@@ -198,8 +200,8 @@ where
     pub fn new() -> Self {
         Self {
             graph: G::new(),
-            vmap: BiHashMap::new(),
-            emap: BiHashMap::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            emap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
         }
     }
 
@@ -219,12 +221,11 @@ where
                     }
                 }
                 directed::Op::AddEdge((source, sink, eid)) => {
-                    match (self.vmap.get_by_right(source), self.vmap.get_by_right(sink)) {
-                        (Some(my_src), Some(my_sink)) => {
-                            let my_eid = self.graph.add_edge(*my_src, *my_sink);
-                            self.emap.insert(my_eid, *eid);
-                        }
-                        _ => {}
+                    if let (Some(my_src), Some(my_sink)) =
+                        (self.vmap.get_by_right(source), self.vmap.get_by_right(sink))
+                    {
+                        let my_eid = self.graph.add_edge(*my_src, *my_sink);
+                        self.emap.insert(my_eid, *eid);
                     }
                 }
                 directed::Op::RemoveEdge(eid) => {
@@ -246,8 +247,8 @@ where
         G2: GrowableGraph,
     {
         let mut res = G2::new();
-        let mut vmap = BiHashMap::new();
-        let mut emap = BiHashMap::new();
+        let mut vmap = BiHashMap::with_hashers(new_random_state(), new_random_state());
+        let mut emap = BiHashMap::with_hashers(new_random_state(), new_random_state());
 
         for v in self.graph.iter_vertices() {
             let new_v = res.add_vertex();
@@ -298,3 +299,35 @@ where
         res
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::{directed::*, *};
+    use quickcheck_macros::quickcheck;
+    use std::collections::BTreeSet;
+
+    #[quickcheck]
+    fn edges_connecting(ops: Ops) {
+        let mapped: MappedGraph<AdjacentListGraph> = (&ops).into();
+        let g = &mapped.graph;
+        for src in g.iter_vertices() {
+            for snk in g.iter_vertices() {
+                let oracle: BTreeSet<_> = g
+                    .edges_connecting(&src, &snk)
+                    .map(|e| Edge {
+                        id: *mapped.emap.get_by_left(&e.id).unwrap(),
+                        source: *mapped.vmap.get_by_left(&e.source).unwrap(),
+                        sink: *mapped.vmap.get_by_left(&e.sink).unwrap(),
+                    })
+                    .collect();
+                let trial: BTreeSet<_> = mapped
+                    .edges_connecting(
+                        mapped.vmap.get_by_left(&src).unwrap(),
+                        mapped.vmap.get_by_left(&snk).unwrap(),
+                    )
+                    .collect();
+                assert_eq!(oracle, trial);
+            }
+        }
+    }
+}
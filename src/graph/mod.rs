@@ -23,23 +23,109 @@
 //! Futhermore, these subgraphs are shrinkable.
 //! While they are shrinking, their underlying graphs are kept unchanged.
 //!
+//! ## `FilteredGraph`
+//!
+//! It lazily hides vertices and edges failing arbitrary predicates.
+//!
+//! ## `OverlayGraph`
+//!
+//! It reads through to a borrowed graph and keeps its own additions aside.
+//! Thus, unlike subgraphs, it is growable.
+//!
+//! ## `WeightedGraph`
+//!
+//! It weights edges of a borrowed graph by a map.
+//!
 //! ## `MappedGraph`
 //!
 //! It wraps a graph and how its vertices and edges are mapped from another graph.
+//!
+//! ## `ObservedGraph`
+//!
+//! It wraps a graph and notifies a [GraphObserver] of every mutation.
+//!
+//! ## `AttributeStore`
+//!
+//! It keeps attributes of vertices or edges aside from a plain graph, without tagging it.
 
 mod vertex;
 pub use self::vertex::*;
 mod edge;
 pub use self::edge::*;
+mod growable;
+pub use self::growable::*;
+#[cfg(feature = "std")]
 mod r#trait;
+#[cfg(feature = "std")]
 pub use self::r#trait::*;
+mod error;
+pub use self::error::*;
+#[cfg(feature = "std")]
 mod mapped_graph;
+#[cfg(feature = "std")]
 pub use self::mapped_graph::*;
+#[cfg(feature = "std")]
 mod shadowed_subgraph;
+#[cfg(feature = "std")]
 pub use self::shadowed_subgraph::*;
+#[cfg(feature = "std")]
 mod selected_subgraph;
+#[cfg(feature = "std")]
 pub use self::selected_subgraph::*;
+#[cfg(feature = "std")]
+mod filtered_graph;
+#[cfg(feature = "std")]
+pub use self::filtered_graph::*;
+#[cfg(feature = "std")]
+mod observed_graph;
+#[cfg(feature = "std")]
+pub use self::observed_graph::*;
+#[cfg(feature = "std")]
+mod overlay_graph;
+#[cfg(feature = "std")]
+pub use self::overlay_graph::*;
+#[cfg(feature = "std")]
+mod weighted_graph;
+#[cfg(feature = "std")]
+pub use self::weighted_graph::*;
+#[cfg(feature = "std")]
+mod csr;
+#[cfg(feature = "std")]
+pub use self::csr::*;
+#[cfg(feature = "std")]
+mod adjacency_matrix;
+#[cfg(feature = "std")]
+pub use self::adjacency_matrix::*;
+#[cfg(feature = "std")]
+mod diff;
+#[cfg(feature = "std")]
+pub use self::diff::*;
+#[cfg(feature = "std")]
+mod compact;
+#[cfg(feature = "std")]
+pub use self::compact::*;
+#[cfg(feature = "std")]
+mod coo;
+#[cfg(feature = "std")]
+pub use self::coo::*;
+#[cfg(feature = "std")]
+mod edge_list;
+#[cfg(feature = "std")]
+pub use self::edge_list::*;
+#[cfg(feature = "std")]
+mod petgraph_interop;
+#[cfg(feature = "std")]
+pub use self::petgraph_interop::*;
+#[cfg(feature = "std")]
+mod attribute_store;
+#[cfg(feature = "std")]
+pub use self::attribute_store::*;
+#[cfg(feature = "std")]
 mod graph_debug;
 
+#[cfg(all(test, feature = "std"))]
+mod checks;
+#[cfg(feature = "std")]
 pub mod directed;
+#[cfg(feature = "std")]
 pub mod undirected;
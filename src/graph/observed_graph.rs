@@ -0,0 +1,241 @@
+use crate::graph::*;
+
+/// Callbacks on mutations of an [ObservedGraph].
+///
+/// All callbacks do nothing by default,
+/// so that observers can implement only those they are interested in.
+pub trait GraphObserver {
+    /// Called after a vertex is added.
+    fn on_add_vertex(&mut self, v: VertexId) {
+        let _ = v;
+    }
+    /// Called after an edge is added.
+    fn on_add_edge(&mut self, e: Edge) {
+        let _ = e;
+    }
+    /// Called after an edge is removed.
+    ///
+    /// It is not called on removing an absent edge.
+    fn on_remove_edge(&mut self, e: &Edge) {
+        let _ = e;
+    }
+    /// Called after a vertex is removed.
+    ///
+    /// Edges connecting to the vertex are notified by `on_remove_edge` beforehand.
+    /// It is not called on removing an absent vertex.
+    fn on_remove_vertex(&mut self, v: &VertexId) {
+        let _ = v;
+    }
+}
+
+/// A graph wrapper which notifies an observer of every mutation.
+///
+/// This lets callers maintain external caches or derived indexes of the graph.
+pub struct ObservedGraph<G, O> {
+    pub graph: G,
+    pub observer: O,
+}
+
+impl<G, O> ObservedGraph<G, O> {
+    /// Wraps a graph with an observer.
+    ///
+    /// Existing vertices and edges in `graph` are not notified.
+    pub fn with_observer(graph: G, observer: O) -> Self {
+        Self { graph, observer }
+    }
+}
+
+impl<G, O> DirectedOrNot for ObservedGraph<G, O>
+where
+    G: DirectedOrNot,
+{
+    const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
+}
+
+impl<G, O> GrowableGraph for ObservedGraph<G, O>
+where
+    G: GrowableGraph,
+    O: GraphObserver + Default,
+{
+    fn new() -> Self {
+        Self::with_observer(G::new(), O::default())
+    }
+
+    fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self::with_observer(G::with_capacity(vertices, edges), O::default())
+    }
+
+    fn add_vertex(&mut self) -> VertexId {
+        let v = self.graph.add_vertex();
+        self.observer.on_add_vertex(v);
+        v
+    }
+
+    fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId {
+        let id = self.graph.add_edge(source, sink);
+        self.observer.on_add_edge(Edge { id, source, sink });
+        id
+    }
+}
+
+impl<G, O> EdgeShrinkableGraph for ObservedGraph<G, O>
+where
+    G: EdgeShrinkableGraph,
+    O: GraphObserver,
+{
+    fn remove_edge(&mut self, edge: &EdgeId) -> Option<Edge> {
+        let res = self.graph.remove_edge(edge);
+        if let Some(e) = &res {
+            self.observer.on_remove_edge(e);
+        }
+        res
+    }
+}
+
+impl<G, O> VertexShrinkableGraph for ObservedGraph<G, O>
+where
+    G: VertexShrinkableGraph + QueryableGraph,
+    O: GraphObserver,
+{
+    fn remove_vertex(&mut self, vertex: &VertexId) -> Box<dyn Iterator<Item = Edge> + 'static> {
+        if !self.graph.contains_vertex(vertex) {
+            return Box::new(std::iter::empty());
+        }
+        let edges: Vec<_> = self.graph.remove_vertex(vertex).collect();
+        for e in edges.iter() {
+            self.observer.on_remove_edge(e);
+        }
+        self.observer.on_remove_vertex(vertex);
+        Box::new(edges.into_iter())
+    }
+}
+
+impl<G, O> QueryableGraph for ObservedGraph<G, O>
+where
+    G: QueryableGraph,
+{
+    fn vertex_size(&self) -> usize {
+        self.graph.vertex_size()
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        self.graph.iter_vertices()
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        self.graph.contains_vertex(v)
+    }
+
+    fn edge_size(&self) -> usize {
+        self.graph.edge_size()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.graph.iter_edges()
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        self.graph.contains_edge(e)
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        self.graph.find_edge(e)
+    }
+
+    fn edges_connecting(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.graph.edges_connecting(source, sink)
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.graph.in_edges(v)
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.graph.out_edges(v)
+    }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.graph.in_degree(v)
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.graph.out_degree(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[derive(Debug, Default, PartialEq, Eq)]
+    struct Tally {
+        vertices: usize,
+        edges: usize,
+    }
+
+    impl GraphObserver for Tally {
+        fn on_add_vertex(&mut self, _: VertexId) {
+            self.vertices += 1;
+        }
+
+        fn on_add_edge(&mut self, _: Edge) {
+            self.edges += 1;
+        }
+
+        fn on_remove_edge(&mut self, _: &Edge) {
+            self.edges -= 1;
+        }
+
+        fn on_remove_vertex(&mut self, _: &VertexId) {
+            self.vertices -= 1;
+        }
+    }
+
+    #[test]
+    fn tally() {
+        let mut g = ObservedGraph::<directed::TreeBackedGraph, Tally>::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let e0 = g.add_edge(v0, v1);
+        g.add_edge(v1, v1);
+        g.add_edge(v1, v0);
+        assert_eq!(
+            g.observer,
+            Tally {
+                vertices: 2,
+                edges: 3
+            }
+        );
+        assert!(g.remove_edge(&e0).is_some());
+        assert!(g.remove_edge(&e0).is_none());
+        assert_eq!(
+            g.observer,
+            Tally {
+                vertices: 2,
+                edges: 2
+            }
+        );
+        assert_eq!(g.remove_vertex(&v1).count(), 2);
+        assert_eq!(g.remove_vertex(&v1).count(), 0);
+        assert_eq!(
+            g.observer,
+            Tally {
+                vertices: 1,
+                edges: 0
+            }
+        );
+    }
+
+    #[quickcheck]
+    fn tally_follows_sizes(ops: Ops) {
+        let g: MappedGraph<ObservedGraph<undirected::AdjacentListGraph, Tally>> = (&ops).into();
+        assert_eq!(g.graph.observer.vertices, g.graph.vertex_size());
+        assert_eq!(g.graph.observer.edges, g.graph.edge_size());
+    }
+}
@@ -0,0 +1,348 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use bimap::BiHashMap;
+
+/// A growable graph which reads through to a borrowed base graph
+/// and keeps its own additions in an owned local graph.
+///
+/// The base graph is never mutated.
+/// Vertices and edges of the base graph keep their ID's.
+/// Those added to the overlay get ID's above all ID's in the base graph,
+/// so that both never collide.
+/// New edges may connect base vertices as well as new vertices.
+///
+/// The local graph `L` must be of the same directedness as `G`,
+/// e.g., [undirected::TreeBackedGraph] for undirected base graphs,
+/// or it fails to compile.
+pub struct OverlayGraph<'a, G, L = directed::TreeBackedGraph> {
+    base: Option<&'a G>,
+    local: L,
+    /// from local vertices to vertices in the overlay,
+    /// including proxies of base vertices which new edges connect
+    vmap: BiHashMap<VertexId, VertexId, RandomState, RandomState>,
+    /// new vertices have ID's no less than it
+    vertex_offset: usize,
+    /// new edges have ID's no less than it
+    edge_offset: usize,
+}
+
+impl<'a, G, L> OverlayGraph<'a, G, L>
+where
+    G: DirectedOrNot,
+    L: DirectedOrNot,
+{
+    /// Fails to compile when `G` and `L` differ in directedness.
+    const SAME_DIRECTEDNESS: () = assert!(
+        G::DIRECTED_OR_NOT == L::DIRECTED_OR_NOT,
+        "base and local graphs differ in directedness"
+    );
+}
+
+impl<'a, G, L> OverlayGraph<'a, G, L>
+where
+    G: QueryableGraph + DirectedOrNot,
+    L: GrowableGraph + QueryableGraph + DirectedOrNot,
+{
+    /// Overlays a graph.
+    pub fn with_base(base: &'a G) -> Self {
+        let () = Self::SAME_DIRECTEDNESS;
+        let vertex_offset = base.iter_vertices().map(|v| v.0 + 1).max().unwrap_or(0);
+        let edge_offset = base.iter_edges().map(|e| e.id.0 + 1).max().unwrap_or(0);
+        Self {
+            base: Some(base),
+            local: L::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            vertex_offset,
+            edge_offset,
+        }
+    }
+}
+
+impl<'a, G, L> OverlayGraph<'a, G, L> {
+    fn is_new_vertex(&self, v: &VertexId) -> bool {
+        v.0 >= self.vertex_offset
+    }
+
+    fn is_new_edge(&self, e: &EdgeId) -> bool {
+        e.0 >= self.edge_offset
+    }
+
+    fn to_outer_edge(&self, e: Edge) -> Edge {
+        Edge {
+            id: EdgeId(e.id.0 + self.edge_offset),
+            source: *self.vmap.get_by_left(&e.source).unwrap(),
+            sink: *self.vmap.get_by_left(&e.sink).unwrap(),
+        }
+    }
+}
+
+impl<'a, G, L> DirectedOrNot for OverlayGraph<'a, G, L>
+where
+    G: DirectedOrNot,
+{
+    const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
+}
+
+impl<'a, G, L> GrowableGraph for OverlayGraph<'a, G, L>
+where
+    G: QueryableGraph + DirectedOrNot,
+    L: GrowableGraph + QueryableGraph + DirectedOrNot,
+{
+    /// An overlay without any base graph.
+    fn new() -> Self {
+        let () = Self::SAME_DIRECTEDNESS;
+        Self {
+            base: None,
+            local: L::new(),
+            vmap: BiHashMap::with_hashers(new_random_state(), new_random_state()),
+            vertex_offset: 0,
+            edge_offset: 0,
+        }
+    }
+
+    fn add_vertex(&mut self) -> VertexId {
+        let local = self.local.add_vertex();
+        let outer = VertexId(local.0 + self.vertex_offset);
+        self.vmap.insert(local, outer);
+        outer
+    }
+
+    fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId {
+        debug_assert!(self.contains_vertex(&source));
+        debug_assert!(self.contains_vertex(&sink));
+        let mut local_of = |outer: VertexId| match self.vmap.get_by_right(&outer) {
+            Some(local) => *local,
+            None => {
+                let proxy = self.local.add_vertex();
+                self.vmap.insert(proxy, outer);
+                proxy
+            }
+        };
+        let local_src = local_of(source);
+        let local_snk = local_of(sink);
+        let local = self.local.add_edge(local_src, local_snk);
+        EdgeId(local.0 + self.edge_offset)
+    }
+}
+
+impl<'a, G, L> QueryableGraph for OverlayGraph<'a, G, L>
+where
+    G: QueryableGraph,
+    L: QueryableGraph,
+{
+    fn vertex_size(&self) -> usize {
+        let base = self.base.map_or(0, |g| g.vertex_size());
+        let new = self
+            .vmap
+            .right_values()
+            .filter(|v| self.is_new_vertex(v))
+            .count();
+        base + new
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        let base = self.base.into_iter().flat_map(|g| g.iter_vertices());
+        let new = self
+            .vmap
+            .right_values()
+            .copied()
+            .filter(|v| self.is_new_vertex(v));
+        Box::new(base.chain(new))
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        if self.is_new_vertex(v) {
+            self.vmap.contains_right(v)
+        } else {
+            self.base.map_or(false, |g| g.contains_vertex(v))
+        }
+    }
+
+    fn edge_size(&self) -> usize {
+        self.base.map_or(0, |g| g.edge_size()) + self.local.edge_size()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        let base = self.base.into_iter().flat_map(|g| g.iter_edges());
+        let new = self.local.iter_edges().map(|e| self.to_outer_edge(e));
+        Box::new(base.chain(new))
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        if self.is_new_edge(e) {
+            self.local.contains_edge(&EdgeId(e.0 - self.edge_offset))
+        } else {
+            self.base.map_or(false, |g| g.contains_edge(e))
+        }
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        if self.is_new_edge(e) {
+            self.local
+                .find_edge(&EdgeId(e.0 - self.edge_offset))
+                .map(|e| self.to_outer_edge(e))
+        } else {
+            self.base.and_then(|g| g.find_edge(e))
+        }
+    }
+
+    fn edges_connecting(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + '_> {
+        let base: Box<dyn Iterator<Item = Edge>> = match self.base {
+            Some(g) if !self.is_new_vertex(source) && !self.is_new_vertex(sink) => {
+                g.edges_connecting(source, sink)
+            }
+            _ => Box::new(std::iter::empty()),
+        };
+        let new: Box<dyn Iterator<Item = Edge>> =
+            match (self.vmap.get_by_right(source), self.vmap.get_by_right(sink)) {
+                (Some(src), Some(snk)) => Box::new(
+                    self.local
+                        .edges_connecting(src, snk)
+                        .map(|e| self.to_outer_edge(e)),
+                ),
+                _ => Box::new(std::iter::empty()),
+            };
+        Box::new(base.chain(new))
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        let base: Box<dyn Iterator<Item = Edge>> = match self.base {
+            Some(g) if !self.is_new_vertex(v) => g.in_edges(v),
+            _ => Box::new(std::iter::empty()),
+        };
+        let new: Box<dyn Iterator<Item = Edge>> = match self.vmap.get_by_right(v) {
+            Some(local) => Box::new(self.local.in_edges(local).map(|e| self.to_outer_edge(e))),
+            None => Box::new(std::iter::empty()),
+        };
+        Box::new(base.chain(new))
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        let base: Box<dyn Iterator<Item = Edge>> = match self.base {
+            Some(g) if !self.is_new_vertex(v) => g.out_edges(v),
+            _ => Box::new(std::iter::empty()),
+        };
+        let new: Box<dyn Iterator<Item = Edge>> = match self.vmap.get_by_right(v) {
+            Some(local) => Box::new(self.local.out_edges(local).map(|e| self.to_outer_edge(e))),
+            None => Box::new(std::iter::empty()),
+        };
+        Box::new(base.chain(new))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn base_and_new_edges() {
+        let mut base = directed::TreeBackedGraph::new();
+        let v0 = base.add_vertex();
+        let v1 = base.add_vertex();
+        let e0 = base.add_edge(v0, v1);
+
+        let mut g = OverlayGraph::<_>::with_base(&base);
+        let v2 = g.add_vertex();
+        assert!(v2 != v0 && v2 != v1);
+        let e1 = g.add_edge(v1, v2);
+        let e2 = g.add_edge(v1, v0);
+        assert!(e1 != e0 && e2 != e0 && e1 != e2);
+
+        assert_eq!(g.vertex_size(), 3);
+        assert_eq!(g.edge_size(), 3);
+        let mut vs: Vec<_> = g.iter_vertices().collect();
+        vs.sort();
+        assert_eq!(vs, vec![v0, v1, v2]);
+        let mut outs: Vec<_> = g.out_edges(&v1).collect();
+        outs.sort();
+        assert_eq!(
+            outs,
+            vec![
+                Edge {
+                    id: e1,
+                    source: v1,
+                    sink: v2
+                },
+                Edge {
+                    id: e2,
+                    source: v1,
+                    sink: v0
+                },
+            ]
+        );
+        let ins: Vec<_> = g.in_edges(&v1).map(|e| e.id).collect();
+        assert_eq!(ins, vec![e0]);
+        let ins: Vec<_> = g.in_edges(&v0).map(|e| e.id).collect();
+        assert_eq!(ins, vec![e2]);
+        assert!(g.contains_edge(&e0));
+        assert_eq!(g.find_edge(&e1).unwrap().sink, v2);
+        assert_eq!(g.edges_connecting(&v1, &v0).count(), 1);
+
+        assert_eq!(base.vertex_size(), 2);
+        assert_eq!(base.edge_size(), 1);
+    }
+
+    #[test]
+    fn undirected_base() {
+        let mut base = undirected::TreeBackedGraph::new();
+        let v0 = base.add_vertex();
+        let v1 = base.add_vertex();
+        let e0 = base.add_edge(v0, v1);
+
+        let mut g = OverlayGraph::<_, undirected::TreeBackedGraph>::with_base(&base);
+        let v2 = g.add_vertex();
+        let e1 = g.add_edge(v1, v2);
+        for v in [v0, v1, v2] {
+            let mut ins: Vec<_> = g.in_edges(&v).map(|e| e.id).collect();
+            ins.sort();
+            let mut outs: Vec<_> = g.out_edges(&v).map(|e| e.id).collect();
+            outs.sort();
+            assert_eq!(ins, outs);
+            assert!(g.out_edges(&v).all(|e| e.source == v));
+        }
+        assert_eq!(
+            g.in_edges(&v1).map(|e| e.id).collect::<Vec<_>>(),
+            vec![e0, e1]
+        );
+        assert_eq!(g.out_edges(&v2).map(|e| e.id).collect::<Vec<_>>(), vec![e1]);
+        assert_eq!(g.edges_connecting(&v2, &v1).count(), 1);
+    }
+
+    #[quickcheck]
+    fn same_as_copy(ops: Ops) {
+        let base: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let mut copy = base.graph.clone();
+        let mut g = OverlayGraph::<_, undirected::TreeBackedGraph>::with_base(&base.graph);
+        let mut vs: Vec<_> = base.graph.iter_vertices().collect();
+        vs.sort();
+        for pair in vs.windows(2) {
+            let e = copy.add_edge(pair[0], pair[1]);
+            let e_trial = g.add_edge(pair[0], pair[1]);
+            assert_eq!(g.find_edge(&e_trial).unwrap().source, pair[0]);
+            assert_eq!(copy.find_edge(&e).unwrap().sink, pair[1]);
+        }
+        assert_eq!(g.vertex_size(), copy.vertex_size());
+        assert_eq!(g.edge_size(), copy.edge_size());
+        for v in vs.iter() {
+            assert_eq!(g.out_degree(v), copy.out_degree(v));
+            assert_eq!(g.in_degree(v), copy.in_degree(v));
+            for w in vs.iter() {
+                assert_eq!(
+                    g.edges_connecting(v, w).count(),
+                    copy.edges_connecting(v, w).count()
+                );
+            }
+        }
+        assert_eq!(
+            base.graph.edge_size() + vs.len().saturating_sub(1),
+            g.edge_size()
+        );
+    }
+}
@@ -0,0 +1,147 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use bimap::BiHashMap;
+use petgraph::{
+    graph::{EdgeIndex, NodeIndex},
+    stable_graph::StableDiGraph,
+    visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers},
+};
+
+/// A trait with default implementation for exporting a graph to petgraph.
+pub trait ToPetgraph: QueryableGraph {
+    /// Exports the graph as a `StableDiGraph` of petgraph.
+    ///
+    /// Indices are kept, i.e.,
+    /// `VertexId(i)` becomes `NodeIndex::new(i)` and `EdgeId(k)` becomes `EdgeIndex::new(k)`.
+    /// To do so, it fills holes of ID's with placeholders and removes them afterwards.
+    /// Thus, it takes time and space linear to the largest ID's, rather than numbers of vertices and edges.
+    ///
+    /// Undirected edges are exported from their sources to their sinks.
+    ///
+    /// # Panics
+    ///
+    /// It panics if any ID does not fit in `u32`, the default index type of petgraph.
+    fn to_petgraph(&self) -> StableDiGraph<(), ()> {
+        let vertex_bound = self.iter_vertices().map(|v| v.0 + 1).max().unwrap_or(0);
+        let mut edges: Vec<_> = self.iter_edges().collect();
+        edges.sort();
+        let edge_bound = edges.last().map(|e| e.id.0 + 1).unwrap_or(0);
+        let mut res = StableDiGraph::with_capacity(vertex_bound, edge_bound);
+        for _ in 0..vertex_bound {
+            res.add_node(());
+        }
+        let mut placeholders = vec![];
+        let mut edges = edges.into_iter().peekable();
+        for k in 0..edge_bound {
+            let (a, b) = match edges.peek() {
+                Some(e) if e.id.0 == k => {
+                    let e = edges.next().unwrap();
+                    (e.source.0, e.sink.0)
+                }
+                Some(e) => {
+                    placeholders.push(k);
+                    (e.source.0, e.source.0)
+                }
+                None => unreachable!(),
+            };
+            let eidx = res.add_edge(NodeIndex::new(a), NodeIndex::new(b), ());
+            debug_assert_eq!(eidx.index(), k);
+        }
+        for k in placeholders {
+            res.remove_edge(EdgeIndex::new(k));
+        }
+        for i in 0..vertex_bound {
+            if !self.contains_vertex(&VertexId(i)) {
+                res.remove_node(NodeIndex::new(i));
+            }
+        }
+        res
+    }
+}
+
+impl<G: QueryableGraph> ToPetgraph for G {}
+
+/// Imports a `StableDiGraph` of petgraph.
+///
+/// Weights are dropped.
+/// In the returned [MappedGraph], a vertex is mapped from `VertexId(i)` for `NodeIndex::new(i)`,
+/// and an edge from `EdgeId(k)` for `EdgeIndex::new(k)`.
+pub fn from_petgraph<N, E>(g: &StableDiGraph<N, E>) -> MappedGraph<directed::TreeBackedGraph> {
+    let mut graph = directed::TreeBackedGraph::with_capacity(g.node_count(), g.edge_count());
+    let mut vmap = BiHashMap::with_capacity_and_hashers(
+        g.node_count(),
+        new_random_state(),
+        new_random_state(),
+    );
+    let mut emap = BiHashMap::with_capacity_and_hashers(
+        g.edge_count(),
+        new_random_state(),
+        new_random_state(),
+    );
+    for n in g.node_identifiers() {
+        let v = graph.add_vertex();
+        vmap.insert(v, VertexId(n.index()));
+    }
+    for e in g.edge_references() {
+        let src = vmap.get_by_right(&VertexId(e.source().index())).unwrap();
+        let snk = vmap.get_by_right(&VertexId(e.target().index())).unwrap();
+        let new_e = graph.add_edge(*src, *snk);
+        emap.insert(new_e, EdgeId(e.id().index()));
+    }
+    MappedGraph { graph, vmap, emap }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[quickcheck]
+    fn round_trip(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        let pg = g.graph.to_petgraph();
+        assert_eq!(pg.node_count(), g.graph.vertex_size());
+        assert_eq!(pg.edge_count(), g.graph.edge_size());
+        for e in g.graph.iter_edges() {
+            let (a, b) = pg.edge_endpoints(EdgeIndex::new(e.id.0)).unwrap();
+            assert_eq!((a.index(), b.index()), (e.source.0, e.sink.0));
+        }
+        let back = from_petgraph(&pg);
+        let mut expected: Vec<_> = g.graph.iter_edges().collect();
+        expected.sort();
+        let mut trial: Vec<_> = back.iter_edges().collect();
+        trial.sort();
+        assert_eq!(trial, expected);
+        let mut expected: Vec<_> = g.graph.iter_vertices().collect();
+        expected.sort();
+        let mut trial: Vec<_> = back.iter_vertices().collect();
+        trial.sort();
+        assert_eq!(trial, expected);
+    }
+
+    #[test]
+    fn from_petgraph_with_holes() {
+        let mut pg = StableDiGraph::<&str, f64>::new();
+        let a = pg.add_node("a");
+        let b = pg.add_node("b");
+        let c = pg.add_node("c");
+        let ab = pg.add_edge(a, b, 1.0);
+        let bc = pg.add_edge(b, c, 2.0);
+        let ca = pg.add_edge(c, a, 3.0);
+        pg.remove_node(b);
+        let g = from_petgraph(&pg);
+        assert_eq!(g.vertex_size(), 2);
+        assert!(!g.contains_vertex(&VertexId(b.index())));
+        assert!(!g.contains_edge(&EdgeId(ab.index())));
+        assert!(!g.contains_edge(&EdgeId(bc.index())));
+        assert_eq!(
+            g.find_edge(&EdgeId(ca.index())),
+            Some(Edge {
+                id: EdgeId(ca.index()),
+                source: VertexId(c.index()),
+                sink: VertexId(a.index()),
+            })
+        );
+    }
+}
@@ -1,36 +1,52 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
 use ahash::RandomState;
-use std::collections::HashSet;
+use std::collections::{BTreeMap, HashSet};
+use std::hash::BuildHasher;
 
 /// A subgraph with selected vertices and edges.
 ///
 /// Removing vertices and edges from a [SelectedSubgraph] just unselects them.
 /// Therefore, shrinking a [SelectedSubgraph] keeps the underlying graph unchanged.
-pub struct SelectedSubgraph<'a, G> {
+///
+/// Selected vertices and edges are kept in hash sets by `S`,
+/// which is [ahash::RandomState] by [SelectedSubgraph::new] and can be customized by
+/// [SelectedSubgraph::new_with_hasher] or [Subgraph::new].
+pub struct SelectedSubgraph<'a, G, S = RandomState> {
     lower_graph: &'a G,
-    selected_vertices: HashSet<VertexId, RandomState>,
-    selected_edges: HashSet<EdgeId, RandomState>,
+    selected_vertices: HashSet<VertexId, S>,
+    selected_edges: HashSet<EdgeId, S>,
 }
 
-impl<'a, G> DirectedOrNot for SelectedSubgraph<'a, G>
+impl<'a, G, S> DirectedOrNot for SelectedSubgraph<'a, G, S>
 where
     G: DirectedOrNot,
 {
     const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
 }
 
-impl<'a, G> Subgraph for SelectedSubgraph<'a, G>
+impl<'a, G> SelectedSubgraph<'a, G>
+where
+    G: QueryableGraph,
+{
+    /// Same as [Subgraph::new], but only for the default hasher,
+    /// which is seeded as the `deterministic` feature says,
+    /// and thus `S` needs no annotation.
+    pub fn new(lower_graph: &'a G) -> Self {
+        Self::new_with_hasher(lower_graph, new_random_state())
+    }
+}
+
+impl<'a, G, S> Subgraph for SelectedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher + Clone + Default,
 {
     type LowerGraph = &'a G;
 
+    /// Hash sets are by `S::default()`.
     fn new(lower_graph: Self::LowerGraph) -> Self {
-        Self {
-            lower_graph,
-            selected_vertices: HashSet::with_hasher(RandomState::new()),
-            selected_edges: HashSet::with_hasher(RandomState::new()),
-        }
+        Self::new_with_hasher(lower_graph, S::default())
     }
 
     fn disclose_vertex(&mut self, v: VertexId) -> &mut Self {
@@ -47,9 +63,28 @@ where
     }
 }
 
-impl<'a, G> QueryableGraph for SelectedSubgraph<'a, G>
+impl<'a, G, S> SelectedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
+{
+    /// Same as [Subgraph::new] but with hash sets by `hasher`.
+    pub fn new_with_hasher(lower_graph: &'a G, hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            lower_graph,
+            selected_vertices: HashSet::with_hasher(hasher.clone()),
+            selected_edges: HashSet::with_hasher(hasher),
+        }
+    }
+}
+
+impl<'a, G, S> QueryableGraph for SelectedSubgraph<'a, G, S>
+where
+    G: QueryableGraph,
+    S: BuildHasher,
 {
     fn vertex_size(&self) -> usize {
         self.selected_vertices.len()
@@ -116,9 +151,10 @@ where
     }
 }
 
-impl<'a, G> EdgeShrinkableGraph for SelectedSubgraph<'a, G>
+impl<'a, G, S> EdgeShrinkableGraph for SelectedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
 {
     fn remove_edge(&mut self, edge: &EdgeId) -> Option<crate::graph::Edge> {
         if self.selected_edges.remove(edge) {
@@ -129,31 +165,70 @@ where
     }
 }
 
-impl<'a, G> VertexShrinkableGraph for SelectedSubgraph<'a, G>
+impl<'a, G, S> VertexShrinkableGraph for SelectedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
 {
+    /// Unselects a vertex and all selected edges connected to it.
+    ///
+    /// Unselected edges are returned in ascending order of their ID's.
     fn remove_vertex(
         &mut self,
         vertex: &VertexId,
     ) -> Box<dyn Iterator<Item = crate::graph::Edge> + 'static> {
         if self.selected_vertices.remove(vertex) {
-            let edges: HashSet<Edge, RandomState> = self
+            let edges: BTreeMap<EdgeId, Edge> = self
                 .lower_graph
                 .in_edges(vertex)
                 .chain(self.lower_graph.out_edges(vertex))
                 .filter(|e| self.selected_edges.contains(&e.id))
+                .map(|e| (e.id, e))
                 .collect();
-            for e in edges.iter() {
-                self.selected_edges.remove(&e.id);
+            for e in edges.keys() {
+                self.selected_edges.remove(e);
             }
-            Box::new(edges.into_iter())
+            Box::new(edges.into_values())
         } else {
             Box::new(std::iter::empty())
         }
     }
 }
 
+impl<'a, G, S> SelectedSubgraph<'a, G, S>
+where
+    G: QueryableGraph,
+    S: BuildHasher,
+{
+    /// Verifies that selected vertices and edges are in the underlying graph,
+    /// and that endpoints of selected edges are selected, for debugging.
+    #[cfg(debug_assertions)]
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if let Some(v) = self
+            .selected_vertices
+            .iter()
+            .find(|v| !self.lower_graph.contains_vertex(v))
+        {
+            return Err(format!("selected {:?} is not in the underlying graph", v));
+        }
+        for e in self.selected_edges.iter() {
+            let edge = match self.lower_graph.find_edge(e) {
+                None => return Err(format!("selected {:?} is not in the underlying graph", e)),
+                Some(x) => x,
+            };
+            for v in [edge.source, edge.sink] {
+                if !self.selected_vertices.contains(&v) {
+                    return Err(format!(
+                        "endpoint {:?} of selected {:?} is unselected",
+                        v, e
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,9 +282,99 @@ mod tests {
                     }
                     _ => unreachable!(),
                 }
+                #[cfg(debug_assertions)]
+                trial.graph.check_invariants().unwrap();
             }
             trial
         };
         assert_eq!(oracle, trial);
     }
+
+    #[quickcheck]
+    fn new_with_hasher(ops: Ops) {
+        let base: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let hasher = std::collections::hash_map::RandomState::new();
+        let mut oracle = SelectedSubgraph::new(&base.graph);
+        let mut trial = SelectedSubgraph::new_with_hasher(&base.graph, hasher);
+        let mut by_default: SelectedSubgraph<_, std::collections::hash_map::RandomState> =
+            Subgraph::new(&base.graph);
+        for e in base.graph.iter_edges().step_by(2) {
+            oracle.disclose_edge(e.id);
+            trial.disclose_edge(e.id);
+            by_default.disclose_edge(e.id);
+        }
+        if let Some(v) = base.graph.iter_vertices().next() {
+            oracle.disclose_vertex(v);
+            trial.disclose_vertex(v);
+            by_default.disclose_vertex(v);
+            let removed: Vec<_> = oracle.remove_vertex(&v).collect();
+            assert!(removed.iter().cloned().eq(trial.remove_vertex(&v)));
+            assert!(removed.iter().cloned().eq(by_default.remove_vertex(&v)));
+        }
+        let mut oracle_edges: Vec<_> = oracle.iter_edges().collect();
+        oracle_edges.sort();
+        for mut trial_edges in [
+            trial.iter_edges().collect::<Vec<_>>(),
+            by_default.iter_edges().collect(),
+        ] {
+            trial_edges.sort();
+            assert_eq!(oracle_edges, trial_edges);
+        }
+        assert_eq!(oracle.vertex_size(), trial.vertex_size());
+        assert_eq!(oracle.vertex_size(), by_default.vertex_size());
+    }
+
+    #[test]
+    fn remove_vertex_in_order() {
+        let mut g = TreeBackedGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let mut es = vec![];
+        for i in 0..3 {
+            es.push(g.add_edge(vs[3], vs[i]));
+            es.push(g.add_edge(vs[i], vs[3]));
+        }
+        es.push(g.add_edge(vs[3], vs[3]));
+        g.add_edge(vs[0], vs[1]);
+        let mut sub = SelectedSubgraph::new(&g);
+        for v in vs.iter() {
+            sub.disclose_vertex(*v);
+        }
+        for e in g.iter_edges() {
+            sub.disclose_edge(e.id);
+        }
+        let removed: Vec<_> = sub.remove_vertex(&vs[3]).map(|e| e.id).collect();
+        assert_eq!(removed, es);
+        assert_eq!(sub.edge_size(), 1);
+
+        let mut ug = crate::graph::undirected::TreeBackedGraph::new();
+        let u0 = ug.add_vertex();
+        let u1 = ug.add_vertex();
+        let e0 = ug.add_edge(u1, u0);
+        let e1 = ug.add_edge(u0, u1);
+        let e2 = ug.add_edge(u0, u0);
+        let mut sub = SelectedSubgraph::new(&ug);
+        sub.disclose_vertex(u0).disclose_vertex(u1);
+        for e in [e0, e1, e2] {
+            sub.disclose_edge(e);
+        }
+        let removed: Vec<_> = sub.remove_vertex(&u0).map(|e| e.id).collect();
+        assert_eq!(removed, vec![e0, e1, e2]);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn corrupted_invariants() {
+        let mut base = TreeBackedGraph::new();
+        let v0 = base.add_vertex();
+        let v1 = base.add_vertex();
+        let e = base.add_edge(v0, v1);
+        let mut trial = SelectedSubgraph::new(&base);
+        trial.disclose_edge(e);
+        assert_eq!(trial.check_invariants(), Ok(()));
+        trial.selected_vertices.remove(&v1);
+        assert!(trial.check_invariants().is_err());
+        let mut trial = SelectedSubgraph::new(&base);
+        trial.disclose_vertex(VertexId(100));
+        assert!(trial.check_invariants().is_err());
+    }
 }
@@ -1,36 +1,54 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
 use ahash::RandomState;
 use std::collections::HashSet;
+use std::hash::BuildHasher;
 
 /// A subgraph by shadowing some of vertices and edges in the underlying graph.
 ///
 /// Removing vertices and edges from a [ShadowedSubgraph] just shadows them.
 /// Therefore, shrinking a [ShadowedSubgraph] keeps the underlying graph unchanged.
-pub struct ShadowedSubgraph<'a, G> {
+///
+/// Shadowed vertices and edges are kept in hash sets by `S`,
+/// which is [ahash::RandomState] by [ShadowedSubgraph::new] and can be customized by
+/// [ShadowedSubgraph::new_with_hasher] or [Subgraph::new].
+pub struct ShadowedSubgraph<'a, G, S = RandomState> {
     lower_graph: &'a G,
-    shadowed_vertices: HashSet<VertexId, RandomState>,
-    shadowed_edges: HashSet<EdgeId, RandomState>,
+    /// Always a subset of vertices in `lower_graph`.
+    shadowed_vertices: HashSet<VertexId, S>,
+    /// Always a subset of edges in `lower_graph`.
+    shadowed_edges: HashSet<EdgeId, S>,
 }
 
-impl<'a, G> DirectedOrNot for ShadowedSubgraph<'a, G>
+impl<'a, G, S> DirectedOrNot for ShadowedSubgraph<'a, G, S>
 where
     G: DirectedOrNot,
 {
     const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
 }
 
-impl<'a, G> Subgraph for ShadowedSubgraph<'a, G>
+impl<'a, G> ShadowedSubgraph<'a, G>
+where
+    G: QueryableGraph,
+{
+    /// Same as [Subgraph::new], but only for the default hasher,
+    /// which is seeded as the `deterministic` feature says,
+    /// and thus `S` needs no annotation.
+    pub fn new(lower_graph: &'a G) -> Self {
+        Self::new_with_hasher(lower_graph, new_random_state())
+    }
+}
+
+impl<'a, G, S> Subgraph for ShadowedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher + Clone + Default,
 {
     type LowerGraph = &'a G;
 
+    /// Hash sets are by `S::default()`.
     fn new(lower_graph: Self::LowerGraph) -> Self {
-        Self {
-            lower_graph,
-            shadowed_edges: HashSet::with_hasher(RandomState::new()),
-            shadowed_vertices: HashSet::with_hasher(RandomState::new()),
-        }
+        Self::new_with_hasher(lower_graph, S::default())
     }
 
     fn disclose_edge(&mut self, e: EdgeId) -> &mut Self {
@@ -47,9 +65,28 @@ where
     }
 }
 
-impl<'a, G> EdgeShrinkableGraph for ShadowedSubgraph<'a, G>
+impl<'a, G, S> ShadowedSubgraph<'a, G, S>
+where
+    G: QueryableGraph,
+    S: BuildHasher,
+{
+    /// Same as [Subgraph::new] but with hash sets by `hasher`.
+    pub fn new_with_hasher(lower_graph: &'a G, hasher: S) -> Self
+    where
+        S: Clone,
+    {
+        Self {
+            lower_graph,
+            shadowed_edges: HashSet::with_hasher(hasher.clone()),
+            shadowed_vertices: HashSet::with_hasher(hasher),
+        }
+    }
+}
+
+impl<'a, G, S> EdgeShrinkableGraph for ShadowedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
 {
     fn remove_edge(&mut self, edge: &EdgeId) -> Option<Edge> {
         if self.shadowed_edges.contains(edge) {
@@ -64,9 +101,10 @@ where
     }
 }
 
-impl<'a, G> VertexShrinkableGraph for ShadowedSubgraph<'a, G>
+impl<'a, G, S> VertexShrinkableGraph for ShadowedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
 {
     fn remove_vertex(&mut self, vertex: &VertexId) -> Box<dyn Iterator<Item = Edge> + 'static> {
         if self.shadowed_vertices.contains(vertex) {
@@ -90,11 +128,13 @@ where
     }
 }
 
-impl<'a, G> QueryableGraph for ShadowedSubgraph<'a, G>
+impl<'a, G, S> QueryableGraph for ShadowedSubgraph<'a, G, S>
 where
     G: QueryableGraph,
+    S: BuildHasher,
 {
     fn vertex_size(&self) -> usize {
+        debug_assert!(self.shadowed_vertices.len() <= self.lower_graph.vertex_size());
         self.lower_graph.vertex_size() - self.shadowed_vertices.len()
     }
 
@@ -129,6 +169,7 @@ where
     }
 
     fn edge_size(&self) -> usize {
+        debug_assert!(self.shadowed_edges.len() <= self.lower_graph.edge_size());
         self.lower_graph.edge_size() - self.shadowed_edges.len()
     }
 
@@ -172,6 +213,58 @@ where
             .filter(|e| !self.shadowed_edges.contains(&e.id));
         Box::new(it)
     }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        if self.shadowed_vertices.contains(v) {
+            return 0;
+        }
+        if self.shadowed_edges.is_empty() {
+            return self.lower_graph.in_degree(v);
+        }
+        self.lower_graph
+            .in_edges(v)
+            .filter(|e| !self.shadowed_edges.contains(&e.id))
+            .count()
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        if self.shadowed_vertices.contains(v) {
+            return 0;
+        }
+        if self.shadowed_edges.is_empty() {
+            return self.lower_graph.out_degree(v);
+        }
+        self.lower_graph
+            .out_edges(v)
+            .filter(|e| !self.shadowed_edges.contains(&e.id))
+            .count()
+    }
+}
+
+impl<'a, G, S> ShadowedSubgraph<'a, G, S>
+where
+    G: QueryableGraph,
+    S: BuildHasher,
+{
+    /// Verifies that shadowed vertices and edges are in the underlying graph, for debugging.
+    #[cfg(debug_assertions)]
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if let Some(v) = self
+            .shadowed_vertices
+            .iter()
+            .find(|v| !self.lower_graph.contains_vertex(v))
+        {
+            return Err(format!("shadowed {:?} is not in the underlying graph", v));
+        }
+        if let Some(e) = self
+            .shadowed_edges
+            .iter()
+            .find(|e| !self.lower_graph.contains_edge(e))
+        {
+            return Err(format!("shadowed {:?} is not in the underlying graph", e));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -218,9 +311,102 @@ mod tests {
                     }
                     _ => unreachable!(),
                 }
+                #[cfg(debug_assertions)]
+                trial.graph.check_invariants().unwrap();
             }
             trial
         };
         assert_eq!(oracle, trial);
     }
+
+    #[quickcheck]
+    fn remove_twice(ops: Ops) {
+        let base: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let mut trial = ShadowedSubgraph::new(&base.graph);
+        let mut removed_edges = 0;
+        for e in base.graph.iter_edges() {
+            assert!(trial.remove_edge(&e.id).is_some());
+            assert!(trial.remove_edge(&e.id).is_none());
+            removed_edges += 1;
+            assert_eq!(trial.edge_size(), base.graph.edge_size() - removed_edges);
+        }
+        let mut removed_vertices = 0;
+        for v in base.graph.iter_vertices() {
+            let _ = trial.remove_vertex(&v);
+            assert_eq!(trial.remove_vertex(&v).count(), 0);
+            removed_vertices += 1;
+            assert_eq!(
+                trial.vertex_size(),
+                base.graph.vertex_size() - removed_vertices
+            );
+            assert_eq!(trial.edge_size(), 0);
+        }
+    }
+
+    #[quickcheck]
+    fn new_with_hasher(ops: Ops) {
+        let base: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let hasher = std::collections::hash_map::RandomState::new();
+        let mut oracle = ShadowedSubgraph::new(&base.graph);
+        let mut trial = ShadowedSubgraph::new_with_hasher(&base.graph, hasher);
+        let mut by_default: ShadowedSubgraph<_, std::collections::hash_map::RandomState> =
+            Subgraph::new(&base.graph);
+        for e in base.graph.iter_edges().step_by(2) {
+            oracle.remove_edge(&e.id);
+            trial.remove_edge(&e.id);
+            by_default.remove_edge(&e.id);
+        }
+        if let Some(v) = base.graph.iter_vertices().next() {
+            let removed: Vec<_> = oracle.remove_vertex(&v).collect();
+            assert!(removed.iter().cloned().eq(trial.remove_vertex(&v)));
+            assert!(removed.iter().cloned().eq(by_default.remove_vertex(&v)));
+            oracle.disclose_vertex(v);
+            trial.disclose_vertex(v);
+            by_default.disclose_vertex(v);
+        }
+        assert!(oracle.iter_vertices().eq(trial.iter_vertices()));
+        assert!(oracle.iter_edges().eq(trial.iter_edges()));
+        assert!(oracle.iter_vertices().eq(by_default.iter_vertices()));
+        assert!(oracle.iter_edges().eq(by_default.iter_edges()));
+    }
+
+    #[quickcheck]
+    fn degrees(ops: Ops) {
+        let base: MappedGraph<TreeBackedGraph> = (&ops).into();
+        let mut trial = ShadowedSubgraph::new(&base.graph);
+        let check = |trial: &ShadowedSubgraph<TreeBackedGraph>| {
+            for v in base.graph.iter_vertices() {
+                assert_eq!(trial.in_degree(&v), trial.in_edges(&v).count());
+                assert_eq!(trial.out_degree(&v), trial.out_edges(&v).count());
+            }
+        };
+        check(&trial);
+        let edges: Vec<_> = base.graph.iter_edges().step_by(2).collect();
+        for e in edges {
+            trial.remove_edge(&e.id);
+        }
+        check(&trial);
+        if let Some(v) = base.graph.iter_vertices().next() {
+            let _ = trial.remove_vertex(&v);
+        }
+        check(&trial);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn corrupted_invariants() {
+        let mut base = TreeBackedGraph::new();
+        let v0 = base.add_vertex();
+        let v1 = base.add_vertex();
+        let e = base.add_edge(v0, v1);
+        let mut trial = ShadowedSubgraph::new(&base);
+        trial.remove_edge(&e);
+        let _ = trial.remove_vertex(&v0);
+        assert_eq!(trial.check_invariants(), Ok(()));
+        trial.shadowed_vertices.insert(VertexId(100));
+        assert!(trial.check_invariants().is_err());
+        let mut trial = ShadowedSubgraph::new(&base);
+        trial.shadowed_edges.insert(EdgeId(100));
+        assert!(trial.check_invariants().is_err());
+    }
 }
@@ -1,14 +1,6 @@
 use crate::graph::*;
-
-/// A trait for low-level growable graphs.
-pub trait GrowableGraph {
-    /// Generate a new and empty graph.
-    fn new() -> Self;
-    /// Add a new vertex into the graph.
-    fn add_vertex(&mut self) -> VertexId;
-    /// Add a new edge from `source` to `sink` for directed graphs or between them for undirected graphs.
-    fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId;
-}
+use crate::random_state::new_random_state;
+use std::collections::{HashSet, VecDeque};
 
 /// A trait for low-level graphs whose edges can be removed.
 pub trait EdgeShrinkableGraph {
@@ -17,6 +9,21 @@ pub trait EdgeShrinkableGraph {
     /// If the edge ID is not in the graph, `None` is returned;
     /// otherwise, it returns complete information about the edge.
     fn remove_edge(&mut self, edge: &EdgeId) -> Option<Edge>;
+
+    /// Removes all edges from `source` to `sink` for directed graphs,
+    /// or those between them in either orientation for undirected graphs.
+    ///
+    /// It returns removed edges in ascending order of their ID's.
+    /// By default, it collects `edges_connecting` and then removes them one by one.
+    fn remove_edges_between(&mut self, source: &VertexId, sink: &VertexId) -> Vec<Edge>
+    where
+        Self: QueryableGraph + Sized,
+    {
+        let mut eids: Vec<_> = self.edges_connecting(source, sink).map(|e| e.id).collect();
+        eids.sort();
+        eids.dedup();
+        eids.iter().filter_map(|e| self.remove_edge(e)).collect()
+    }
 }
 
 /// A trait for low-level graphs whose vertices can be removed.
@@ -31,6 +38,67 @@ pub trait VertexShrinkableGraph: EdgeShrinkableGraph {
     ///   It is implementation-specific.
     /// * If the vertex is not in the graph, it returns an empty iterator.
     fn remove_vertex(&mut self, vertex: &VertexId) -> Box<dyn Iterator<Item = Edge> + 'static>;
+
+    /// Removes all isolated vertices, i.e., those of degree 0.
+    ///
+    /// It returns the number of removed vertices.
+    fn prune_isolated(&mut self) -> usize
+    where
+        Self: QueryableGraph + Sized,
+    {
+        let isolated: Vec<_> = self.isolated_vertices().collect();
+        for v in isolated.iter() {
+            self.remove_vertex(v).for_each(drop);
+        }
+        isolated.len()
+    }
+
+    /// Contracts `verts` into a single vertex, the one of the smallest ID among them.
+    ///
+    /// Edges between `verts`, including self-loops, are dropped.
+    /// Other edges of the contracted vertices are rewired to the surviving vertex,
+    /// keeping their directions and multiplicities.
+    /// Rewired edges are removed and re-added, so they get new ID's;
+    /// edges of the surviving vertex to outside keep theirs.
+    ///
+    /// It returns the surviving vertex,
+    /// or `None` if none of `verts` are in the graph.
+    fn contract_vertices<I>(&mut self, verts: I) -> Option<VertexId>
+    where
+        I: IntoIterator<Item = VertexId>,
+        Self: GrowableGraph + QueryableGraph + Sized,
+    {
+        let mut verts: Vec<_> = verts
+            .into_iter()
+            .filter(|v| self.contains_vertex(v))
+            .collect();
+        verts.sort();
+        verts.dedup();
+        let survivor = *verts.first()?;
+        let mut members = HashSet::with_hasher(new_random_state());
+        members.extend(verts.iter().copied());
+        let rename = |v: VertexId| if members.contains(&v) { survivor } else { v };
+        let mut rewired = vec![];
+        for v in verts[1..].iter() {
+            for e in self.remove_vertex(v) {
+                if !(members.contains(&e.source) && members.contains(&e.sink)) {
+                    rewired.push(e);
+                }
+            }
+        }
+        rewired.sort();
+        for e in rewired {
+            self.add_edge(rename(e.source), rename(e.sink));
+        }
+        let self_loops: Vec<_> = self
+            .edges_connecting(&survivor, &survivor)
+            .map(|e| e.id)
+            .collect();
+        for e in self_loops {
+            self.remove_edge(&e);
+        }
+        Some(survivor)
+    }
 }
 
 /// A trait for querying vertices and edges about low-level graphs.
@@ -65,6 +133,229 @@ pub trait QueryableGraph {
     ///
     /// For undirected graphs, the sources of returned edges must be `v`.
     fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_>;
+    /// Iteration over all edges incident to `v`, each exactly once.
+    ///
+    /// * For directed graphs, they are out-edges followed by in-edges,
+    ///   where self-loops occur only among out-edges.
+    /// * For undirected graphs, they are `out_edges`, i.e., the sources of returned edges are `v`.
+    ///
+    /// In both cases, a self-loop occurs once.
+    fn incident_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_>
+    where
+        Self: DirectedOrNot + Sized,
+    {
+        if Self::DIRECTED_OR_NOT {
+            let it = self
+                .out_edges(v)
+                .chain(self.in_edges(v).filter(|e| e.source != e.sink));
+            Box::new(it)
+        } else {
+            self.out_edges(v)
+        }
+    }
+
+    /// Calls `f` on every edge going out of `v`, in the order of `out_edges`.
+    ///
+    /// By default, it walks `out_edges`.
+    /// Backends may override it to avoid boxing, which pays off in bulk, e.g., exporting in CSR.
+    fn for_each_out_edge<F: FnMut(&Edge)>(&self, v: &VertexId, mut f: F)
+    where
+        Self: Sized,
+    {
+        for e in self.out_edges(v) {
+            f(&e);
+        }
+    }
+
+    /// Number of edges going into the vertex `v`.
+    ///
+    /// By default, it counts `in_edges`.
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.in_edges(v).count()
+    }
+    /// Number of edges going out of the vertex `v`.
+    ///
+    /// By default, it counts `out_edges`.
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.out_edges(v).count()
+    }
+    /// Number of edges from `source` to `sink` in directed graphs
+    /// or those between them in undirected graphs.
+    ///
+    /// By default, it counts `edges_connecting`.
+    fn edge_multiplicity(&self, source: &VertexId, sink: &VertexId) -> usize {
+        self.edges_connecting(source, sink).count()
+    }
+
+    /// Iteration over all vertices, each of which is along with sinks of its out-edges.
+    ///
+    /// Sinks are in the order of `out_edges`.
+    /// Thus, a vertex occurs as many times as there are parallel edges.
+    fn adjacency(&self) -> Box<dyn Iterator<Item = (VertexId, Vec<VertexId>)> + '_> {
+        let it = self
+            .iter_vertices()
+            .map(|v| (v, self.out_edges(&v).map(|e| e.sink).collect()));
+        Box::new(it)
+    }
+
+    /// Iteration over all vertices, each of which is along with sources of its in-edges.
+    ///
+    /// Sources are in the order of `in_edges`.
+    /// Thus, a vertex occurs as many times as there are parallel edges.
+    fn adjacency_in(&self) -> Box<dyn Iterator<Item = (VertexId, Vec<VertexId>)> + '_> {
+        let it = self
+            .iter_vertices()
+            .map(|v| (v, self.in_edges(&v).map(|e| e.source).collect()));
+        Box::new(it)
+    }
+
+    /// Vertices which both `a` and `b` have out-edges to, in ascending order without duplicates.
+    ///
+    /// For undirected graphs, they are common neighbors.
+    /// By default, it sorts sinks of out-edges of both and intersects them.
+    fn common_successors(&self, a: &VertexId, b: &VertexId) -> Vec<VertexId> {
+        let mut xs: Vec<_> = self.out_edges(a).map(|e| e.sink).collect();
+        let mut ys: Vec<_> = self.out_edges(b).map(|e| e.sink).collect();
+        xs.sort();
+        ys.sort();
+        intersect_sorted(xs.into_iter(), ys.into_iter())
+    }
+
+    /// Vertices which have out-edges to both `a` and `b`, in ascending order without duplicates.
+    ///
+    /// For undirected graphs, they are common neighbors.
+    /// By default, it sorts sources of in-edges of both and intersects them.
+    fn common_predecessors(&self, a: &VertexId, b: &VertexId) -> Vec<VertexId> {
+        let mut xs: Vec<_> = self.in_edges(a).map(|e| e.source).collect();
+        let mut ys: Vec<_> = self.in_edges(b).map(|e| e.source).collect();
+        xs.sort();
+        ys.sort();
+        intersect_sorted(xs.into_iter(), ys.into_iter())
+    }
+
+    /// Iteration over vertices without in-edges.
+    ///
+    /// They are entry points of DAG's, e.g., for topological sorting.
+    /// For undirected graphs, they are isolated vertices, i.e., those of degree 0.
+    fn sources(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        let it = self.iter_vertices().filter(|v| self.in_degree(v) == 0);
+        Box::new(it)
+    }
+
+    /// Iteration over vertices without out-edges.
+    ///
+    /// For undirected graphs, they are isolated vertices, i.e., those of degree 0.
+    fn sinks(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        let it = self.iter_vertices().filter(|v| self.out_degree(v) == 0);
+        Box::new(it)
+    }
+
+    /// Iteration over isolated vertices, i.e., those without in-edges or out-edges.
+    fn isolated_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        let it = self
+            .iter_vertices()
+            .filter(|v| self.in_degree(v) == 0 && self.out_degree(v) == 0);
+        Box::new(it)
+    }
+
+    /// Whether adding an edge from `source` to `sink` would close a cycle.
+    ///
+    /// It checks, by BFS along out-edges, whether `source` is reachable from `sink`.
+    /// The graph is untouched.
+    /// For undirected graphs, it tells whether both are already connected.
+    /// A self-loop always closes a cycle.
+    fn would_create_cycle(&self, source: &VertexId, sink: &VertexId) -> bool {
+        if source == sink {
+            return true;
+        }
+        let mut visited = HashSet::with_hasher(new_random_state());
+        let mut queue = VecDeque::new();
+        visited.insert(*sink);
+        queue.push_back(*sink);
+        while let Some(v) = queue.pop_front() {
+            for e in self.out_edges(&v) {
+                if e.sink == *source {
+                    return true;
+                }
+                if visited.insert(e.sink) {
+                    queue.push_back(e.sink);
+                }
+            }
+        }
+        false
+    }
+
+    /// Number of edges on the shortest path along out-edges from `source` to `sink`.
+    ///
+    /// It is `Some(0)` when both are the same vertex in the graph,
+    /// and `None` when `sink` is unreachable.
+    /// It runs a breadth-first search which stops as soon as `sink` is dequeued.
+    fn distance(&self, source: &VertexId, sink: &VertexId) -> Option<usize> {
+        if !self.contains_vertex(source) {
+            return None;
+        }
+        let mut visited = HashSet::with_hasher(new_random_state());
+        let mut queue = VecDeque::new();
+        visited.insert(*source);
+        queue.push_back((*source, 0));
+        while let Some((v, d)) = queue.pop_front() {
+            if v == *sink {
+                return Some(d);
+            }
+            for e in self.out_edges(&v) {
+                if visited.insert(e.sink) {
+                    queue.push_back((e.sink, d + 1));
+                }
+            }
+        }
+        None
+    }
+
+    /// Whether `a` and `b` are in the same connected component.
+    ///
+    /// Directions of edges are ignored, i.e., it means weakly connected for directed graphs.
+    /// It is false if either is not in the graph.
+    /// It runs a bidirectional breadth-first search,
+    /// which always expands the smaller frontier and stops as soon as both searches meet.
+    fn in_same_component(&self, a: &VertexId, b: &VertexId) -> bool {
+        if !self.contains_vertex(a) || !self.contains_vertex(b) {
+            return false;
+        }
+        if a == b {
+            return true;
+        }
+        let mut visited = [
+            HashSet::with_hasher(new_random_state()),
+            HashSet::with_hasher(new_random_state()),
+        ];
+        visited[0].insert(*a);
+        visited[1].insert(*b);
+        let mut frontiers = [vec![*a], vec![*b]];
+        while !frontiers[0].is_empty() && !frontiers[1].is_empty() {
+            let side = if frontiers[0].len() <= frontiers[1].len() {
+                0
+            } else {
+                1
+            };
+            let mut next = vec![];
+            for v in frontiers[side].iter() {
+                let neighbors = self
+                    .out_edges(v)
+                    .map(|e| e.sink)
+                    .chain(self.in_edges(v).map(|e| e.source));
+                for u in neighbors {
+                    if visited[1 - side].contains(&u) {
+                        return true;
+                    }
+                    if visited[side].insert(u) {
+                        next.push(u);
+                    }
+                }
+            }
+            frontiers[side] = next;
+        }
+        false
+    }
 
     /// Returns something can inspect into the graph.
     fn debug(&self) -> Box<dyn std::fmt::Debug + '_>
@@ -75,6 +366,35 @@ pub trait QueryableGraph {
     }
 }
 
+/// Intersection of two ascending sequences, without duplicates.
+pub(crate) fn intersect_sorted<I, J>(xs: I, ys: J) -> Vec<VertexId>
+where
+    I: Iterator<Item = VertexId>,
+    J: Iterator<Item = VertexId>,
+{
+    let mut res: Vec<VertexId> = vec![];
+    let mut xs = xs.peekable();
+    let mut ys = ys.peekable();
+    while let (Some(x), Some(y)) = (xs.peek(), ys.peek()) {
+        match x.cmp(y) {
+            std::cmp::Ordering::Less => {
+                xs.next();
+            }
+            std::cmp::Ordering::Greater => {
+                ys.next();
+            }
+            std::cmp::Ordering::Equal => {
+                if res.last() != Some(x) {
+                    res.push(*x);
+                }
+                xs.next();
+                ys.next();
+            }
+        }
+    }
+    res
+}
+
 /// Whether a graph is directed or not.
 pub trait DirectedOrNot {
     /// When the graph is directed, it is true; otherwise, it is false.
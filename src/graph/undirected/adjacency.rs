@@ -0,0 +1,295 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// An undirected graph by hashed adjacent lists, with $O(1)$ degrees.
+///
+/// |                    | Complexity                                                                                       |
+/// | ------------------ | ------------------------------------------------------------------------------------------------ |
+/// | `add_vertex`       | amortized $O(1)$                                                                                 |
+/// | `add_edge`         | amortized $O(1)$                                                                                 |
+/// | `remove_edge`      | $O(d)$, where $d$ is the sum of degrees of both endpoints.                                       |
+/// | `remove_vertex`    | $O(\sum d)$, where the sum is over degrees of the vertex to remove and its neighbors.            |
+/// | `vertex_size`      | $O(1)$                                                                                           |
+/// | `iter_vertices`    | $O(1)$ per call to `.next()`.                                                                    |
+/// | `contains_vertex`  | $O(1)$                                                                                           |
+/// | `edge_size`        | $O(1)$                                                                                           |
+/// | `iter_edges`       | $O(1)$ per call to `.next()`.                                                                    |
+/// | `contains_edge`    | $O(1)$                                                                                           |
+/// | `find_edge`        | $O(1)$                                                                                           |
+/// | `edges_connecting` | returns in $O(1)$. $O(d)$ in total, where $d$ is the degree of `source`.                         |
+/// | `in_edges`         | returns in $O(1)$. $O(1)$ on each call to `.next`.                                               |
+/// | `out_edges`        | returns in $O(1)$. $O(1)$ on each call to `.next`.                                               |
+/// | `in_degree`        | $O(1)$                                                                                           |
+/// | `out_degree`       | $O(1)$                                                                                           |
+///
+/// In exchange, vertices and edges are iterated in no particular order,
+/// unlike [TreeBackedGraph] which iterates in ascending order of ID's.
+#[derive(Clone)]
+pub struct AdjacencyGraph {
+    vid_factory: VertexIdFactory,
+    eid_factory: EdgeIdFactory,
+    /// neighbors of vertices, where self-loops appear once
+    adjacency: HashMap<VertexId, Vec<(VertexId, EdgeId)>, RandomState>,
+    edges: HashMap<EdgeId, (VertexId, VertexId), RandomState>,
+}
+
+impl DirectedOrNot for AdjacencyGraph {
+    const DIRECTED_OR_NOT: bool = false;
+}
+
+impl std::fmt::Debug for AdjacencyGraph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "AdjacencyGraph {{")?;
+        let mut vs: Vec<_> = self.adjacency.keys().collect();
+        vs.sort();
+        for v in vs {
+            writeln!(f, "{:?}:", v)?;
+            for e in self.out_edges(v) {
+                writeln!(f, "  -> {:?} by {:?}", e.sink, e.id)?;
+            }
+        }
+        writeln!(f, "}}")?;
+        Ok(())
+    }
+}
+
+impl GrowableGraph for AdjacencyGraph {
+    fn new() -> Self {
+        Self::with_capacity(0, 0)
+    }
+
+    fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            vid_factory: VertexIdFactory::new(),
+            eid_factory: EdgeIdFactory::new(),
+            adjacency: HashMap::with_capacity_and_hasher(vertices, new_random_state()),
+            edges: HashMap::with_capacity_and_hasher(edges, new_random_state()),
+        }
+    }
+
+    fn add_vertex(&mut self) -> VertexId {
+        let vid = self.vid_factory.one_more();
+        self.adjacency.insert(vid, vec![]);
+        vid
+    }
+
+    fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId {
+        debug_assert!(self.adjacency.contains_key(&source));
+        debug_assert!(self.adjacency.contains_key(&sink));
+        let eid = self.eid_factory.one_more();
+        self.edges.insert(eid, (source, sink));
+        if let Some(neighbors) = self.adjacency.get_mut(&source) {
+            neighbors.push((sink, eid));
+        }
+        if source != sink {
+            if let Some(neighbors) = self.adjacency.get_mut(&sink) {
+                neighbors.push((source, eid));
+            }
+        }
+        eid
+    }
+}
+
+impl EdgeShrinkableGraph for AdjacencyGraph {
+    fn remove_edge(&mut self, edge: &EdgeId) -> Option<Edge> {
+        let (src, snk) = self.edges.remove(edge)?;
+        for v in [src, snk] {
+            if let Some(neighbors) = self.adjacency.get_mut(&v) {
+                if let Some(idx) = neighbors.iter().position(|(_, e)| e == edge) {
+                    neighbors.swap_remove(idx);
+                }
+            }
+        }
+        Some(Edge {
+            id: *edge,
+            source: src,
+            sink: snk,
+        })
+    }
+}
+
+impl VertexShrinkableGraph for AdjacencyGraph {
+    fn remove_vertex(&mut self, vertex: &VertexId) -> Box<dyn Iterator<Item = Edge> + 'static> {
+        let neighbors = match self.adjacency.remove(vertex) {
+            None => return Box::new(std::iter::empty()),
+            Some(x) => x,
+        };
+        let mut res: Vec<_> = neighbors
+            .into_iter()
+            .map(|(_, e)| self.remove_edge(&e).unwrap())
+            .collect();
+        res.sort();
+        Box::new(res.into_iter())
+    }
+}
+
+impl AdjacencyGraph {
+    /// Same as [QueryableGraph::iter_vertices] but without boxing the iterator.
+    pub fn vertices_iter(&self) -> impl Iterator<Item = VertexId> + '_ {
+        self.adjacency.keys().copied()
+    }
+
+    /// Same as [QueryableGraph::iter_edges] but without boxing the iterator.
+    pub fn edges_iter(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.edges.iter().map(|(e, (src, snk))| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::in_edges] but without boxing the iterator.
+    pub fn in_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        let sink = *v;
+        self.neighbors(v).iter().map(move |(src, e)| Edge {
+            id: *e,
+            source: *src,
+            sink,
+        })
+    }
+
+    /// Same as [QueryableGraph::out_edges] but without boxing the iterator.
+    pub fn out_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        let source = *v;
+        self.neighbors(v).iter().map(move |(snk, e)| Edge {
+            id: *e,
+            source,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::edges_connecting] but without boxing the iterator.
+    pub fn edges_connecting_iter(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> impl Iterator<Item = Edge> + '_ {
+        let sink = *sink;
+        self.out_edges_iter(source).filter(move |e| e.sink == sink)
+    }
+
+    fn neighbors(&self, v: &VertexId) -> &[(VertexId, EdgeId)] {
+        self.adjacency.get(v).map(|x| x.as_slice()).unwrap_or(&[])
+    }
+}
+
+/// Iteration over vertices in no particular order, without boxing.
+///
+/// Thus, `for v in &graph` is the same as `for v in graph.vertices_iter()`.
+impl<'a> IntoIterator for &'a AdjacencyGraph {
+    type Item = VertexId;
+    type IntoIter =
+        std::iter::Copied<std::collections::hash_map::Keys<'a, VertexId, Vec<(VertexId, EdgeId)>>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.adjacency.keys().copied()
+    }
+}
+
+impl QueryableGraph for AdjacencyGraph {
+    fn vertex_size(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        Box::new(self.vertices_iter())
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        self.adjacency.contains_key(v)
+    }
+
+    fn edge_size(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.edges_iter())
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        self.edges.contains_key(e)
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        self.edges.get(e).map(|(src, snk)| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.in_edges_iter(v))
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        Box::new(self.out_edges_iter(v))
+    }
+
+    fn for_each_out_edge<F: FnMut(&Edge)>(&self, v: &VertexId, mut f: F) {
+        for e in self.out_edges_iter(v) {
+            f(&e);
+        }
+    }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.neighbors(v).len()
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.in_degree(v)
+    }
+
+    fn edges_connecting<'a, 'b>(
+        &'a self,
+        source: &'b VertexId,
+        sink: &'b VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + 'a> {
+        Box::new(self.edges_connecting_iter(source, sink))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::graph::*;
+    use quickcheck_macros::*;
+    use std::collections::BTreeSet;
+
+    #[quickcheck]
+    fn adjacency_gen(ops: directed::Ops) {
+        let dig: MappedGraph<directed::AdjacentListGraph> = (&ops).into();
+        let oracle: MappedGraph<undirected::AdjacentListGraph> = dig.transform();
+        let trial: MappedGraph<undirected::AdjacencyGraph> = dig.transform();
+        assert_eq!(oracle, trial);
+    }
+
+    #[quickcheck]
+    fn in_and_out_edges(ops: directed::Ops) {
+        let oracle: MappedGraph<undirected::AdjacentListGraph> = (&ops).into();
+        let trial: MappedGraph<undirected::AdjacencyGraph> = (&ops).into();
+        assert_eq!(oracle, trial);
+        for v in oracle.iter_vertices() {
+            let oracle_ins: BTreeSet<_> = oracle.in_edges(&v).collect();
+            let trial_ins: BTreeSet<_> = trial.in_edges(&v).collect();
+            assert_eq!(oracle_ins, trial_ins);
+            assert!(trial_ins.iter().all(|e| e.sink == v));
+            let oracle_outs: BTreeSet<_> = oracle.out_edges(&v).collect();
+            let trial_outs: BTreeSet<_> = trial.out_edges(&v).collect();
+            assert_eq!(oracle_outs, trial_outs);
+            assert!(trial_outs.iter().all(|e| e.source == v));
+        }
+    }
+
+    #[quickcheck]
+    fn degrees(ops: directed::Ops) {
+        let trial: MappedGraph<undirected::AdjacencyGraph> = (&ops).into();
+        let g = &trial.graph;
+        for v in g.iter_vertices() {
+            assert_eq!(g.in_degree(&v), g.in_edges(&v).count());
+            assert_eq!(g.out_degree(&v), g.out_edges(&v).count());
+        }
+    }
+}
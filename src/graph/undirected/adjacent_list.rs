@@ -4,15 +4,15 @@ use petgraph::{
     stable_graph::StableUnGraph,
     visit::EdgeRef,
 };
-use std::collections::BTreeSet;
+use std::collections::{btree_map, BTreeMap, BTreeSet};
 
 /// An undirected graph implemented by adjacent lists.
 ///
 /// |                    | Complexity                                                                                             |
 /// | ------------------ | ------------------------------------------------------------------------------------------------------ |
-/// | `add_vertex`       | $O(1)$                                                                                                 |
-/// | `add_edge`         | $O(1)$                                                                                                 |
-/// | `remove_edge`      | $O(\|E'\|)$, where $E'$ is the set of edges sharing the same endpoints of edge to remove.              |
+/// | `add_vertex`       | $O(\log \|V\|)$                                                                                        |
+/// | `add_edge`         | $O(\log \|E\|)$                                                                                        |
+/// | `remove_edge`      | $O(\|E'\| + \log \|E\|)$, where $E'$ is the set of edges sharing endpoints of edge to remove.         |
 /// | `remove_vertex`    | $O(\|E'\|)$ calls to `remove_edge`, where $E'$ is the set of edges connecting to the vertex to remove. |
 /// | `vertex_size`      | $O(1)$                                                                                                 |
 /// | `iter_vertices`    | $O(1)$ per call to `.next()`.                                                                          |
@@ -24,8 +24,19 @@ use std::collections::BTreeSet;
 /// | `edges_connecting` | returns in $O(1)$. $O(1)$ on each call to `.next`.                                                     |
 /// | `in_edges`         | returns in $O(1)$. $O(1)$ on each call to `.next`.                                                     |
 /// | `out_edges`        | returns in $O(1)$. $O(1)$ on each call to `.next`.                                                     |
+///
+/// Vertices and edges are iterated in the order they are added, even across removals.
+/// Their ID's are indices into the underlying [StableGraph](petgraph::stable_graph::StableGraph),
+/// so ID's vacated by removals may be reused by later additions,
+/// but those additions are still iterated after everything added before them.
 #[derive(Clone)]
-pub struct AdjacentListGraph(StableUnGraph<(), (VertexId, VertexId), usize>);
+pub struct AdjacentListGraph {
+    /// Weights of vertices and edges are their keys in `vertices` and `edges`.
+    graph: StableUnGraph<usize, (VertexId, VertexId, usize), usize>,
+    vertices: BTreeMap<usize, VertexId>,
+    edges: BTreeMap<usize, EdgeId>,
+    next_order: usize,
+}
 
 impl DirectedOrNot for AdjacentListGraph {
     const DIRECTED_OR_NOT: bool = false;
@@ -33,26 +44,52 @@ impl DirectedOrNot for AdjacentListGraph {
 
 impl GrowableGraph for AdjacentListGraph {
     fn new() -> Self {
-        Self(StableUnGraph::<(), (VertexId, VertexId), usize>::with_capacity(0, 0))
+        Self::with_capacity(0, 0)
+    }
+
+    fn with_capacity(vertices: usize, edges: usize) -> Self {
+        Self {
+            graph: StableUnGraph::with_capacity(vertices, edges),
+            vertices: BTreeMap::new(),
+            edges: BTreeMap::new(),
+            next_order: 0,
+        }
     }
 
     fn add_vertex(&mut self) -> VertexId {
-        let vid = self.0.add_node(());
-        VertexId::new(vid.index())
+        let order = self.next_order;
+        self.next_order += 1;
+        let vid = VertexId::new(self.graph.add_node(order).index());
+        self.vertices.insert(order, vid);
+        vid
     }
 
+    /// # Panics
+    ///
+    /// It panics if either endpoint is not in the graph, e.g., it has been removed.
+    /// Use [GrowableGraph::try_add_edge] for an error instead.
     fn add_edge(&mut self, source: VertexId, sink: VertexId) -> EdgeId {
         let a = NodeIndex::new(source.to_raw());
         let b = NodeIndex::new(sink.to_raw());
-        let eid = self.0.add_edge(a, b, (source, sink));
-        EdgeId::new(eid.index())
+        assert!(
+            self.graph.contains_node(a) && self.graph.contains_node(b),
+            "cannot add an edge from {:?} to {:?}: endpoint not in the graph",
+            source,
+            sink
+        );
+        let order = self.next_order;
+        self.next_order += 1;
+        let eid = EdgeId::new(self.graph.add_edge(a, b, (source, sink, order)).index());
+        self.edges.insert(order, eid);
+        eid
     }
 }
 
 impl EdgeShrinkableGraph for AdjacentListGraph {
     fn remove_edge(&mut self, edge: &EdgeId) -> Option<Edge> {
         let pg_eidx = EdgeIndex::new(edge.to_raw());
-        if let Some((src, sink)) = self.0.remove_edge(pg_eidx) {
+        if let Some((src, sink, order)) = self.graph.remove_edge(pg_eidx) {
+            self.edges.remove(&order);
             Some(Edge {
                 id: *edge,
                 source: src,
@@ -67,12 +104,16 @@ impl EdgeShrinkableGraph for AdjacentListGraph {
 impl VertexShrinkableGraph for AdjacentListGraph {
     fn remove_vertex(&mut self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + 'static> {
         let a = NodeIndex::new(v.to_raw());
+        if !self.graph.contains_node(a) {
+            return Box::new(std::iter::empty());
+        }
         let res: BTreeSet<Edge> = self
-            .0
+            .graph
             .edges(a)
             .map(|e| {
-                let (src, sink) = e.weight();
+                let (src, sink, order) = e.weight();
                 let eid = EdgeId::new(e.id().index());
+                self.edges.remove(order);
                 Edge {
                     id: eid,
                     source: *src,
@@ -80,36 +121,48 @@ impl VertexShrinkableGraph for AdjacentListGraph {
                 }
             })
             .collect();
-        self.0.remove_node(a);
+        if let Some(order) = self.graph.remove_node(a) {
+            self.vertices.remove(&order);
+        }
         Box::new(res.into_iter())
     }
 }
 
+/// Iteration over vertices without boxing.
+///
+/// Thus, `for v in &graph` is the same as `for v in graph.iter_vertices()`.
+impl<'a> IntoIterator for &'a AdjacentListGraph {
+    type Item = VertexId;
+    type IntoIter = std::iter::Copied<btree_map::Values<'a, usize, VertexId>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.values().copied()
+    }
+}
+
 impl QueryableGraph for AdjacentListGraph {
     fn vertex_size(&self) -> usize {
-        self.0.node_count()
+        self.graph.node_count()
     }
 
     fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
-        let it = self.0.node_indices().map(|x| VertexId::new(x.index()));
-        Box::new(it)
+        Box::new(self.vertices.values().copied())
     }
 
     fn contains_vertex(&self, v: &VertexId) -> bool {
         let nidx = NodeIndex::new(v.to_raw());
-        self.0.contains_node(nidx)
+        self.graph.contains_node(nidx)
     }
 
     fn edge_size(&self) -> usize {
-        self.0.edge_count()
+        self.graph.edge_count()
     }
 
     fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let it = self.0.edge_indices().map(|x| {
-            let id = EdgeId::new(x.index());
-            let (source, sink) = self.0.edge_weight(x).unwrap();
+        let it = self.edges.values().map(|id| {
+            let (source, sink, _) = self.graph.edge_weight(EdgeIndex::new(id.to_raw())).unwrap();
             Edge {
-                id,
+                id: *id,
                 source: *source,
                 sink: *sink,
             }
@@ -119,12 +172,12 @@ impl QueryableGraph for AdjacentListGraph {
 
     fn contains_edge(&self, e: &EdgeId) -> bool {
         let eidx = EdgeIndex::new(e.to_raw());
-        self.0.edge_weight(eidx).is_some()
+        self.graph.edge_weight(eidx).is_some()
     }
 
     fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
         let eidx = EdgeIndex::new(e.to_raw());
-        self.0.edge_weight(eidx).map(|(src, sink)| Edge {
+        self.graph.edge_weight(eidx).map(|(src, sink, _)| Edge {
             id: *e,
             source: *src,
             sink: *sink,
@@ -132,21 +185,22 @@ impl QueryableGraph for AdjacentListGraph {
     }
 
     fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let nidx = NodeIndex::new(v.to_raw());
-        let it = self.0.edges(nidx).map(|x| {
-            let id = EdgeId::new(x.id().index());
-            let source = VertexId::new(x.source().index());
-            let sink = VertexId::new(x.target().index());
-            Edge { id, source, sink }
+        let it = self.out_edges(v).map(|e| Edge {
+            id: e.id,
+            source: e.sink,
+            sink: e.source,
         });
         Box::new(it)
     }
 
     fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let it = self.in_edges(v).map(|e| Edge {
-            id: e.id,
-            source: e.sink,
-            sink: e.source,
+        let nidx = NodeIndex::new(v.to_raw());
+        // for undirected graphs, petgraph yields edges whose sources are `nidx`.
+        let it = self.graph.edges(nidx).map(|x| {
+            let id = EdgeId::new(x.id().index());
+            let source = VertexId::new(x.source().index());
+            let sink = VertexId::new(x.target().index());
+            Edge { id, source, sink }
         });
         Box::new(it)
     }
@@ -158,7 +212,7 @@ impl QueryableGraph for AdjacentListGraph {
     ) -> Box<dyn Iterator<Item = Edge> + '_> {
         let src = NodeIndex::new(source.to_raw());
         let snk = NodeIndex::new(sink.to_raw());
-        let it = self.0.edges_connecting(src, snk).map(|x| {
+        let it = self.graph.edges_connecting(src, snk).map(|x| {
             let id = EdgeId::new(x.id().index());
             let source = VertexId::new(x.source().index());
             let sink = VertexId::new(x.target().index());
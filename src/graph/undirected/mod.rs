@@ -4,10 +4,247 @@ mod tree_backed;
 pub use self::tree_backed::*;
 mod adjacent_list;
 pub use self::adjacent_list::*;
+mod adjacency;
+pub use self::adjacency::*;
 
 #[cfg(test)]
 mod tests {
+    use crate::graph::checks;
+    use crate::graph::directed::Ops;
     use crate::graph::*;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn with_capacity() {
+        checks::with_capacity::<undirected::AdjacentListGraph>();
+        checks::with_capacity::<undirected::TreeBackedGraph>();
+        checks::with_capacity::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn edge_multiplicity() {
+        checks::edge_multiplicity::<undirected::AdjacentListGraph>();
+        checks::edge_multiplicity::<undirected::TreeBackedGraph>();
+        checks::edge_multiplicity::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn distance() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v1, v0);
+        g.add_edge(v2, v1);
+        assert_eq!(g.distance(&v1, &v1), Some(0));
+        assert_eq!(g.distance(&v0, &v1), Some(1));
+        assert_eq!(g.distance(&v0, &v2), Some(2));
+        assert_eq!(g.distance(&v2, &v0), Some(2));
+        assert_eq!(g.distance(&v0, &v3), None);
+    }
+
+    #[test]
+    fn sources_and_sinks() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        g.add_edge(v0, v1);
+        assert_eq!(g.sources().collect::<Vec<_>>(), vec![v2]);
+        assert_eq!(g.sinks().collect::<Vec<_>>(), vec![v2]);
+    }
+
+    #[test]
+    fn try_add_edge() {
+        checks::try_add_edge::<undirected::AdjacentListGraph>();
+        checks::try_add_edge::<undirected::TreeBackedGraph>();
+        checks::try_add_edge::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn adjacent_list_iteration_order() {
+        let mut g = undirected::AdjacentListGraph::new();
+        let vs: Vec<_> = (0..4).map(|_| g.add_vertex()).collect();
+        let es = [
+            g.add_edge(vs[3], vs[0]),
+            g.add_edge(vs[0], vs[2]),
+            g.add_edge(vs[2], vs[3]),
+            g.add_edge(vs[1], vs[3]),
+        ];
+        let _ = g.remove_vertex(&vs[1]);
+        g.remove_edge(&es[1]);
+        assert_eq!(
+            g.iter_vertices().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3]]
+        );
+        assert_eq!(
+            g.iter_edges().map(|e| e.id).collect::<Vec<_>>(),
+            vec![es[0], es[2]]
+        );
+        // ID's vacated above may be reused, but new ones still come last.
+        let v = g.add_vertex();
+        let e = g.add_edge(v, vs[0]);
+        assert_eq!(
+            g.iter_vertices().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3], v]
+        );
+        assert_eq!(
+            (&g).into_iter().collect::<Vec<_>>(),
+            vec![vs[0], vs[2], vs[3], v]
+        );
+        assert_eq!(
+            g.iter_edges().map(|e| e.id).collect::<Vec<_>>(),
+            vec![es[0], es[2], e]
+        );
+    }
+
+    #[test]
+    fn try_add_edge_to_removed_vertex() {
+        let mut g = undirected::AdjacentListGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let _ = g.remove_vertex(&v1);
+        assert_eq!(g.try_add_edge(v0, v1), Err(GraphError::VertexNotFound(v1)));
+        assert_eq!(g.edge_size(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "endpoint not in the graph")]
+    fn add_edge_to_removed_vertex() {
+        let mut g = undirected::AdjacentListGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let _ = g.remove_vertex(&v1);
+        g.add_edge(v0, v1);
+    }
+
+    #[test]
+    fn in_same_component() {
+        checks::in_same_component::<undirected::AdjacentListGraph>();
+        checks::in_same_component::<undirected::TreeBackedGraph>();
+        checks::in_same_component::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn common_successors_and_predecessors() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph,
+        {
+            let mut g = G::new();
+            let a = g.add_vertex();
+            let b = g.add_vertex();
+            let c = g.add_vertex();
+            let d = g.add_vertex();
+            g.add_edge(a, c);
+            g.add_edge(b, c);
+            g.add_edge(a, d);
+            g.add_edge(b, d);
+            g.add_edge(b, d);
+            g.add_edge(a, b);
+            assert_eq!(g.common_successors(&a, &b), vec![c, d]);
+            assert_eq!(g.common_successors(&b, &a), vec![c, d]);
+            assert_eq!(g.common_successors(&c, &d), vec![a, b]);
+            assert_eq!(g.common_predecessors(&c, &d), vec![a, b]);
+            assert_eq!(g.common_predecessors(&a, &c), vec![b]);
+            assert_eq!(g.common_successors(&a, &VertexId(100)), vec![]);
+        }
+        check::<undirected::AdjacentListGraph>();
+        check::<undirected::TreeBackedGraph>();
+        check::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn remove_edges_between() {
+        fn check<G>()
+        where
+            G: GrowableGraph + QueryableGraph + EdgeShrinkableGraph,
+        {
+            let mut g = G::new();
+            let v0 = g.add_vertex();
+            let v1 = g.add_vertex();
+            let e0 = g.add_edge(v0, v1);
+            let e1 = g.add_edge(v1, v0);
+            let e2 = g.add_edge(v0, v1);
+            let e3 = g.add_edge(v1, v1);
+            let trial = g.remove_edges_between(&v1, &v0);
+            assert_eq!(
+                trial.iter().map(|e| e.id).collect::<Vec<_>>(),
+                vec![e0, e1, e2]
+            );
+            assert_eq!(g.edge_size(), 1);
+            assert!(g.contains_edge(&e3));
+            assert_eq!(g.remove_edges_between(&v0, &v1), vec![]);
+        }
+        check::<undirected::AdjacentListGraph>();
+        check::<undirected::TreeBackedGraph>();
+        check::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn contract_vertices() {
+        checks::contract_vertices::<undirected::AdjacentListGraph>();
+        checks::contract_vertices::<undirected::TreeBackedGraph>();
+        checks::contract_vertices::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn prune_isolated() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let e = g.add_edge(v1, v0);
+        g.add_edge(v2, v2);
+        assert_eq!(g.prune_isolated(), 0);
+        g.remove_edge(&e);
+        assert_eq!(g.prune_isolated(), 2);
+        assert_eq!(g.iter_vertices().collect::<Vec<_>>(), vec![v2]);
+    }
+
+    #[test]
+    fn incident_edges() {
+        checks::incident_edges::<undirected::AdjacentListGraph>();
+        checks::incident_edges::<undirected::TreeBackedGraph>();
+        checks::incident_edges::<undirected::AdjacencyGraph>();
+    }
+
+    #[quickcheck]
+    fn double_removal(ops: Ops) {
+        checks::double_removal::<undirected::AdjacentListGraph>(&ops);
+        checks::double_removal::<undirected::TreeBackedGraph>(&ops);
+        checks::double_removal::<undirected::AdjacencyGraph>(&ops);
+    }
+
+    #[quickcheck]
+    fn for_each_out_edge(ops: Ops) {
+        checks::for_each_out_edge::<undirected::AdjacentListGraph>(&ops);
+        checks::for_each_out_edge::<undirected::TreeBackedGraph>(&ops);
+        checks::for_each_out_edge::<undirected::AdjacencyGraph>(&ops);
+    }
+
+    #[test]
+    fn into_iterator() {
+        checks::into_iterator::<undirected::AdjacentListGraph>();
+        checks::into_iterator::<undirected::TreeBackedGraph>();
+        checks::into_iterator::<undirected::AdjacencyGraph>();
+    }
+
+    #[test]
+    fn would_create_cycle() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let v2 = g.add_vertex();
+        let v3 = g.add_vertex();
+        g.add_edge(v0, v1);
+        g.add_edge(v2, v1);
+        assert!(g.would_create_cycle(&v0, &v2));
+        assert!(g.would_create_cycle(&v2, &v0));
+        assert!(!g.would_create_cycle(&v0, &v3));
+        assert!(!g.would_create_cycle(&v3, &v2));
+    }
 
     #[test]
     fn to_graphviz() {
@@ -19,13 +19,23 @@ use std::collections::{BTreeMap, BTreeSet};
 /// | `edges_connecting` | returns in $O(\log \|E\|)$. amortized $O(1)$ and $O(\log \|E\|)$ in the worst cases on each call to `.next`.|
 /// | `in_edges`         | returns in $O(\log \|E\|)$. amortized $O(1)$ and $O(\log \|E\|)$ in the worst cases on each call to `.next`.|
 /// | `out_edges`        | returns in $O(\log \|E\|)$. amortized $O(1)$ and $O(\log \|E\|)$ in the worst cases on each call to `.next`.|
+/// | `in_degree`        | $O(\log \|V\|)$                                                                              |
+/// | `out_degree`       | $O(\log \|V\|)$                                                                              |
+/// | `edge_multiplicity`| $O(\log \|E\| + k)$, where $k$ is the multiplicity.                                          |
+///
+/// Iterators from [QueryableGraph] are boxed trait objects, which is the default way to go.
+/// For performance-sensitive callers, there are also unboxed counterparts,
+/// e.g., [TreeBackedGraph::out_edges_iter] to [QueryableGraph::out_edges].
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TreeBackedGraph {
     vid_factory: VertexIdFactory,
     eid_factory: EdgeIdFactory,
     vertices: BTreeSet<VertexId>,
     edges: BTreeMap<EdgeId, (VertexId, VertexId)>,
     adjacent_edges: BTreeSet<(VertexId, VertexId, EdgeId)>,
+    /// degrees of vertices, where self-loops count once
+    degrees: BTreeMap<VertexId, usize>,
 }
 
 impl DirectedOrNot for TreeBackedGraph {
@@ -54,12 +64,14 @@ impl GrowableGraph for TreeBackedGraph {
             vertices: BTreeSet::new(),
             edges: BTreeMap::new(),
             adjacent_edges: BTreeSet::new(),
+            degrees: BTreeMap::new(),
         }
     }
 
     fn add_vertex(&mut self) -> VertexId {
         let vid = self.vid_factory.one_more();
         self.vertices.insert(vid);
+        self.degrees.insert(vid, 0);
         vid
     }
 
@@ -70,6 +82,14 @@ impl GrowableGraph for TreeBackedGraph {
         self.edges.insert(eid, (source, sink));
         self.adjacent_edges.insert((sink, source, eid));
         self.adjacent_edges.insert((source, sink, eid));
+        if let Some(degree) = self.degrees.get_mut(&source) {
+            *degree += 1;
+        }
+        if source != sink {
+            if let Some(degree) = self.degrees.get_mut(&sink) {
+                *degree += 1;
+            }
+        }
         eid
     }
 }
@@ -81,6 +101,14 @@ impl EdgeShrinkableGraph for TreeBackedGraph {
             Some((src, snk)) => {
                 self.adjacent_edges.remove(&(snk, src, *edge));
                 self.adjacent_edges.remove(&(src, snk, *edge));
+                if let Some(degree) = self.degrees.get_mut(&src) {
+                    *degree -= 1;
+                }
+                if src != snk {
+                    if let Some(degree) = self.degrees.get_mut(&snk) {
+                        *degree -= 1;
+                    }
+                }
                 Some(Edge {
                     id: *edge,
                     source: src,
@@ -110,17 +138,203 @@ impl VertexShrinkableGraph for TreeBackedGraph {
         for x in res.iter() {
             self.remove_edge(&x.id);
         }
+        self.degrees.remove(vertex);
         Box::new(res.into_iter())
     }
 }
 
+/// It fails unless the graph passes [TreeBackedGraph::check_invariants].
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for TreeBackedGraph {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        #[serde(rename = "TreeBackedGraph")]
+        struct Repr {
+            vid_factory: VertexIdFactory,
+            eid_factory: EdgeIdFactory,
+            vertices: BTreeSet<VertexId>,
+            edges: BTreeMap<EdgeId, (VertexId, VertexId)>,
+            adjacent_edges: BTreeSet<(VertexId, VertexId, EdgeId)>,
+            degrees: BTreeMap<VertexId, usize>,
+        }
+
+        let repr = Repr::deserialize(deserializer)?;
+        let res = Self {
+            vid_factory: repr.vid_factory,
+            eid_factory: repr.eid_factory,
+            vertices: repr.vertices,
+            edges: repr.edges,
+            adjacent_edges: repr.adjacent_edges,
+            degrees: repr.degrees,
+        };
+        res.check_invariants().map_err(serde::de::Error::custom)?;
+        Ok(res)
+    }
+}
+
+impl TreeBackedGraph {
+    /// Verifies that redundant indices agree with each other,
+    /// for debugging and for validating deserialized graphs.
+    ///
+    /// That is, endpoints of edges are vertices,
+    /// `adjacent_edges` mirrors `edges` in both orientations exactly,
+    /// cached degrees are those counted from edges,
+    /// and ID's to generate are not taken yet.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        if let Some(v) = self.vertices.iter().next_back() {
+            if self.vid_factory.clone().one_more() <= *v {
+                return Err(format!("{:?} may be generated again", v));
+            }
+        }
+        if let Some(e) = self.edges.keys().next_back() {
+            if self.eid_factory.clone().one_more() <= *e {
+                return Err(format!("{:?} may be generated again", e));
+            }
+        }
+        let mut degrees: BTreeMap<VertexId, usize> =
+            self.vertices.iter().map(|v| (*v, 0)).collect();
+        let mut adjacent_edges = 0;
+        for (e, (src, snk)) in self.edges.iter() {
+            for v in [src, snk] {
+                if !self.vertices.contains(v) {
+                    return Err(format!("endpoint {:?} of {:?} is not a vertex", v, e));
+                }
+            }
+            for x in [(*snk, *src, *e), (*src, *snk, *e)] {
+                if !self.adjacent_edges.contains(&x) {
+                    return Err(format!("{:?} is missing from adjacent_edges", x));
+                }
+            }
+            *degrees.get_mut(src).unwrap() += 1;
+            if src != snk {
+                *degrees.get_mut(snk).unwrap() += 1;
+                adjacent_edges += 2;
+            } else {
+                adjacent_edges += 1;
+            }
+        }
+        if self.adjacent_edges.len() != adjacent_edges {
+            return Err("adjacent_edges has stale entries".to_string());
+        }
+        if degrees != self.degrees {
+            return Err(format!(
+                "cached degrees {:?} differ from counted ones {:?}",
+                self.degrees, degrees
+            ));
+        }
+        Ok(())
+    }
+
+    /// Relabels every vertex `v` as `f(v)`, and rebuilds indices in $O(\|V\| + \|E\| \log \|E\|)$.
+    ///
+    /// Edges keep their ID's.
+    /// Vertices added later get ID's greater than all relabeled ones.
+    ///
+    /// # Panics
+    ///
+    /// `f` must be injective on vertices of the graph.
+    /// Otherwise, it panics and leaves the graph untouched.
+    pub fn relabel_vertices<F: Fn(VertexId) -> VertexId>(&mut self, f: F) {
+        let relabeled: BTreeMap<VertexId, VertexId> =
+            self.vertices.iter().map(|v| (*v, f(*v))).collect();
+        let vertices: BTreeSet<VertexId> = relabeled.values().copied().collect();
+        assert_eq!(
+            vertices.len(),
+            self.vertices.len(),
+            "relabeling must be injective"
+        );
+        if let Some(max) = vertices.iter().next_back() {
+            self.vid_factory.skip_past(*max);
+        }
+        self.vertices = vertices;
+        self.degrees = std::mem::take(&mut self.degrees)
+            .into_iter()
+            .map(|(v, d)| (relabeled[&v], d))
+            .collect();
+        self.adjacent_edges.clear();
+        for (e, (src, snk)) in self.edges.iter_mut() {
+            *src = relabeled[src];
+            *snk = relabeled[snk];
+            self.adjacent_edges.insert((*snk, *src, *e));
+            self.adjacent_edges.insert((*src, *snk, *e));
+        }
+    }
+
+    /// Same as [QueryableGraph::iter_vertices] but without boxing the iterator.
+    pub fn vertices_iter(&self) -> impl Iterator<Item = VertexId> + '_ {
+        self.vertices.iter().copied()
+    }
+
+    /// Same as [QueryableGraph::iter_edges] but without boxing the iterator.
+    pub fn edges_iter(&self) -> impl Iterator<Item = Edge> + '_ {
+        self.edges.iter().map(|(e, (src, snk))| Edge {
+            id: *e,
+            source: *src,
+            sink: *snk,
+        })
+    }
+
+    /// Same as [QueryableGraph::in_edges] but without boxing the iterator.
+    pub fn in_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        let start = (*v, VertexId::MIN, EdgeId::MIN);
+        let end = (v.next(), VertexId::MIN, EdgeId::MIN);
+        self.adjacent_edges
+            .range(start..end)
+            .map(|(snk, src, e)| Edge {
+                id: *e,
+                source: *src,
+                sink: *snk,
+            })
+    }
+
+    /// Same as [QueryableGraph::out_edges] but without boxing the iterator.
+    pub fn out_edges_iter(&self, v: &VertexId) -> impl Iterator<Item = Edge> + '_ {
+        self.in_edges_iter(v).map(|e| Edge {
+            id: e.id,
+            source: e.sink,
+            sink: e.source,
+        })
+    }
+
+    /// Same as [QueryableGraph::edges_connecting] but without boxing the iterator.
+    pub fn edges_connecting_iter(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> impl Iterator<Item = Edge> + '_ {
+        let source = *source;
+        let sink = *sink;
+        let start = (source, sink, EdgeId::MIN);
+        let end = (source, sink, EdgeId::MAX);
+        self.adjacent_edges
+            .range(start..=end)
+            .map(move |(_, _, eid)| Edge {
+                id: *eid,
+                source,
+                sink,
+            })
+    }
+}
+
+/// Iteration over vertices in ascending order of their ID's, without boxing.
+///
+/// Thus, `for v in &graph` is the same as `for v in graph.vertices_iter()`.
+impl<'a> IntoIterator for &'a TreeBackedGraph {
+    type Item = VertexId;
+    type IntoIter = std::iter::Copied<std::collections::btree_set::Iter<'a, VertexId>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.vertices.iter().copied()
+    }
+}
+
 impl QueryableGraph for TreeBackedGraph {
     fn vertex_size(&self) -> usize {
         self.vertices.len()
     }
 
     fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
-        Box::new(self.vertices.iter().copied())
+        Box::new(self.vertices_iter())
     }
 
     fn contains_vertex(&self, v: &VertexId) -> bool {
@@ -132,11 +346,7 @@ impl QueryableGraph for TreeBackedGraph {
     }
 
     fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
-        Box::new(self.edges.iter().map(|(e, (src, snk))| Edge {
-            id: *e,
-            source: *src,
-            sink: *snk,
-        }))
+        Box::new(self.edges_iter())
     }
 
     fn contains_edge(&self, e: &EdgeId) -> bool {
@@ -152,26 +362,42 @@ impl QueryableGraph for TreeBackedGraph {
     }
 
     fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let start = (*v, VertexId::MIN, EdgeId::MIN);
-        let end = (v.next(), VertexId::MIN, EdgeId::MIN);
-        let it = self
-            .adjacent_edges
-            .range(start..end)
-            .map(|(snk, src, e)| Edge {
-                id: *e,
-                source: *src,
-                sink: *snk,
-            });
-        Box::new(it)
+        Box::new(self.in_edges_iter(v))
     }
 
     fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
-        let it = self.in_edges(v).map(|e| Edge {
-            id: e.id,
-            source: e.sink,
-            sink: e.source,
-        });
-        Box::new(it)
+        Box::new(self.out_edges_iter(v))
+    }
+
+    fn for_each_out_edge<F: FnMut(&Edge)>(&self, v: &VertexId, mut f: F) {
+        for e in self.out_edges_iter(v) {
+            f(&e);
+        }
+    }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.degrees.get(v).copied().unwrap_or(0)
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.in_degree(v)
+    }
+
+    fn edge_multiplicity(&self, source: &VertexId, sink: &VertexId) -> usize {
+        self.edges_connecting_iter(source, sink).count()
+    }
+
+    /// Intersects neighbors, which are already sorted.
+    fn common_successors(&self, a: &VertexId, b: &VertexId) -> Vec<VertexId> {
+        crate::graph::r#trait::intersect_sorted(
+            self.in_edges_iter(a).map(|e| e.source),
+            self.in_edges_iter(b).map(|e| e.source),
+        )
+    }
+
+    /// Common neighbors as well.
+    fn common_predecessors(&self, a: &VertexId, b: &VertexId) -> Vec<VertexId> {
+        self.common_successors(a, b)
     }
 
     fn edges_connecting<'a, 'b>(
@@ -179,19 +405,7 @@ impl QueryableGraph for TreeBackedGraph {
         source: &'b VertexId,
         sink: &'b VertexId,
     ) -> Box<dyn Iterator<Item = Edge> + 'a> {
-        let source = *source;
-        let sink = *sink;
-        let start = (source, sink, EdgeId::MIN);
-        let end = (source, sink, EdgeId::MAX);
-        let it = self
-            .adjacent_edges
-            .range(start..=end)
-            .map(move |(_, _, eid)| Edge {
-                id: *eid,
-                source,
-                sink,
-            });
-        Box::new(it)
+        Box::new(self.edges_connecting_iter(source, sink))
     }
 }
 
@@ -199,6 +413,58 @@ impl QueryableGraph for TreeBackedGraph {
 mod tests {
     use crate::graph::*;
     use quickcheck_macros::*;
+    use std::collections::BTreeSet;
+
+    #[quickcheck]
+    fn invariants(ops: directed::Ops) {
+        let mut trial: MappedGraph<undirected::TreeBackedGraph> = MappedGraph::new();
+        for op in ops.iter() {
+            trial.apply(&directed::Ops { ops: vec![*op] });
+            trial.graph.check_invariants().unwrap();
+        }
+    }
+
+    #[test]
+    fn corrupted_invariants() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let e = g.add_edge(v0, v1);
+        g.add_edge(v1, v1);
+        assert_eq!(g.check_invariants(), Ok(()));
+
+        let mut h = g.clone();
+        h.adjacent_edges.remove(&(v1, v0, e));
+        assert!(h.check_invariants().is_err());
+
+        let mut h = g.clone();
+        h.adjacent_edges.insert((v0, v0, e));
+        assert!(h.check_invariants().is_err());
+
+        let mut h = g.clone();
+        h.degrees.insert(v1, 1);
+        assert!(h.check_invariants().is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_inconsistent() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        g.add_edge(v0, v1);
+        let json = serde_json::to_value(&g).unwrap();
+        let trial: undirected::TreeBackedGraph = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(trial.check_invariants(), Ok(()));
+
+        let mut broken = json.clone();
+        broken["adjacent_edges"] = serde_json::json!([]);
+        assert!(serde_json::from_value::<undirected::TreeBackedGraph>(broken).is_err());
+
+        let mut broken = json;
+        broken["vid_factory"] = serde_json::json!(0);
+        assert!(serde_json::from_value::<undirected::TreeBackedGraph>(broken).is_err());
+    }
 
     #[quickcheck]
     fn tree_backed_gen(ops: directed::Ops) {
@@ -207,4 +473,52 @@ mod tests {
         let trial: MappedGraph<undirected::TreeBackedGraph> = dig.transform();
         assert_eq!(oracle, trial);
     }
+
+    #[quickcheck]
+    fn in_and_out_edges(ops: directed::Ops) {
+        let oracle: MappedGraph<undirected::AdjacentListGraph> = (&ops).into();
+        let trial: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        for v in oracle.iter_vertices() {
+            let oracle_ins: BTreeSet<_> = oracle.in_edges(&v).collect();
+            let trial_ins: BTreeSet<_> = trial.in_edges(&v).collect();
+            assert_eq!(oracle_ins, trial_ins);
+            assert!(trial_ins.iter().all(|e| e.sink == v));
+            let oracle_outs: BTreeSet<_> = oracle.out_edges(&v).collect();
+            let trial_outs: BTreeSet<_> = trial.out_edges(&v).collect();
+            assert_eq!(oracle_outs, trial_outs);
+            assert!(trial_outs.iter().all(|e| e.source == v));
+        }
+    }
+
+    #[quickcheck]
+    fn cached_degrees(ops: directed::Ops) {
+        let trial: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let g = &trial.graph;
+        for v in g.iter_vertices() {
+            assert_eq!(g.in_degree(&v), g.in_edges(&v).count());
+            assert_eq!(g.out_degree(&v), g.out_edges(&v).count());
+        }
+    }
+
+    #[quickcheck]
+    fn relabel_vertices(ops: directed::Ops) {
+        let oracle: MappedGraph<undirected::TreeBackedGraph> = (&ops).into();
+        let relabel = |v: VertexId| VertexId(2 * v.0 + 7);
+        let mut graph = oracle.graph.clone();
+        graph.relabel_vertices(relabel);
+        let vmap = oracle
+            .vmap
+            .iter()
+            .map(|(inner, outer)| (relabel(*inner), *outer))
+            .collect();
+        let mut trial = MappedGraph {
+            graph,
+            vmap,
+            emap: oracle.emap.clone(),
+        };
+        assert_eq!(oracle, trial);
+        let v = trial.graph.add_vertex();
+        assert_eq!(trial.graph.vertex_size(), oracle.graph.vertex_size() + 1);
+        assert_eq!(trial.graph.in_degree(&v), 0);
+    }
 }
@@ -1,9 +1,11 @@
 /// ID for vertices, which are essentially `usize`.
 #[derive(Debug, Clone, Copy, Eq, PartialEq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexId(pub usize);
 
 /// A factory to generate `VertexId` uniquely.
 #[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct VertexIdFactory(usize);
 
 impl Default for VertexIdFactory {
@@ -22,6 +24,11 @@ impl VertexIdFactory {
         self.0 += 1;
         VertexId(cur)
     }
+
+    /// Makes sure that ID's generated later are greater than `vid`.
+    pub fn skip_past(&mut self, vid: VertexId) {
+        self.0 = self.0.max(vid.0 + 1);
+    }
 }
 
 impl VertexId {
@@ -39,4 +46,30 @@ impl VertexId {
     pub fn next(&self) -> Self {
         Self(self.0 + 1)
     }
+
+    /// Converts into `u32`, or `None` if it does not fit in.
+    pub fn to_u32(&self) -> Option<u32> {
+        u32::try_from(self.0).ok()
+    }
+
+    /// Converts from `u32`, the inverse of `to_u32`.
+    pub fn from_u32(x: u32) -> Self {
+        Self(x as usize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn u32_conversion() {
+        for x in [0, 1, u32::MAX - 1, u32::MAX] {
+            assert_eq!(VertexId::from_u32(x).to_u32(), Some(x));
+        }
+        if let Some(x) = (u32::MAX as usize).checked_add(1) {
+            assert_eq!(VertexId::new(x).to_u32(), None);
+        }
+        assert_eq!(VertexId::MAX.to_u32(), u32::try_from(usize::MAX).ok());
+    }
 }
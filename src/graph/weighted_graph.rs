@@ -0,0 +1,157 @@
+use crate::graph::*;
+use crate::random_state::new_random_state;
+use ahash::RandomState;
+use std::collections::HashMap;
+
+/// A graph whose edges are weighted by a map.
+///
+/// It borrows the underlying graph and owns the weights,
+/// so that weights need not be threaded through closures.
+/// As a [QueryableGraph], it behaves the same as the underlying graph.
+pub struct WeightedGraph<'a, G, W> {
+    lower_graph: &'a G,
+    weights: HashMap<EdgeId, W, RandomState>,
+}
+
+impl<'a, G, W> WeightedGraph<'a, G, W>
+where
+    G: QueryableGraph,
+{
+    /// Wraps a graph without any weight.
+    pub fn new(lower_graph: &'a G) -> Self {
+        Self {
+            lower_graph,
+            weights: HashMap::with_hasher(new_random_state()),
+        }
+    }
+
+    /// Wraps a graph with every edge weighted by `f`.
+    pub fn from_fn<F>(lower_graph: &'a G, f: F) -> Self
+    where
+        F: Fn(&Edge) -> W,
+    {
+        let mut res = Self::new(lower_graph);
+        for e in lower_graph.iter_edges() {
+            res.weights.insert(e.id, f(&e));
+        }
+        res
+    }
+
+    /// Sets the weight of an edge and returns the old one if any.
+    pub fn set_weight(&mut self, e: EdgeId, weight: W) -> Option<W> {
+        debug_assert!(self.lower_graph.contains_edge(&e));
+        self.weights.insert(e, weight)
+    }
+
+    /// Weight of an edge, or `None` if it is not weighted yet.
+    pub fn weight(&self, e: &EdgeId) -> Option<&W> {
+        self.weights.get(e)
+    }
+
+    /// The underlying graph.
+    pub fn lower_graph(&self) -> &'a G {
+        self.lower_graph
+    }
+}
+
+impl<'a, G, W> DirectedOrNot for WeightedGraph<'a, G, W>
+where
+    G: DirectedOrNot,
+{
+    const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
+}
+
+impl<'a, G, W> QueryableGraph for WeightedGraph<'a, G, W>
+where
+    G: QueryableGraph,
+{
+    fn vertex_size(&self) -> usize {
+        self.lower_graph.vertex_size()
+    }
+
+    fn iter_vertices(&self) -> Box<dyn Iterator<Item = VertexId> + '_> {
+        self.lower_graph.iter_vertices()
+    }
+
+    fn contains_vertex(&self, v: &VertexId) -> bool {
+        self.lower_graph.contains_vertex(v)
+    }
+
+    fn edge_size(&self) -> usize {
+        self.lower_graph.edge_size()
+    }
+
+    fn iter_edges(&self) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.lower_graph.iter_edges()
+    }
+
+    fn contains_edge(&self, e: &EdgeId) -> bool {
+        self.lower_graph.contains_edge(e)
+    }
+
+    fn find_edge(&self, e: &EdgeId) -> Option<Edge> {
+        self.lower_graph.find_edge(e)
+    }
+
+    fn edges_connecting(
+        &self,
+        source: &VertexId,
+        sink: &VertexId,
+    ) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.lower_graph.edges_connecting(source, sink)
+    }
+
+    fn in_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.lower_graph.in_edges(v)
+    }
+
+    fn out_edges(&self, v: &VertexId) -> Box<dyn Iterator<Item = Edge> + '_> {
+        self.lower_graph.out_edges(v)
+    }
+
+    fn in_degree(&self, v: &VertexId) -> usize {
+        self.lower_graph.in_degree(v)
+    }
+
+    fn out_degree(&self, v: &VertexId) -> usize {
+        self.lower_graph.out_degree(v)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::Dijkstra;
+    use crate::graph::directed::Ops;
+    use quickcheck_macros::quickcheck;
+
+    #[test]
+    fn set_weight() {
+        let mut g = undirected::TreeBackedGraph::new();
+        let v0 = g.add_vertex();
+        let v1 = g.add_vertex();
+        let e0 = g.add_edge(v0, v1);
+        let e1 = g.add_edge(v1, v1);
+        let mut wg = WeightedGraph::from_fn(&g, |e| e.id.0 * 10);
+        assert_eq!(wg.weight(&e1), Some(&(e1.0 * 10)));
+        assert_eq!(wg.set_weight(e0, 7), Some(e0.0 * 10));
+        assert_eq!(wg.weight(&e0), Some(&7));
+        let wg = WeightedGraph::<_, usize>::new(&g);
+        assert_eq!(wg.weight(&e0), None);
+    }
+
+    #[quickcheck]
+    fn dijkstra_over_weights(ops: Ops) {
+        let g: MappedGraph<directed::TreeBackedGraph> = (&ops).into();
+        let weight = |e: &Edge| (e.id.0 * 31 % 11) as u64;
+        let wg = WeightedGraph::from_fn(&g.graph, weight);
+        for v in g.graph.iter_vertices() {
+            let oracle = g.graph.dijkstra(&v, weight);
+            let trial = wg.dijkstra(&v, |e| *wg.weight(&e.id).unwrap());
+            assert_eq!(trial.len(), oracle.len());
+            for (u, (d, _)) in oracle.iter() {
+                assert_eq!(trial[u].0, *d);
+            }
+        }
+    }
+}
@@ -1,5 +1,11 @@
 #![doc = include_str!("../README.md")]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "std")]
 pub mod algorithm;
 pub mod graph;
+#[cfg(feature = "std")]
 pub mod tagged;
+
+#[cfg(feature = "std")]
+mod random_state;
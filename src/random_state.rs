@@ -0,0 +1,40 @@
+//! Hasher builders for hash maps and hash sets inside this crate.
+use ahash::RandomState;
+
+/// Builds a hasher builder for hash maps and hash sets inside this crate.
+///
+/// With the `deterministic` feature, hashers are seeded with fixed seeds,
+/// so that iteration orders of hash maps and hash sets are reproducible from run to run.
+pub(crate) fn new_random_state() -> RandomState {
+    if cfg!(feature = "deterministic") {
+        RandomState::with_seeds(
+            0x243f_6a88_85a3_08d3,
+            0x1319_8a2e_0370_7344,
+            0xa409_3822_299f_31d0,
+            0x082e_fa98_ec4e_6c89,
+        )
+    } else {
+        RandomState::new()
+    }
+}
+
+#[cfg(all(test, feature = "deterministic"))]
+mod tests {
+    use crate::graph::{directed::TreeBackedGraph, *};
+
+    #[test]
+    fn mapped_graphs_in_same_order() {
+        let input: String = (0..64)
+            .map(|i| format!("{} {}\n", i, (i * 7) % 64))
+            .collect();
+        let load = || {
+            let g: MappedGraph<TreeBackedGraph> = stream_edge_list(input.as_bytes()).unwrap();
+            let vs: Vec<_> = g.vmap.iter().map(|(l, r)| (*l, *r)).collect();
+            let es: Vec<_> = g.emap.iter().map(|(l, r)| (*l, *r)).collect();
+            (vs, es)
+        };
+        let first = load();
+        let second = load();
+        assert_eq!(first, second);
+    }
+}
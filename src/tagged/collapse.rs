@@ -0,0 +1,121 @@
+use crate::graph::*;
+use crate::tagged::{
+    Edge as _Edge, EdgeShrinkableTaggedGraph, GrowableTaggedGraph, QueryableTaggedGraph,
+};
+use std::collections::BTreeMap;
+
+/// A trait with default implementation for collapsing parallel edges in tagged graphs.
+pub trait CollapseParallelEdges
+where
+    Self: QueryableTaggedGraph + GrowableTaggedGraph + EdgeShrinkableTaggedGraph + DirectedOrNot,
+    Self::LowerGraph: QueryableGraph + GrowableGraph + EdgeShrinkableGraph,
+{
+    /// Collapses each group of parallel edges into a single edge.
+    ///
+    /// For each pair of endpoints, the edge with the least ID is kept,
+    /// and its tag becomes the fold of tags of all parallel edges in ascending order of their ID's,
+    /// i.e., `combine(&combine(&e0, &e1), &e2)` and so on.
+    /// The other edges are removed.
+    ///
+    /// The first argument to `combine` is always a tag of the kept edge, or a fold thereof.
+    /// `combine` must return a tag with the same endpoints as it.
+    fn collapse_parallel_edges<F>(&mut self, combine: F)
+    where
+        F: Fn(&Self::Edge, &Self::Edge) -> Self::Edge,
+    {
+        let mut groups: BTreeMap<(VertexId, VertexId), Vec<EdgeId>> = BTreeMap::new();
+        for (eid, e) in self.iter_edges() {
+            let (src, snk) = (e.source(), e.sink());
+            let key = if Self::DIRECTED_OR_NOT || src <= snk {
+                (src, snk)
+            } else {
+                (snk, src)
+            };
+            groups.entry(key).or_default().push(eid);
+        }
+        for (_, mut eids) in groups {
+            if eids.len() < 2 {
+                continue;
+            }
+            eids.sort();
+            let keep = eids[0];
+            let mut acc = self.remove_edge(&eids[1]).unwrap();
+            acc = combine(self.edge_by_id(&keep).unwrap(), &acc);
+            for eid in eids[2..].iter() {
+                let e = self.remove_edge(eid).unwrap();
+                acc = combine(&acc, &e);
+            }
+            self.update_edge(keep, acc);
+        }
+    }
+}
+
+impl<G> CollapseParallelEdges for G
+where
+    G: QueryableTaggedGraph + GrowableTaggedGraph + EdgeShrinkableTaggedGraph + DirectedOrNot,
+    G::LowerGraph: QueryableGraph + GrowableGraph + EdgeShrinkableGraph,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tagged::test_edges::WeightedEdge;
+    use crate::tagged::{NaiveTaggedGraph, TaggedGraph};
+
+    fn sum(x: &WeightedEdge, y: &WeightedEdge) -> WeightedEdge {
+        WeightedEdge {
+            weight: x.weight + y.weight,
+            ..x.clone()
+        }
+    }
+
+    #[test]
+    fn directed() {
+        let mut g = NaiveTaggedGraph::<usize, WeightedEdge>::new();
+        let v0 = g.overwrite_vertex(0);
+        let v1 = g.overwrite_vertex(1);
+        let edges = [
+            (v0, v1, 1),
+            (v1, v0, 10),
+            (v0, v1, 2),
+            (v0, v1, 4),
+            (v1, v1, 5),
+        ];
+        let eids: Vec<_> = edges
+            .iter()
+            .map(|(src, snk, weight)| {
+                g.add_edge(WeightedEdge {
+                    src: *src,
+                    snk: *snk,
+                    weight: *weight,
+                })
+            })
+            .collect();
+        g.collapse_parallel_edges(sum);
+        assert_eq!(g.edge_size(), 3);
+        let collapsed: Vec<_> = g.edges_connecting(&v0, &v1).collect();
+        assert_eq!(collapsed.len(), 1);
+        assert_eq!(collapsed[0].0, eids[0]);
+        assert_eq!(collapsed[0].1.weight, 7);
+        assert_eq!(g.edge_by_id(&eids[1]).unwrap().weight, 10);
+        assert!(!g.contains_edge_by_id(&eids[2]));
+        assert!(!g.contains_edge_by_id(&eids[3]));
+        assert_eq!(g.lower_graph().edge_size(), 3);
+    }
+
+    #[test]
+    fn undirected() {
+        let mut g = NaiveTaggedGraph::<usize, WeightedEdge, undirected::TreeBackedGraph>::new();
+        let v0 = g.overwrite_vertex(0);
+        let v1 = g.overwrite_vertex(1);
+        let edges = [(v0, v1, 1), (v1, v0, 10), (v0, v1, 2)];
+        for (src, snk, weight) in edges {
+            g.add_edge(WeightedEdge { src, snk, weight });
+        }
+        g.collapse_parallel_edges(sum);
+        assert_eq!(g.edge_size(), 1);
+        let (_, e) = g.iter_edges().next().unwrap();
+        assert_eq!(e.weight, 13);
+    }
+}
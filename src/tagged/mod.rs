@@ -14,3 +14,13 @@ pub use self::traits::*;
 
 mod naive_impl;
 pub use self::naive_impl::*;
+
+mod weight_matrix;
+pub use self::weight_matrix::*;
+mod weighted_degree;
+pub use self::weighted_degree::*;
+mod collapse;
+pub use self::collapse::*;
+
+#[cfg(test)]
+pub(crate) mod test_edges;
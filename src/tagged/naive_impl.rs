@@ -1,22 +1,45 @@
 use crate::graph::*;
+use crate::random_state::new_random_state;
 use crate::tagged::traits::{GrowableTaggedGraph, TaggedGraph};
 use ahash::RandomState;
 use bimap::BiHashMap;
-use std::hash::Hash;
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasher, Hash};
 
 /// A naive implementation of tagged graphs.
-#[derive(Clone)]
-pub struct NaiveTaggedGraph<V, E, G = directed::TreeBackedGraph>
+///
+/// Vertices and edges are tagged by bidirectional hash maps by `S`,
+/// which is [ahash::RandomState] by default and can be customized by
+/// [NaiveTaggedGraph::new_with_hasher].
+pub struct NaiveTaggedGraph<V, E, G = directed::TreeBackedGraph, S = RandomState>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone,
 {
     lower_graph: G,
-    vertices: BiHashMap<VertexId, V, RandomState, RandomState>,
-    edges: BiHashMap<EdgeId, E, RandomState, RandomState>,
+    vertices: BiHashMap<VertexId, V, S, S>,
+    edges: BiHashMap<EdgeId, E, S, S>,
+    hasher: S,
 }
 
-impl<V, E, G> DirectedOrNot for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> Clone for NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone,
+    E: Hash + Eq + Clone,
+    G: Clone,
+    S: BuildHasher + Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            lower_graph: self.lower_graph.clone(),
+            vertices: self.vertices.clone(),
+            edges: self.edges.clone(),
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<V, E, G, S> DirectedOrNot for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone,
@@ -25,21 +48,23 @@ where
     const DIRECTED_OR_NOT: bool = G::DIRECTED_OR_NOT;
 }
 
-impl<V, E, G> Default for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> Default for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
     G: GrowableGraph,
+    S: BuildHasher + Clone + Default,
 {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<V, E, G> super::TaggedGraph for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> super::TaggedGraph for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
+    S: BuildHasher,
 {
     type LowerGraph = G;
     type Vertex = V;
@@ -64,20 +89,75 @@ where
     fn id_by_edge(&self, edge: &Self::Edge) -> Option<EdgeId> {
         self.edges.get_by_right(edge).copied()
     }
+
+    /// Vertices are keys of a [BiHashMap], so they are all rehashed.
+    /// They are mapped and checked on copies before any is replaced,
+    /// which is what keeps the graph untouched on panics.
+    fn map_vertex_tags<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&VertexId, &mut Self::Vertex),
+    {
+        let mapped: Vec<_> = self
+            .vertices
+            .iter()
+            .map(|(vid, vert)| {
+                let mut vert = vert.clone();
+                f(vid, &mut vert);
+                (*vid, vert)
+            })
+            .collect();
+        let mut seen = HashSet::with_capacity_and_hasher(mapped.len(), new_random_state());
+        assert!(
+            mapped.iter().all(|(_, vert)| seen.insert(vert)),
+            "duplicated vertices"
+        );
+        drop(seen);
+        self.vertices.clear();
+        for (vid, vert) in mapped {
+            self.vertices.insert(vid, vert);
+        }
+    }
+
+    /// Edges are keys of a [BiHashMap], so they are all rehashed.
+    /// They are mapped and checked on copies before any is replaced,
+    /// which is what keeps the graph untouched on panics.
+    fn map_edge_tags<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&EdgeId, &mut Self::Edge),
+    {
+        let mapped: Vec<_> = self
+            .edges
+            .iter()
+            .map(|(eid, edge)| {
+                let mut mapped = edge.clone();
+                f(eid, &mut mapped);
+                assert_eq!(edge.source(), mapped.source());
+                assert_eq!(edge.sink(), mapped.sink());
+                (*eid, mapped)
+            })
+            .collect();
+        let mut seen = HashSet::with_capacity_and_hasher(mapped.len(), new_random_state());
+        assert!(
+            mapped.iter().all(|(_, edge)| seen.insert(edge)),
+            "duplicated edges"
+        );
+        drop(seen);
+        self.edges.clear();
+        for (eid, edge) in mapped {
+            self.edges.insert(eid, edge);
+        }
+    }
 }
 
-impl<V, E, G> super::GrowableTaggedGraph for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> super::GrowableTaggedGraph for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
     G: GrowableGraph,
+    S: BuildHasher + Clone + Default,
 {
     fn new() -> Self {
-        Self {
-            lower_graph: G::new(),
-            vertices: BiHashMap::with_hashers(RandomState::new(), RandomState::new()),
-            edges: BiHashMap::with_hashers(RandomState::new(), RandomState::new()),
-        }
+        Self::new_with_hasher(S::default())
     }
 
     fn overwrite_vertex(&mut self, vert: Self::Vertex) -> VertexId {
@@ -109,11 +189,30 @@ where
     }
 }
 
-impl<V, E, G> super::EdgeShrinkableTaggedGraph for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone,
+    E: Hash + Eq + Clone + super::Edge,
+    G: GrowableGraph,
+    S: BuildHasher + Clone,
+{
+    /// Same as [GrowableTaggedGraph::new] but with hash maps by `hasher`.
+    pub fn new_with_hasher(hasher: S) -> Self {
+        Self {
+            lower_graph: G::new(),
+            vertices: BiHashMap::with_hashers(hasher.clone(), hasher.clone()),
+            edges: BiHashMap::with_hashers(hasher.clone(), hasher.clone()),
+            hasher,
+        }
+    }
+}
+
+impl<V, E, G, S> super::EdgeShrinkableTaggedGraph for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
     G: EdgeShrinkableGraph,
+    S: BuildHasher,
 {
     fn remove_edge(&mut self, eid: &EdgeId) -> Option<Self::Edge> {
         self.lower_graph
@@ -123,11 +222,12 @@ where
     }
 }
 
-impl<V, E, G> super::VertexShrinkableTaggedGraph for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> super::VertexShrinkableTaggedGraph for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
     G: VertexShrinkableGraph,
+    S: BuildHasher,
 {
     fn remove_vertex(
         &mut self,
@@ -146,11 +246,12 @@ where
     }
 }
 
-impl<V, E, G> super::QueryableTaggedGraph for NaiveTaggedGraph<V, E, G>
+impl<V, E, G, S> super::QueryableTaggedGraph for NaiveTaggedGraph<V, E, G, S>
 where
     V: Hash + Eq + Clone,
     E: Hash + Eq + Clone + super::Edge,
     G: QueryableGraph,
+    S: BuildHasher,
 {
     fn vertex_size(&self) -> usize {
         self.vertices.len()
@@ -204,3 +305,556 @@ where
         Box::new(it)
     }
 }
+
+impl<V, E, G, S> NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone,
+    E: Hash + Eq + Clone + super::Edge,
+    G: QueryableGraph + VertexShrinkableGraph + Clone,
+    S: BuildHasher + Clone,
+{
+    /// A copy of the graph with vertices and edges passing predicates.
+    ///
+    /// An edge is kept only if it passes `keep_e` and both its endpoints are kept.
+    /// Kept vertices and edges keep their ID's,
+    /// because customized edges refer to their endpoints by ID's.
+    /// Thus, the lower graph is a pruned copy of this one, of the same type.
+    pub fn filter_by_tags<FV, FE>(&self, mut keep_v: FV, mut keep_e: FE) -> Self
+    where
+        FV: FnMut(&VertexId, &V) -> bool,
+        FE: FnMut(&EdgeId, &E) -> bool,
+    {
+        let mut lower_graph = self.lower_graph.clone();
+        let mut vertices = BiHashMap::with_hashers(self.hasher.clone(), self.hasher.clone());
+        for (vid, vert) in self.vertices.iter() {
+            if keep_v(vid, vert) {
+                vertices.insert(*vid, vert.clone());
+            } else {
+                lower_graph.remove_vertex(vid).for_each(drop);
+            }
+        }
+        let mut edges = BiHashMap::with_hashers(self.hasher.clone(), self.hasher.clone());
+        for (eid, edge) in self.edges.iter() {
+            if !lower_graph.contains_edge(eid) {
+                continue;
+            }
+            if keep_e(eid, edge) {
+                edges.insert(*eid, edge.clone());
+            } else {
+                lower_graph.remove_edge(eid);
+            }
+        }
+        Self {
+            lower_graph,
+            vertices,
+            edges,
+            hasher: self.hasher.clone(),
+        }
+    }
+}
+
+impl<V, E, G, S> NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone,
+    E: Hash + Eq + Clone + super::Edge,
+    G: GrowableGraph,
+    S: BuildHasher + Clone + Default,
+{
+    /// Merges another tagged graph into this one.
+    ///
+    /// Vertices are identified by equality,
+    /// so vertices of `other` equal to existing ones are unified with them,
+    /// and the others are added.
+    /// Customized edges refer to their endpoints by ID's,
+    /// so each edge of `other` is rebuilt by `relink(edge, source, sink)`
+    /// with endpoints in this graph before it is added.
+    /// Rebuilt edges equal to existing ones are skipped, as edges are unique in tagged graphs.
+    ///
+    /// It returns maps from ID's in `other` to those in this graph.
+    pub fn merge<G2, S2, F>(
+        &mut self,
+        other: &NaiveTaggedGraph<V, E, G2, S2>,
+        mut relink: F,
+    ) -> (
+        HashMap<VertexId, VertexId, RandomState>,
+        HashMap<EdgeId, EdgeId, RandomState>,
+    )
+    where
+        G2: QueryableGraph,
+        S2: BuildHasher,
+        F: FnMut(&E, VertexId, VertexId) -> E,
+    {
+        let mut vmap = HashMap::with_hasher(new_random_state());
+        let mut vertices: Vec<_> = other.vertices.iter().collect();
+        vertices.sort_by_key(|(vid, _)| **vid);
+        for (vid, vert) in vertices {
+            let my_vid = match self.id_by_vertex(vert) {
+                Some(my_vid) => my_vid,
+                None => self.overwrite_vertex(vert.clone()),
+            };
+            vmap.insert(*vid, my_vid);
+        }
+        let mut emap = HashMap::with_hasher(new_random_state());
+        let mut edges: Vec<_> = other.edges.iter().collect();
+        edges.sort_by_key(|(eid, _)| **eid);
+        for (eid, edge) in edges {
+            let edge = relink(edge, vmap[&edge.source()], vmap[&edge.sink()]);
+            let my_eid = match self.id_by_edge(&edge) {
+                Some(my_eid) => my_eid,
+                None => self.add_edge(edge),
+            };
+            emap.insert(*eid, my_eid);
+        }
+        (vmap, emap)
+    }
+}
+
+/// Serialized as the lower graph along with vertices and edges paired with their ID's.
+#[cfg(feature = "serde")]
+impl<V, E, G, S> serde::Serialize for NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone + serde::Serialize,
+    E: Hash + Eq + Clone + serde::Serialize,
+    G: serde::Serialize,
+    S: BuildHasher,
+{
+    fn serialize<Ser: serde::Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+        #[derive(serde::Serialize)]
+        struct Repr<'a, V, E, G> {
+            lower_graph: &'a G,
+            vertices: Vec<(&'a VertexId, &'a V)>,
+            edges: Vec<(&'a EdgeId, &'a E)>,
+        }
+
+        let mut vertices: Vec<_> = self.vertices.iter().collect();
+        vertices.sort_by_key(|(vid, _)| **vid);
+        let mut edges: Vec<_> = self.edges.iter().collect();
+        edges.sort_by_key(|(eid, _)| **eid);
+        Repr {
+            lower_graph: &self.lower_graph,
+            vertices,
+            edges,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// It fails unless vertices and edges are exactly those in the lower graph,
+/// each of which is tagged uniquely,
+/// and endpoints of edge tags are those of their edges in the lower graph,
+/// in either orientation if the lower graph is undirected.
+#[cfg(feature = "serde")]
+impl<'de, V, E, G, S> serde::Deserialize<'de> for NaiveTaggedGraph<V, E, G, S>
+where
+    V: Hash + Eq + Clone + serde::Deserialize<'de>,
+    E: Hash + Eq + Clone + super::Edge + serde::Deserialize<'de>,
+    G: QueryableGraph + DirectedOrNot + serde::Deserialize<'de>,
+    S: BuildHasher + Clone + Default,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        #[derive(serde::Deserialize)]
+        struct Repr<V, E, G> {
+            lower_graph: G,
+            vertices: Vec<(VertexId, V)>,
+            edges: Vec<(EdgeId, E)>,
+        }
+
+        let repr = Repr::<V, E, G>::deserialize(deserializer)?;
+        let hasher = S::default();
+        let mut res = Self {
+            lower_graph: repr.lower_graph,
+            vertices: BiHashMap::with_hashers(hasher.clone(), hasher.clone()),
+            edges: BiHashMap::with_hashers(hasher.clone(), hasher.clone()),
+            hasher,
+        };
+        for (vid, vert) in repr.vertices {
+            if !res.lower_graph.contains_vertex(&vid) {
+                return Err(D::Error::custom(format!("unknown vertex {:?}", vid)));
+            }
+            if res.vertices.insert_no_overwrite(vid, vert).is_err() {
+                return Err(D::Error::custom(format!("duplicated vertex {:?}", vid)));
+            }
+        }
+        for (eid, edge) in repr.edges {
+            let lower = match res.lower_graph.find_edge(&eid) {
+                Some(lower) => lower,
+                None => return Err(D::Error::custom(format!("unknown edge {:?}", eid))),
+            };
+            let ends = (edge.source(), edge.sink());
+            if ends != (lower.source, lower.sink)
+                && (G::DIRECTED_OR_NOT || ends != (lower.sink, lower.source))
+            {
+                return Err(D::Error::custom(format!(
+                    "endpoints {:?} of edge {:?} differ from those in the lower graph",
+                    ends, eid
+                )));
+            }
+            if res.edges.insert_no_overwrite(eid, edge).is_err() {
+                return Err(D::Error::custom(format!("duplicated edge {:?}", eid)));
+            }
+        }
+        if res.vertices.len() != res.lower_graph.vertex_size() {
+            return Err(D::Error::custom("untagged vertices"));
+        }
+        if res.edges.len() != res.lower_graph.edge_size() {
+            return Err(D::Error::custom("untagged edges"));
+        }
+        Ok(res)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithm::TopologicalSort;
+    use crate::tagged::test_edges::{PlainEdge, WeightedEdge};
+    use crate::tagged::{QueryableTaggedGraph, TaggedGraph};
+
+    #[test]
+    fn lower_graph() {
+        let mut g = NaiveTaggedGraph::<&str, PlainEdge>::new();
+        let a = g.overwrite_vertex("a");
+        let b = g.overwrite_vertex("b");
+        let c = g.overwrite_vertex("c");
+        g.add_edge(PlainEdge { src: b, snk: c });
+        g.add_edge(PlainEdge { src: a, snk: b });
+        let lower = g.lower_graph();
+        assert_eq!(lower.vertex_size(), g.vertex_size());
+        assert_eq!(lower.edge_size(), g.edge_size());
+        assert_eq!(lower.out_degree(&a), 1);
+        let sorted: Vec<_> = lower
+            .toposort()
+            .map(|v| *g.vertex_by_id(&v).unwrap())
+            .collect();
+        assert_eq!(sorted, vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn edges_connecting_by_vertex() {
+        let mut g = NaiveTaggedGraph::<&str, PlainEdge>::new();
+        let a = g.overwrite_vertex("a");
+        let b = g.overwrite_vertex("b");
+        let ab = g.add_edge(PlainEdge { src: a, snk: b });
+        g.add_edge(PlainEdge { src: b, snk: a });
+        let trial: Vec<_> = g.edges_connecting_by_vertex(&"a", &"b").collect();
+        assert_eq!(trial, vec![(ab, &PlainEdge { src: a, snk: b })]);
+        assert_eq!(g.edges_connecting_by_vertex(&"a", &"a").count(), 0);
+        assert_eq!(g.edges_connecting_by_vertex(&"a", &"c").count(), 0);
+        assert_eq!(g.edges_connecting_by_vertex(&"c", &"b").count(), 0);
+    }
+
+    #[test]
+    fn take_vertex() {
+        use crate::tagged::VertexShrinkableTaggedGraph;
+
+        let mut g = NaiveTaggedGraph::<String, PlainEdge>::new();
+        let a = g.overwrite_vertex("a".to_owned());
+        let b = g.overwrite_vertex("b".to_owned());
+        let ab = g.add_edge(PlainEdge { src: a, snk: b });
+        let bb = g.add_edge(PlainEdge { src: b, snk: b });
+        let (vert, mut edges) = g.take_vertex(&b).unwrap();
+        assert_eq!(vert, "b");
+        edges.sort_by_key(|(eid, _)| *eid);
+        assert_eq!(
+            edges,
+            vec![
+                (ab, PlainEdge { src: a, snk: b }),
+                (bb, PlainEdge { src: b, snk: b }),
+            ]
+        );
+        assert!(!g.contains_vertex_by_id(&b));
+        assert_eq!(g.edge_size(), 0);
+        assert_eq!(g.take_vertex(&b), None);
+    }
+
+    #[test]
+    fn map_vertex_and_edge_tags() {
+        let mut g = NaiveTaggedGraph::<String, WeightedEdge>::new();
+        let a = g.overwrite_vertex("a".to_owned());
+        let b = g.overwrite_vertex("b".to_owned());
+        let ab = g.add_edge(WeightedEdge {
+            src: a,
+            snk: b,
+            weight: 1,
+        });
+        let ba = g.add_edge(WeightedEdge {
+            src: b,
+            snk: a,
+            weight: 2,
+        });
+        g.map_edge_tags(|_, e| e.weight *= 2);
+        let mut weights: Vec<_> = g.iter_edges().map(|(eid, e)| (eid, e.weight)).collect();
+        weights.sort();
+        assert_eq!(weights, vec![(ab, 2), (ba, 4)]);
+
+        g.map_vertex_tags(|_, v| v.make_ascii_uppercase());
+        assert_eq!(g.id_by_vertex(&"A".to_owned()), Some(a));
+        assert_eq!(g.id_by_vertex(&"B".to_owned()), Some(b));
+        assert_eq!(g.id_by_vertex(&"a".to_owned()), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_edge_tags_moving_endpoints() {
+        let mut g = NaiveTaggedGraph::<&str, PlainEdge>::new();
+        let a = g.overwrite_vertex("a");
+        let b = g.overwrite_vertex("b");
+        g.add_edge(PlainEdge { src: a, snk: b });
+        g.map_edge_tags(|_, e| e.snk = a);
+    }
+
+    #[test]
+    fn panicking_maps_leave_graph_untouched() {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let mut g = NaiveTaggedGraph::<&str, PlainEdge>::new();
+        let vs: Vec<_> = ["a", "b", "c"]
+            .iter()
+            .map(|v| g.overwrite_vertex(*v))
+            .collect();
+        let es: Vec<_> = [(0, 1), (1, 2), (2, 0)]
+            .iter()
+            .map(|(u, v)| {
+                g.add_edge(PlainEdge {
+                    src: vs[*u],
+                    snk: vs[*v],
+                })
+            })
+            .collect();
+        let res = catch_unwind(AssertUnwindSafe(|| g.map_vertex_tags(|_, v| *v = "x")));
+        assert!(res.is_err());
+        let res = catch_unwind(AssertUnwindSafe(|| g.map_edge_tags(|_, e| e.snk = e.src)));
+        assert!(res.is_err());
+        for (vid, v) in vs.iter().zip(["a", "b", "c"]) {
+            assert_eq!(g.vertex_by_id(vid), Some(&v));
+        }
+        for (eid, (u, v)) in es.iter().zip([(0, 1), (1, 2), (2, 0)]) {
+            let e = g.edge_by_id(eid).unwrap();
+            assert_eq!((e.src, e.snk), (vs[u], vs[v]));
+        }
+    }
+
+    #[test]
+    fn filter_by_tags() {
+        let mut g = NaiveTaggedGraph::<&str, WeightedEdge>::new();
+        let a = g.overwrite_vertex("a");
+        let b = g.overwrite_vertex("b");
+        let c = g.overwrite_vertex("c");
+        g.add_edge(WeightedEdge {
+            src: a,
+            snk: b,
+            weight: 1,
+        });
+        let bc = g.add_edge(WeightedEdge {
+            src: b,
+            snk: c,
+            weight: 2,
+        });
+        let cb = g.add_edge(WeightedEdge {
+            src: c,
+            snk: b,
+            weight: 3,
+        });
+        let filtered = g.filter_by_tags(|_, v| *v != "a", |_, e| e.weight < 3);
+        assert_eq!(filtered.vertex_size(), 2);
+        assert_eq!(filtered.id_by_vertex(&"a"), None);
+        assert_eq!(filtered.id_by_vertex(&"b"), Some(b));
+        assert_eq!(filtered.id_by_vertex(&"c"), Some(c));
+        let edges: Vec<_> = filtered.iter_edges().map(|(eid, _)| eid).collect();
+        assert_eq!(edges, vec![bc]);
+        assert_eq!(filtered.lower_graph().edge_size(), 1);
+        assert_eq!(filtered.lower_graph().vertex_size(), 2);
+        // the original is untouched
+        assert_eq!(g.vertex_size(), 3);
+        assert!(g.contains_edge_by_id(&cb));
+    }
+
+    #[test]
+    fn merge() {
+        let mut g1 = NaiveTaggedGraph::<&str, WeightedEdge>::new();
+        let a = g1.overwrite_vertex("a");
+        let b = g1.overwrite_vertex("b");
+        g1.add_edge(WeightedEdge {
+            src: a,
+            snk: b,
+            weight: 1,
+        });
+        let mut g2 = NaiveTaggedGraph::<&str, WeightedEdge>::new();
+        let c2 = g2.overwrite_vertex("c");
+        let b2 = g2.overwrite_vertex("b");
+        let bc2 = g2.add_edge(WeightedEdge {
+            src: b2,
+            snk: c2,
+            weight: 2,
+        });
+        let (vmap, emap) = g1.merge(&g2, |e, src, snk| WeightedEdge {
+            src,
+            snk,
+            weight: e.weight,
+        });
+        assert_eq!(g1.vertex_size(), 3);
+        assert_eq!(vmap[&b2], b);
+        let c = g1.id_by_vertex(&"c").unwrap();
+        assert_eq!(vmap[&c2], c);
+        assert_eq!(g1.edge_size(), 2);
+        assert_eq!(
+            g1.edge_by_id(&emap[&bc2]),
+            Some(&WeightedEdge {
+                src: b,
+                snk: c,
+                weight: 2,
+            })
+        );
+        assert_eq!(g1.lower_graph().edge_size(), 2);
+
+        // merging again adds nothing
+        g1.merge(&g2, |e, src, snk| WeightedEdge {
+            src,
+            snk,
+            weight: e.weight,
+        });
+        assert_eq!(g1.vertex_size(), 3);
+        assert_eq!(g1.edge_size(), 2);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn json_round_trip() {
+        use crate::tagged::VertexShrinkableTaggedGraph;
+
+        let mut g = NaiveTaggedGraph::<String, WeightedEdge>::new();
+        let a = g.overwrite_vertex("a".to_string());
+        let b = g.overwrite_vertex("b".to_string());
+        let c = g.overwrite_vertex("c".to_string());
+        for (src, snk, weight) in [(a, b, 1), (b, c, 2), (c, c, 3)] {
+            g.add_edge(WeightedEdge { src, snk, weight });
+        }
+        g.remove_vertex(&a).for_each(drop);
+
+        let json = serde_json::to_string(&g).unwrap();
+        let trial: NaiveTaggedGraph<String, WeightedEdge> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&trial).unwrap(), json);
+        assert_eq!(trial.vertex_size(), 2);
+        assert_eq!(trial.edge_size(), 2);
+        for (vid, vert) in g.iter_vertices() {
+            assert_eq!(trial.vertex_by_id(&vid), Some(vert));
+            assert_eq!(trial.id_by_vertex(vert), Some(vid));
+        }
+        for (eid, edge) in g.iter_edges() {
+            assert_eq!(trial.edge_by_id(&eid), Some(edge));
+            assert_eq!(trial.id_by_edge(edge), Some(eid));
+        }
+        let mut trial = trial;
+        let d = trial.overwrite_vertex("d".to_string());
+        assert!(d != b && d != c);
+
+        let broken = json.replace("\"b\"", "\"c\"");
+        assert!(serde_json::from_str::<NaiveTaggedGraph<String, WeightedEdge>>(&broken).is_err());
+        let pattern = format!("\"snk\":{},\"weight\":2", c.0);
+        assert!(json.contains(&pattern));
+        let broken = json.replace(&pattern, &format!("\"snk\":{},\"weight\":2", b.0));
+        assert!(serde_json::from_str::<NaiveTaggedGraph<String, WeightedEdge>>(&broken).is_err());
+
+        // the same edge reversed is fine only for undirected lower graphs
+        let mut g = NaiveTaggedGraph::<String, WeightedEdge, undirected::TreeBackedGraph>::new();
+        let a = g.overwrite_vertex("a".to_string());
+        let b = g.overwrite_vertex("b".to_string());
+        g.add_edge(WeightedEdge {
+            src: a,
+            snk: b,
+            weight: 1,
+        });
+        let json = serde_json::to_string(&g).unwrap();
+        let pattern = format!("\"src\":{},\"snk\":{}", a.0, b.0);
+        assert!(json.contains(&pattern));
+        let reversed = json.replace(&pattern, &format!("\"src\":{},\"snk\":{}", b.0, a.0));
+        assert!(serde_json::from_str::<
+            NaiveTaggedGraph<String, WeightedEdge, undirected::TreeBackedGraph>,
+        >(&reversed)
+        .is_ok());
+    }
+
+    /// A vertex identified by `key` alone, carrying a mutable `tag`.
+    #[derive(Debug, Clone)]
+    struct KeyedVertex {
+        key: &'static str,
+        tag: usize,
+    }
+
+    impl PartialEq for KeyedVertex {
+        fn eq(&self, other: &Self) -> bool {
+            self.key == other.key
+        }
+    }
+
+    impl Eq for KeyedVertex {}
+
+    impl std::hash::Hash for KeyedVertex {
+        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+            self.key.hash(state);
+        }
+    }
+
+    #[test]
+    fn vertex_entry() {
+        let mut g = NaiveTaggedGraph::<KeyedVertex, PlainEdge>::new();
+        let a = g.overwrite_vertex(KeyedVertex { key: "a", tag: 1 });
+        let probe = |key| KeyedVertex { key, tag: 0 };
+
+        let entry = g.vertex_entry(probe("a"));
+        assert_eq!(entry.key().key, "a");
+        assert_eq!(entry.id(), Some(a));
+        let mut called = false;
+        let trial = entry.or_insert_with(|| {
+            called = true;
+            KeyedVertex { key: "a", tag: 2 }
+        });
+        assert_eq!(trial, a);
+        assert!(!called);
+        assert_eq!(g.vertex_by_id(&a).unwrap().tag, 1);
+
+        let entry = g.vertex_entry(probe("b"));
+        assert_eq!(entry.id(), None);
+        let b = entry.or_insert_with(|| KeyedVertex { key: "b", tag: 3 });
+        assert_eq!(g.vertex_size(), 2);
+        assert_eq!(g.vertex_by_id(&b).unwrap().tag, 3);
+        assert_eq!(g.vertex_entry(probe("b")).or_insert(), b);
+
+        let c = g.vertex_entry(probe("c")).or_insert();
+        assert_eq!(g.vertex_by_id(&c).unwrap().tag, 0);
+        assert_eq!(g.vertex_size(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "differs from the key")]
+    fn vertex_entry_of_another_key() {
+        let mut g = NaiveTaggedGraph::<KeyedVertex, PlainEdge>::new();
+        g.vertex_entry(KeyedVertex { key: "a", tag: 0 })
+            .or_insert_with(|| KeyedVertex { key: "b", tag: 0 });
+    }
+
+    #[test]
+    fn new_with_hasher() {
+        use std::collections::hash_map::RandomState;
+        type StdTaggedGraph =
+            NaiveTaggedGraph<&'static str, PlainEdge, directed::TreeBackedGraph, RandomState>;
+
+        let mut g = StdTaggedGraph::new_with_hasher(RandomState::new());
+        let a = g.overwrite_vertex("a");
+        let b = g.overwrite_vertex("b");
+        let e = g.add_edge(PlainEdge { src: a, snk: b });
+        assert_eq!(g.id_by_vertex(&"b"), Some(b));
+        assert_eq!(g.id_by_edge(&PlainEdge { src: a, snk: b }), Some(e));
+        g.map_vertex_tags(|_, v| *v = if *v == "a" { "c" } else { "d" });
+        assert_eq!(g.id_by_vertex(&"c"), Some(a));
+
+        let mut h = StdTaggedGraph::new();
+        h.overwrite_vertex("c");
+        let (vmap, _) = h.merge(&g, |_, src, snk| PlainEdge { src, snk });
+        assert_eq!(h.vertex_size(), 2);
+        assert_eq!(h.vertex_by_id(&vmap[&b]), Some(&"d"));
+        assert_eq!(h.edge_size(), 1);
+    }
+}
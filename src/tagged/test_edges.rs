@@ -0,0 +1,58 @@
+//! Edge tags and graphs shared by tests.
+use super::GrowableTaggedGraph;
+use crate::graph::{GrowableGraph, VertexId};
+
+/// An edge tag of nothing but its endpoints.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PlainEdge {
+    pub src: VertexId,
+    pub snk: VertexId,
+}
+
+impl super::Edge for PlainEdge {
+    fn source(&self) -> VertexId {
+        self.src
+    }
+
+    fn sink(&self) -> VertexId {
+        self.snk
+    }
+}
+
+/// An edge tag with a weight.
+///
+/// Tags identify edges, so parallel edges must differ in weights.
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WeightedEdge {
+    pub src: VertexId,
+    pub snk: VertexId,
+    pub weight: u32,
+}
+
+impl super::Edge for WeightedEdge {
+    fn source(&self) -> VertexId {
+        self.src
+    }
+
+    fn sink(&self) -> VertexId {
+        self.snk
+    }
+}
+
+/// Vertices `0`, `1` and `2`, with weighted edges `0 -> 1` of weights 3 and 2,
+/// `1 -> 2` of weight 5 and a self-loop on `2` of weight 1.
+pub fn build_weighted<G>() -> (G, [VertexId; 3])
+where
+    G: GrowableTaggedGraph<Vertex = usize, Edge = WeightedEdge>,
+    G::LowerGraph: GrowableGraph,
+{
+    let mut g = G::new();
+    let v0 = g.overwrite_vertex(0);
+    let v1 = g.overwrite_vertex(1);
+    let v2 = g.overwrite_vertex(2);
+    for (src, snk, weight) in [(v0, v1, 3), (v0, v1, 2), (v1, v2, 5), (v2, v2, 1)] {
+        g.add_edge(WeightedEdge { src, snk, weight });
+    }
+    (g, [v0, v1, v2])
+}
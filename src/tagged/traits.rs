@@ -33,6 +33,26 @@ pub trait TaggedGraph {
     fn contains_edge(&self, edge: &Self::Edge) -> bool {
         self.id_by_edge(edge).is_some()
     }
+
+    /// Transforms all customized vertices by `f`, which sees their [VertexId]'s.
+    ///
+    /// # Panics
+    ///
+    /// Vertices must stay distinct from each other.
+    /// Otherwise, it panics and leaves the graph untouched.
+    fn map_vertex_tags<F>(&mut self, f: F)
+    where
+        F: FnMut(&VertexId, &mut Self::Vertex);
+
+    /// Transforms all customized edges by `f`, which sees their [EdgeId]'s.
+    ///
+    /// # Panics
+    ///
+    /// Edges must stay distinct from each other, and keep their endpoints.
+    /// Otherwise, it panics and leaves the graph untouched.
+    fn map_edge_tags<F>(&mut self, f: F)
+    where
+        F: FnMut(&EdgeId, &mut Self::Edge);
 }
 
 /// Interfaces to query vertices and edges in tagged graphs.
@@ -59,6 +79,21 @@ where
     fn in_edges(&self, vid: &VertexId) -> Box<dyn Iterator<Item = (EdgeId, &Self::Edge)> + '_>;
     /// Iterates over out-edges of a specified vertex.
     fn out_edges(&self, vid: &VertexId) -> Box<dyn Iterator<Item = (EdgeId, &Self::Edge)> + '_>;
+
+    /// Iterates edges connecting two specified customized vertices.
+    ///
+    /// It resolves both vertices into [VertexId]'s and then delegates to `edges_connecting`.
+    /// If either is absent, nothing is iterated.
+    fn edges_connecting_by_vertex(
+        &self,
+        source: &Self::Vertex,
+        sink: &Self::Vertex,
+    ) -> Box<dyn Iterator<Item = (EdgeId, &Self::Edge)> + '_> {
+        match (self.id_by_vertex(source), self.id_by_vertex(sink)) {
+            (Some(src), Some(snk)) => self.edges_connecting(&src, &snk),
+            _ => Box::new(std::iter::empty()),
+        }
+    }
 }
 
 /// Interfaces to add customized vertices and edges into tagged graphs.
@@ -75,6 +110,81 @@ where
     fn add_edge(&mut self, edge: Self::Edge) -> EdgeId;
     /// Updates an edge w.r.t. its [EdgeId].
     fn update_edge(&mut self, eid: EdgeId, edge: Self::Edge);
+
+    /// Gets the entry of the vertex equal to `key`, for in-place insertion like `HashMap::entry`.
+    fn vertex_entry(&mut self, key: Self::Vertex) -> VertexEntry<'_, Self>
+    where
+        Self: Sized,
+    {
+        let vid = self.id_by_vertex(&key);
+        VertexEntry {
+            graph: self,
+            key,
+            vid,
+        }
+    }
+}
+
+/// An entry of a vertex in a tagged graph, which may be absent.
+///
+/// It is made by [GrowableTaggedGraph::vertex_entry].
+pub struct VertexEntry<'a, G>
+where
+    G: TaggedGraph,
+{
+    graph: &'a mut G,
+    key: G::Vertex,
+    vid: Option<VertexId>,
+}
+
+impl<'a, G> VertexEntry<'a, G>
+where
+    G: GrowableTaggedGraph,
+    G::LowerGraph: GrowableGraph,
+{
+    /// The key of this entry.
+    pub fn key(&self) -> &G::Vertex {
+        &self.key
+    }
+
+    /// [VertexId] of the vertex if it is present.
+    pub fn id(&self) -> Option<VertexId> {
+        self.vid
+    }
+
+    /// Inserts the key as a vertex if it is absent, and returns its [VertexId].
+    ///
+    /// A present vertex is left untouched.
+    pub fn or_insert(self) -> VertexId {
+        let key = self.key;
+        match self.vid {
+            Some(vid) => vid,
+            None => self.graph.overwrite_vertex(key),
+        }
+    }
+
+    /// Inserts the vertex made by `f` if it is absent, and returns its [VertexId].
+    ///
+    /// `f` is called only when the vertex is absent.
+    ///
+    /// # Panics
+    ///
+    /// The vertex made by `f` must be equal to the key,
+    /// e.g., differing only in fields which `Eq` and `Hash` ignore.
+    /// Otherwise, it panics without inserting anything.
+    pub fn or_insert_with<F: FnOnce() -> G::Vertex>(self, f: F) -> VertexId
+    where
+        G::Vertex: PartialEq,
+    {
+        match self.vid {
+            Some(vid) => vid,
+            None => {
+                let vert = f();
+                assert!(vert == self.key, "the vertex made differs from the key");
+                self.graph.overwrite_vertex(vert)
+            }
+        }
+    }
 }
 
 /// Interfaces to remove edges from tagged graphs.
@@ -98,6 +208,20 @@ where
         &mut self,
         vid: &VertexId,
     ) -> Box<dyn Iterator<Item = (EdgeId, Self::Edge)> + '_>;
+
+    /// Removes a vertex and edges connecting to it,
+    /// and returns the vertex along with these edges.
+    ///
+    /// If the vertex is absent, it returns `None`.
+    #[allow(clippy::type_complexity)]
+    fn take_vertex(&mut self, vid: &VertexId) -> Option<(Self::Vertex, Vec<(EdgeId, Self::Edge)>)>
+    where
+        Self::Vertex: Clone,
+    {
+        let vert = self.vertex_by_id(vid)?.clone();
+        let edges = self.remove_vertex(vid).collect();
+        Some((vert, edges))
+    }
 }
 
 /// A trait for customized edges.
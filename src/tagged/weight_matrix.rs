@@ -0,0 +1,90 @@
+use crate::graph::*;
+use crate::tagged::{Edge as _Edge, QueryableTaggedGraph};
+
+/// A trait with default implementation for exporting a tagged graph as a weighted adjacency matrix.
+pub trait ToWeightMatrix
+where
+    Self: QueryableTaggedGraph + DirectedOrNot,
+    Self::LowerGraph: QueryableGraph,
+{
+    /// Exports the graph as a square matrix of edge weights.
+    ///
+    /// It returns vertices in ascending order of their ID's,
+    /// and a matrix whose `[i][j]` cell is the weight, measured by `f`,
+    /// of the edge from the `i`-th vertex to the `j`-th.
+    /// Among parallel edges, the minimal weight wins.
+    /// Cells without any edge are `None`.
+    /// For undirected graphs, the matrix is symmetric.
+    ///
+    /// Like [ToAdjacencyMatrix::to_adjacency_matrix], it takes O(V^2) memory.
+    fn to_weight_matrix<F>(&self, f: F) -> (Vec<VertexId>, Vec<Vec<Option<f64>>>)
+    where
+        F: Fn(&Self::Edge) -> f64,
+    {
+        let mut order: Vec<_> = self.iter_vertices().map(|(vid, _)| vid).collect();
+        order.sort();
+        let mut matrix = vec![vec![None; order.len()]; order.len()];
+        let mut fill = |src: usize, snk: usize, w: f64| {
+            let cell: &mut Option<f64> = &mut matrix[src][snk];
+            match cell {
+                Some(old) if *old <= w => {}
+                _ => *cell = Some(w),
+            }
+        };
+        for (_, e) in self.iter_edges() {
+            let src = order.binary_search(&e.source()).unwrap();
+            let snk = order.binary_search(&e.sink()).unwrap();
+            let w = f(e);
+            fill(src, snk, w);
+            if !Self::DIRECTED_OR_NOT {
+                fill(snk, src, w);
+            }
+        }
+        (order, matrix)
+    }
+}
+
+impl<G> ToWeightMatrix for G
+where
+    G: QueryableTaggedGraph + DirectedOrNot,
+    G::LowerGraph: QueryableGraph,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tagged::test_edges::{build_weighted, WeightedEdge};
+    use crate::tagged::NaiveTaggedGraph;
+
+    #[test]
+    fn directed() {
+        let (g, vs) = build_weighted::<NaiveTaggedGraph<usize, WeightedEdge>>();
+        let (order, matrix) = g.to_weight_matrix(|e| e.weight as f64);
+        assert_eq!(order, vs.to_vec());
+        assert_eq!(
+            matrix,
+            vec![
+                vec![None, Some(2.0), None],
+                vec![None, None, Some(5.0)],
+                vec![None, None, Some(1.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn undirected() {
+        let (g, vs) =
+            build_weighted::<NaiveTaggedGraph<usize, WeightedEdge, undirected::TreeBackedGraph>>();
+        let (order, matrix) = g.to_weight_matrix(|e| e.weight as f64);
+        assert_eq!(order, vs.to_vec());
+        assert_eq!(
+            matrix,
+            vec![
+                vec![None, Some(2.0), None],
+                vec![Some(2.0), None, Some(5.0)],
+                vec![None, Some(5.0), Some(1.0)],
+            ]
+        );
+    }
+}
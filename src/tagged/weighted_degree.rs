@@ -0,0 +1,79 @@
+use crate::graph::*;
+use crate::tagged::QueryableTaggedGraph;
+
+/// A trait with default implementation for weighted degrees, a.k.a. strengths, of vertices in tagged graphs.
+pub trait WeightedDegree
+where
+    Self: QueryableTaggedGraph + DirectedOrNot,
+    Self::LowerGraph: QueryableGraph,
+{
+    /// Sum of weights, measured by `f`, of out-edges of `vid`.
+    fn weighted_out_degree<F>(&self, vid: &VertexId, f: F) -> f64
+    where
+        F: Fn(&Self::Edge) -> f64,
+    {
+        self.out_edges(vid).map(|(_, e)| f(e)).sum()
+    }
+
+    /// Sum of weights, measured by `f`, of in-edges of `vid`.
+    fn weighted_in_degree<F>(&self, vid: &VertexId, f: F) -> f64
+    where
+        F: Fn(&Self::Edge) -> f64,
+    {
+        self.in_edges(vid).map(|(_, e)| f(e)).sum()
+    }
+
+    /// Sum of weights, measured by `f`, of edges connecting to `vid`.
+    ///
+    /// * For directed graphs, it sums up both weighted in-degree and weighted out-degree.
+    ///   Thus, self-loops count twice.
+    /// * For undirected graphs, it is the same as the weighted out-degree.
+    ///   Thus, self-loops count once.
+    fn weighted_degree<F>(&self, vid: &VertexId, f: F) -> f64
+    where
+        F: Fn(&Self::Edge) -> f64,
+    {
+        if Self::DIRECTED_OR_NOT {
+            self.weighted_in_degree(vid, &f) + self.weighted_out_degree(vid, &f)
+        } else {
+            self.weighted_out_degree(vid, f)
+        }
+    }
+}
+
+impl<G> WeightedDegree for G
+where
+    G: QueryableTaggedGraph + DirectedOrNot,
+    G::LowerGraph: QueryableGraph,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tagged::test_edges::{build_weighted, WeightedEdge};
+    use crate::tagged::NaiveTaggedGraph;
+
+    #[test]
+    fn directed() {
+        let (g, [v0, v1, v2]) = build_weighted::<NaiveTaggedGraph<usize, WeightedEdge>>();
+        let f = |e: &WeightedEdge| e.weight as f64;
+        assert_eq!(g.weighted_out_degree(&v0, f), 5.0);
+        assert_eq!(g.weighted_in_degree(&v0, f), 0.0);
+        assert_eq!(g.weighted_out_degree(&v1, f), 5.0);
+        assert_eq!(g.weighted_in_degree(&v1, f), 5.0);
+        assert_eq!(g.weighted_degree(&v1, f), 10.0);
+        assert_eq!(g.weighted_degree(&v2, f), 7.0);
+    }
+
+    #[test]
+    fn undirected() {
+        let (g, [v0, v1, v2]) =
+            build_weighted::<NaiveTaggedGraph<usize, WeightedEdge, undirected::TreeBackedGraph>>();
+        let f = |e: &WeightedEdge| e.weight as f64;
+        assert_eq!(g.weighted_degree(&v0, f), 5.0);
+        assert_eq!(g.weighted_degree(&v1, f), 10.0);
+        assert_eq!(g.weighted_in_degree(&v1, f), 10.0);
+        assert_eq!(g.weighted_degree(&v2, f), 6.0);
+    }
+}